@@ -190,6 +190,7 @@ fn default_config() -> cbindgen::Config {
             ("KeyEventArg".into(), "KeyEvent".into()),
             ("PointerEventArg".into(), "PointerEvent".into()),
             ("PointerScrollEventArg".into(), "PointerScrollEvent".into()),
+            ("SwipeDirectionArg".into(), "SwipeDirection".into()),
             ("PointArg".into(), "slint::LogicalPosition".into()),
             ("FloatArg".into(), "float".into()),
             ("Coord".into(), "float".into()),
@@ -249,6 +250,7 @@ fn gen_corelib(
         "ClippedImage",
         "TouchArea",
         "FocusScope",
+        "GestureArea",
         "Flickable",
         "Text",
         "Path",
@@ -282,6 +284,7 @@ fn gen_corelib(
         "PointerEventButton",
         "PointerEvent",
         "PointerScrollEvent",
+        "SwipeDirection",
         "Rect",
         "SortOrder",
         "BitmapFont",
@@ -331,7 +334,9 @@ fn gen_corelib(
         "KeyEventArg",
         "PointerEventArg",
         "PointerScrollEventArg",
+        "SwipeDirectionArg",
         "PointArg",
+        "FloatArg",
         "Point",
         "slint_color_brighter",
         "slint_color_darker",
@@ -478,6 +483,9 @@ fn gen_corelib(
             "slint_windowrc_dark_color_scheme",
             "slint_windowrc_dispatch_pointer_event",
             "slint_windowrc_dispatch_key_event",
+            "slint_windowrc_dispatch_key_event_with_details",
+            "slint_windowrc_dispatch_input_method_preedit_event",
+            "slint_windowrc_dispatch_input_method_commit_event",
             "slint_windowrc_dispatch_event",
             "slint_new_path_elements",
             "slint_new_path_events",
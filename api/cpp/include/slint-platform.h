@@ -6,6 +6,8 @@
 #include "slint.h"
 
 #include <cassert>
+#include <ranges>
+#include <span>
 #include <utility>
 
 struct xcb_connection_t;
@@ -536,21 +538,66 @@ public:
     /// The region may be composed of multiple sub-regions.
     struct PhysicalRegion
     {
+        /// A rectangle of the region
+        struct Rect
+        {
+            /// The origin of the rectangle
+            PhysicalPosition origin;
+            /// The size of the rectangle
+            PhysicalSize size;
+        };
+
         /// Returns the size of the bounding box of this region.
         PhysicalSize bounding_box_size() const
         {
-            return PhysicalSize({ uint32_t(inner.width), uint32_t(inner.height) });
+            auto r = bounding_rect();
+            return PhysicalSize({ uint32_t(r.width), uint32_t(r.height) });
         }
         /// Returns the origin of the bounding box of this region.
         PhysicalPosition bounding_box_origin() const
         {
-            return PhysicalPosition({ inner.x, inner.y });
+            auto r = bounding_rect();
+            return PhysicalPosition({ r.x, r.y });
+        }
+
+        /// Returns a view on the rectangles of this region. The rectangles don't overlap.
+        ///
+        /// It can be used like so:
+        /// ```cpp
+        /// for (auto [origin, size] : region.rectangles()) {
+        ///     // Send the pixels of that rectangle to the screen
+        /// }
+        /// ```
+        auto rectangles() const
+        {
+            return std::span(inner.rectangles, inner.count)
+                    | std::views::transform([](const cbindgen_private::types::IntRect &r) {
+                          return Rect { PhysicalPosition({ r.x, r.y }),
+                                        PhysicalSize({ uint32_t(r.width), uint32_t(r.height) }) };
+                      });
         }
 
     private:
-        cbindgen_private::types::IntRect inner;
+        cbindgen_private::types::IntRect bounding_rect() const
+        {
+            if (inner.count == 0) {
+                return {};
+            }
+            int32_t x1 = inner.rectangles[0].x, y1 = inner.rectangles[0].y;
+            int32_t x2 = x1 + inner.rectangles[0].width, y2 = y1 + inner.rectangles[0].height;
+            for (std::size_t i = 1; i < inner.count; ++i) {
+                const auto &r = inner.rectangles[i];
+                x1 = std::min(x1, r.x);
+                y1 = std::min(y1, r.y);
+                x2 = std::max(x2, r.x + r.width);
+                y2 = std::max(y2, r.y + r.height);
+            }
+            return { x1, y1, x2 - x1, y2 - y1 };
+        }
+
+        cbindgen_private::PhysicalRegion inner;
         friend class SoftwareRenderer;
-        PhysicalRegion(cbindgen_private::types::IntRect inner) : inner(inner) { }
+        PhysicalRegion(cbindgen_private::PhysicalRegion inner) : inner(inner) { }
     };
 
     /// This enum describes which parts of the buffer passed to the SoftwareRenderer may be
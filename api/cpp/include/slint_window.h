@@ -18,6 +18,11 @@ class SoftwareRenderer;
 }
 #endif
 
+namespace platform {
+/// The state of the modifier keys (alt, control, shift, and meta) when a key event occurs.
+using cbindgen_private::KeyboardModifiers;
+}
+
 namespace private_api {
 /// Internal function that checks that the API that must be called from the main
 /// thread is indeed called from the main thread, or abort the program otherwise
@@ -344,6 +349,70 @@ public:
                 &inner.handle(), cbindgen_private::KeyEventType::KeyReleased, &text);
     }
 
+    /// Dispatch a key press event to the scene, with the details reported by the windowing system.
+    ///
+    /// Use this when you're implementing your own backend and want to forward user input events.
+    ///
+    /// The \a text is the unicode representation of the key. The \a modifiers are the modifier
+    /// keys held down and are used instead of the modifiers that Slint tracks from the key events
+    /// of the modifier keys. The \a physical_key names the key independent of the keyboard
+    /// layout, like the `code` of the W3C UI Events specification (for example "KeyZ"), or is
+    /// empty. Set \a repeat if the event is an automatic repetition of a key held down.
+    void dispatch_key_press_event(const SharedString &text,
+                                  const platform::KeyboardModifiers &modifiers,
+                                  const SharedString &physical_key, bool repeat = false)
+    {
+        private_api::assert_main_thread();
+        cbindgen_private::slint_windowrc_dispatch_key_event_with_details(
+                &inner.handle(), cbindgen_private::KeyEventType::KeyPressed, &text, modifiers,
+                &physical_key, repeat);
+    }
+
+    /// Dispatch a key release event to the scene, with the details reported by the windowing
+    /// system.
+    ///
+    /// Use this when you're implementing your own backend and want to forward user input events.
+    ///
+    /// See dispatch_key_press_event() for the meaning of the \a text, \a modifiers, and
+    /// \a physical_key.
+    void dispatch_key_release_event(const SharedString &text,
+                                    const platform::KeyboardModifiers &modifiers,
+                                    const SharedString &physical_key)
+    {
+        private_api::assert_main_thread();
+        cbindgen_private::slint_windowrc_dispatch_key_event_with_details(
+                &inner.handle(), cbindgen_private::KeyEventType::KeyReleased, &text, modifiers,
+                &physical_key, false);
+    }
+
+    /// Dispatch an event of the input method that changed the text it's composing, also called
+    /// the pre-edit text. The focused text input field shows it at its cursor position, until the
+    /// input method commits the text or the field loses the focus.
+    ///
+    /// Use this when you're implementing your own backend and want to forward input method events.
+    ///
+    /// The \a text replaces the previous pre-edit text; an empty \a text cancels the composition.
+    /// \a cursor_start and \a cursor_end are the positions, in bytes within \a text, of the
+    /// selection within the pre-edit text. The cursor is shown at \a cursor_end.
+    void dispatch_input_method_preedit_event(const SharedString &text, std::size_t cursor_start,
+                                             std::size_t cursor_end)
+    {
+        private_api::assert_main_thread();
+        cbindgen_private::slint_windowrc_dispatch_input_method_preedit_event(
+                &inner.handle(), &text, cursor_start, cursor_end);
+    }
+
+    /// Dispatch an event of the input method that finished composing. The focused text input
+    /// field replaces the pre-edit text with \a text and inserts it at its cursor position.
+    ///
+    /// Use this when you're implementing your own backend and want to forward input method events.
+    void dispatch_input_method_commit_event(const SharedString &text)
+    {
+        private_api::assert_main_thread();
+        cbindgen_private::slint_windowrc_dispatch_input_method_commit_event(&inner.handle(),
+                                                                            &text);
+    }
+
     /// Dispatches a pointer or mouse press event to the scene.
     ///
     /// Use this function when you're implementing your own backend and want to forward user
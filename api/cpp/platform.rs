@@ -338,6 +338,29 @@ mod software_renderer {
     use i_slint_core::graphics::{IntRect, Rgb8Pixel};
     use i_slint_core::software_renderer::{RepaintBufferType, Rgb565Pixel, SoftwareRenderer};
 
+    /// The rectangles of the region returned by the rendering functions
+    #[repr(C)]
+    pub struct PhysicalRegion {
+        rectangles: [IntRect; 3],
+        count: usize,
+    }
+
+    impl From<i_slint_core::software_renderer::PhysicalRegion> for PhysicalRegion {
+        fn from(region: i_slint_core::software_renderer::PhysicalRegion) -> Self {
+            let mut result = PhysicalRegion { rectangles: [IntRect::zero(); 3], count: 0 };
+            for (orig, size) in region.iter() {
+                result.rectangles[result.count] = i_slint_core::graphics::euclid::rect(
+                    orig.x,
+                    orig.y,
+                    size.width as i32,
+                    size.height as i32,
+                );
+                result.count += 1;
+            }
+            result
+        }
+    }
+
     #[no_mangle]
     pub unsafe extern "C" fn slint_software_renderer_new(
         buffer_age: u32,
@@ -363,12 +386,10 @@ mod software_renderer {
         buffer: *mut Rgb8Pixel,
         buffer_len: usize,
         pixel_stride: usize,
-    ) -> IntRect {
+    ) -> PhysicalRegion {
         let buffer = core::slice::from_raw_parts_mut(buffer, buffer_len);
         let renderer = &*(r as *const SoftwareRenderer);
-        let r = renderer.render(buffer, pixel_stride);
-        let (orig, size) = (r.bounding_box_origin(), r.bounding_box_size());
-        i_slint_core::graphics::euclid::rect(orig.x, orig.y, size.width as i32, size.height as i32)
+        renderer.render(buffer, pixel_stride).into()
     }
 
     #[no_mangle]
@@ -377,12 +398,10 @@ mod software_renderer {
         buffer: *mut u16,
         buffer_len: usize,
         pixel_stride: usize,
-    ) -> IntRect {
+    ) -> PhysicalRegion {
         let buffer = core::slice::from_raw_parts_mut(buffer as *mut Rgb565Pixel, buffer_len);
         let renderer = &*(r as *const SoftwareRenderer);
-        let r = renderer.render(buffer, pixel_stride);
-        let (orig, size) = (r.bounding_box_origin(), r.bounding_box_size());
-        i_slint_core::graphics::euclid::rect(orig.x, orig.y, size.width as i32, size.height as i32)
+        renderer.render(buffer, pixel_stride).into()
     }
 
     #[cfg(feature = "experimental")]
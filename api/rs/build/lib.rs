@@ -56,10 +56,12 @@ fn main() {
 use std::path::Path;
 
 use i_slint_compiler::diagnostics::BuildDiagnostics;
+pub use i_slint_compiler::diagnostics::DiagnosticFormat;
 
 /// The structure for configuring aspects of the compilation of `.slint` markup files to Rust.
 pub struct CompilerConfiguration {
     config: i_slint_compiler::CompilerConfiguration,
+    diagnostic_format: DiagnosticFormat,
 }
 
 /// How should the slint compiler embed images and fonts
@@ -83,6 +85,7 @@ fn default() -> Self {
             config: i_slint_compiler::CompilerConfiguration::new(
                 i_slint_compiler::generator::OutputFormat::Rust,
             ),
+            diagnostic_format: DiagnosticFormat::Human,
         }
     }
 }
@@ -99,7 +102,7 @@ pub fn new() -> Self {
     pub fn with_include_paths(self, include_paths: Vec<std::path::PathBuf>) -> Self {
         let mut config = self.config;
         config.include_paths = include_paths;
-        Self { config }
+        Self { config, ..self }
     }
 
     /// Create a new configuration that sets the library paths used for looking up
@@ -132,7 +135,7 @@ pub fn with_include_paths(self, include_paths: Vec<std::path::PathBuf>) -> Self
     pub fn with_library_paths(self, library_paths: HashMap<String, std::path::PathBuf>) -> Self {
         let mut config = self.config;
         config.library_paths = library_paths;
-        Self { config }
+        Self { config, ..self }
     }
 
     /// Create a new configuration that selects the style to be used for widgets.
@@ -140,7 +143,7 @@ pub fn with_library_paths(self, library_paths: HashMap<String, std::path::PathBu
     pub fn with_style(self, style: String) -> Self {
         let mut config = self.config;
         config.style = Some(style);
-        Self { config }
+        Self { config, ..self }
     }
 
     /// Selects how the resources such as images and font are processed.
@@ -160,7 +163,28 @@ pub fn embed_resources(self, kind: EmbedResourcesKind) -> Self {
                 i_slint_compiler::EmbedResourcesKind::EmbedTextures
             }
         };
-        Self { config }
+        Self { config, ..self }
+    }
+
+    /// Selects how the diagnostics are printed and returned in [`CompileError::CompileError`].
+    ///
+    /// Use [`DiagnosticFormat::Json`] to let the build script, or the tools that run it,
+    /// process the errors and warnings. The errors are then printed on stderr as a JSON array,
+    /// the warnings are forwarded to cargo as one `cargo:warning` per JSON object, and
+    /// [`CompileError::CompileError`] contains one JSON object per diagnostic:
+    /// ```rust,no_run
+    /// let config = slint_build::CompilerConfiguration::new()
+    ///     .with_diagnostic_format(slint_build::DiagnosticFormat::Json);
+    /// if let Err(slint_build::CompileError::CompileError(errors)) =
+    ///     slint_build::compile_with_config("ui/hello.slint", config)
+    /// {
+    ///     // Each error is a JSON object
+    ///     panic!("{}", errors.join("\n"));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_diagnostic_format(self, format: DiagnosticFormat) -> Self {
+        Self { diagnostic_format: format, ..self }
     }
 }
 
@@ -322,13 +346,12 @@ pub fn compile_with_config(
     let path = Path::new(&env::var_os("CARGO_MANIFEST_DIR").ok_or(CompileError::NotRunViaCargo)?)
         .join(path.as_ref());
 
+    let diagnostic_format = config.diagnostic_format;
     let mut diag = BuildDiagnostics::default();
     let syntax_node = i_slint_compiler::parser::parse_file(&path, &mut diag);
 
     if diag.has_error() {
-        let vec = diag.to_string_vec();
-        diag.print();
-        return Err(CompileError::CompileError(vec));
+        return Err(report_errors(diag, diagnostic_format));
     }
 
     let mut compiler_config = config.config;
@@ -341,9 +364,7 @@ pub fn compile_with_config(
         spin_on::spin_on(i_slint_compiler::compile_syntax_node(syntax_node, diag, compiler_config));
 
     if diag.has_error() {
-        let vec = diag.to_string_vec();
-        diag.print();
-        return Err(CompileError::CompileError(vec));
+        return Err(report_errors(diag, diagnostic_format));
     }
 
     let output_file_path = Path::new(&env::var_os("OUT_DIR").ok_or(CompileError::NotRunViaCargo)?)
@@ -365,11 +386,16 @@ pub fn compile_with_config(
     }
 
     // print warnings
-    diag.diagnostics_as_string().lines().for_each(|w| {
-        if !w.is_empty() {
-            println!("cargo:warning={}", w.strip_prefix("warning: ").unwrap_or(w))
+    match diagnostic_format {
+        DiagnosticFormat::Human => diag.diagnostics_as_string().lines().for_each(|w| {
+            if !w.is_empty() {
+                println!("cargo:warning={}", w.strip_prefix("warning: ").unwrap_or(w))
+            }
+        }),
+        DiagnosticFormat::Json => {
+            diag.to_json_vec().iter().for_each(|w| println!("cargo:warning={}", w))
         }
-    });
+    }
 
     write!(code_formatter, "{}", generated).map_err(CompileError::SaveError)?;
     println!("cargo:rerun-if-changed={}", path.display());
@@ -390,6 +416,16 @@ pub fn compile_with_config(
     Ok(())
 }
 
+/// Print the diagnostics on stderr and return them in a [`CompileError::CompileError`]
+fn report_errors(diag: BuildDiagnostics, format: DiagnosticFormat) -> CompileError {
+    let vec = match format {
+        DiagnosticFormat::Human => diag.to_string_vec(),
+        DiagnosticFormat::Json => diag.to_json_vec(),
+    };
+    diag.print_with_format(format);
+    CompileError::CompileError(vec)
+}
+
 /// This function is for use the application's build script, in order to print any device specific
 /// build flags reported by the backend
 pub fn print_rustc_flags() -> std::io::Result<()> {
@@ -36,8 +36,28 @@ fn from_rgb(_red: u8, _green: u8, _blue: u8) -> Self {
 
 #[track_caller]
 fn do_test_render_region(renderer: &SoftwareRenderer, x: i32, y: i32, x2: i32, y2: i32) {
+    do_test_render_rects(renderer, &[(x, y, x2, y2)]);
+}
+
+/// Check that the rendered region is made of exactly the given `(x, y, x2, y2)` rectangles
+#[track_caller]
+fn do_test_render_rects(renderer: &SoftwareRenderer, rects: &[(i32, i32, i32, i32)]) {
     let mut buffer = vec![TestPixel(false); 500 * 500];
     let r = renderer.render(buffer.as_mut_slice(), 500);
+
+    let mut actual = r
+        .iter()
+        .map(|(o, s)| (o.x, o.y, o.x + s.width as i32, o.y + s.height as i32))
+        .collect::<Vec<_>>();
+    actual.sort();
+    let mut expected = rects.to_vec();
+    expected.sort();
+    assert_eq!(actual, expected);
+
+    let x = rects.iter().map(|r| r.0).min().unwrap();
+    let y = rects.iter().map(|r| r.1).min().unwrap();
+    let x2 = rects.iter().map(|r| r.2).max().unwrap();
+    let y2 = rects.iter().map(|r| r.3).max().unwrap();
     assert_eq!(r.bounding_box_size(), PhysicalSize { width: (x2 - x) as _, height: (y2 - y) as _ });
     assert_eq!(r.bounding_box_origin(), PhysicalPosition { x, y });
 
@@ -45,7 +65,9 @@ fn do_test_render_region(renderer: &SoftwareRenderer, x: i32, y: i32, x2: i32, y
         for px in 0..500 {
             assert_eq!(
                 buffer[py * 500 + px].0,
-                (x..x2).contains(&(px as i32)) && (y..y2).contains(&(py as i32)),
+                rects.iter().any(|&(x, y, x2, y2)| {
+                    (x..x2).contains(&(px as i32)) && (y..y2).contains(&(py as i32))
+                }),
                 "unexpected value at {px},{py}"
             )
         }
@@ -97,7 +119,13 @@ fn simple() {
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
     ui.set_c(slint::Color::from_rgb_u8(45, 12, 13));
     assert!(window.draw_if_needed(|renderer| {
-        do_test_render_region(renderer, 10 + 5, 19 + 8, 10 + 50 + 15, 19 + 80 + 13);
+        do_test_render_rects(
+            renderer,
+            &[
+                (10 + 5, 19 + 80, 10 + 5 + 12, 19 + 80 + 13),
+                (10 + 50, 19 + 8, 10 + 50 + 15, 19 + 8 + 17),
+            ],
+        );
     }));
     ui.set_c(slint::Color::from_rgb_u8(45, 12, 13));
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
@@ -143,12 +171,24 @@ fn visibility() {
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
     ui.set_c(false);
     assert!(window.draw_if_needed(|renderer| {
-        do_test_render_region(renderer, 10 + 5, 19 + 8, 10 + 50 + 15, 19 + 80 + 13);
+        do_test_render_rects(
+            renderer,
+            &[
+                (10 + 5, 19 + 80, 10 + 5 + 12, 19 + 80 + 13),
+                (10 + 50, 19 + 8, 10 + 50 + 15, 19 + 8 + 17),
+            ],
+        );
     }));
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
     ui.set_c(true);
     assert!(window.draw_if_needed(|renderer| {
-        do_test_render_region(renderer, 10 + 5, 19 + 8, 10 + 50 + 15, 19 + 80 + 13);
+        do_test_render_rects(
+            renderer,
+            &[
+                (10 + 5, 19 + 80, 10 + 5 + 12, 19 + 80 + 13),
+                (10 + 50, 19 + 8, 10 + 50 + 15, 19 + 8 + 17),
+            ],
+        );
     }));
     assert!(!window.draw_if_needed(|_| { unreachable!() }));
 }
@@ -41,6 +41,10 @@ slint_init(slint_wasm_data).then((_) => {
         return the_lsp.server_initialize_result(params.capabilities);
     });
 
+    connection.onInitialized(async () => {
+        await the_lsp.startup();
+    });
+
     connection.onRequest(async (method, params, token) => {
         return await the_lsp.handle_request(token, method, params);
     });
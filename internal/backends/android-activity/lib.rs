@@ -193,9 +193,7 @@ fn internal(
     ) -> Option<&dyn i_slint_core::window::WindowAdapterInternal> {
         Some(self)
     }
-}
 
-impl i_slint_core::window::WindowAdapterInternal for AndroidWindowAdapter {
     fn input_method_request(&self, request: i_slint_core::window::InputMethodRequest) {
         let props = match request {
             i_slint_core::window::InputMethodRequest::Enable(props) => {
@@ -227,6 +225,8 @@ fn input_method_request(&self, request: i_slint_core::window::InputMethodRequest
     }
 }
 
+impl i_slint_core::window::WindowAdapterInternal for AndroidWindowAdapter {}
+
 impl AndroidWindowAdapter {
     fn process_event(&self, event: &PollEvent<'_>) -> Result<ControlFlow<()>, PlatformError> {
         match event {
@@ -413,10 +413,27 @@ fn position_for_event(motion_event: &MotionEvent) -> PhysicalPosition {
 
 fn map_key_event(key_event: &android_activity::input::KeyEvent) -> Option<WindowEvent> {
     let text = map_key_code(key_event.key_code())?;
+    let meta_state = key_event.meta_state();
+    let modifiers = i_slint_core::platform::KeyboardModifiers {
+        alt: meta_state.alt_on(),
+        control: meta_state.ctrl_on(),
+        shift: meta_state.shift_on(),
+        meta: meta_state.meta_on(),
+    };
+    let physical_key = SharedString::default();
     match key_event.action() {
-        KeyAction::Down => Some(WindowEvent::KeyPressed { text }),
-        KeyAction::Up => Some(WindowEvent::KeyReleased { text }),
-        KeyAction::Multiple => Some(WindowEvent::KeyPressed { text }),
+        KeyAction::Down => Some(WindowEvent::key_pressed_with_details(
+            text,
+            modifiers,
+            physical_key,
+            key_event.repeat_count() > 0,
+        )),
+        KeyAction::Up => {
+            Some(WindowEvent::key_released_with_details(text, modifiers, physical_key))
+        }
+        KeyAction::Multiple => {
+            Some(WindowEvent::key_pressed_with_details(text, modifiers, physical_key, true))
+        }
         _ => None,
     }
 }
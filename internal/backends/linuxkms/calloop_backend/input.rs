@@ -17,7 +17,8 @@
 use input::LibinputInterface;
 
 use input::event::keyboard::{KeyState, KeyboardEventTrait};
-use input::event::touch::TouchEventPosition;
+use input::event::pointer::{Axis, PointerScrollEvent};
+use input::event::touch::{TouchEventPosition, TouchEventSlot};
 use xkbcommon::*;
 
 struct SeatWrap {
@@ -52,11 +53,16 @@ fn close_restricted(&mut self, fd: OwnedFd) {
     }
 }
 
+/// The number of logical pixels scrolled by one detent of a mouse wheel, as in the winit backend
+const SCROLL_WHEEL_LINE_HEIGHT: f32 = 60.;
+
 pub struct LibInputHandler<'a> {
     libinput: input::Libinput,
     token: Option<calloop::Token>,
     mouse_pos: Pin<Rc<Property<Option<LogicalPosition>>>>,
-    last_touch_pos: LogicalPosition,
+    /// The last position of the fingers touching the screen, by seat slot, as libinput doesn't
+    /// provide one when they are lifted
+    touch_points: HashMap<u32, LogicalPosition>,
     window: &'a i_slint_core::api::Window,
     keystate: Option<xkb::State>,
 }
@@ -80,7 +86,7 @@ pub fn init<T>(
             libinput,
             token: Default::default(),
             mouse_pos: mouse_pos_property.clone(),
-            last_touch_pos: Default::default(),
+            touch_points: Default::default(),
             window,
             keystate: Default::default(),
         };
@@ -93,6 +99,68 @@ pub fn init<T>(
     }
 }
 
+impl<'a> LibInputHandler<'a> {
+    fn dispatch_scroll(&self, delta_x: f32, delta_y: f32) {
+        if delta_x == 0. && delta_y == 0. {
+            return;
+        }
+        let position = self.mouse_pos.as_ref().get().unwrap_or_default();
+        self.window.dispatch_event(WindowEvent::PointerScrolled { position, delta_x, delta_y });
+    }
+
+    /// Forwards the fingers touching the screen, identified by their seat slot, to the window.
+    /// It delivers a single finger like the left button of a pointer, and recognizes the
+    /// gestures of several fingers.
+    fn process_touch_event(
+        &mut self,
+        touch_event: input::event::TouchEvent,
+        screen_size: i_slint_core::api::LogicalSize,
+    ) {
+        let event = match touch_event {
+            input::event::TouchEvent::Down(touch_down_event) => {
+                let slot = touch_down_event.seat_slot();
+                let position = LogicalPosition::new(
+                    touch_down_event.x_transformed(screen_size.width as u32) as _,
+                    touch_down_event.y_transformed(screen_size.height as u32) as _,
+                );
+                self.touch_points.insert(slot, position);
+                WindowEvent::TouchPressed { id: slot.into(), position }
+            }
+            input::event::TouchEvent::Motion(touch_motion_event) => {
+                let slot = touch_motion_event.seat_slot();
+                let position = LogicalPosition::new(
+                    touch_motion_event.x_transformed(screen_size.width as u32) as _,
+                    touch_motion_event.y_transformed(screen_size.height as u32) as _,
+                );
+                self.touch_points.insert(slot, position);
+                WindowEvent::TouchMoved { id: slot.into(), position }
+            }
+            input::event::TouchEvent::Up(touch_up_event) => {
+                let slot = touch_up_event.seat_slot();
+                let position = self.touch_points.remove(&slot).unwrap_or_default();
+                WindowEvent::TouchReleased { id: slot.into(), position }
+            }
+            input::event::TouchEvent::Cancel(touch_cancel_event) => {
+                let slot = touch_cancel_event.seat_slot();
+                self.touch_points.remove(&slot);
+                WindowEvent::TouchCancelled { id: slot.into() }
+            }
+            _ => return,
+        };
+        self.window.dispatch_event(event);
+    }
+}
+
+/// The delta of a touchpad or continuous scroll event in logical pixels, in the same direction as
+/// the other backends.
+fn scroll_delta(scroll_event: &impl PointerScrollEvent, axis: Axis) -> f32 {
+    if scroll_event.has_axis(axis) {
+        -scroll_event.scroll_value(axis) as f32
+    } else {
+        0.
+    }
+}
+
 impl<'a> calloop::EventSource for LibInputHandler<'a> {
     type Event = i_slint_core::platform::WindowEvent;
     type Metadata = ();
@@ -116,7 +184,7 @@ fn process_events<F>(
 
         let screen_size = self.window.size().to_logical(self.window.scale_factor());
 
-        for event in &mut self.libinput {
+        while let Some(event) = self.libinput.next() {
             match event {
                 input::Event::Pointer(pointer_event) => {
                     match pointer_event {
@@ -131,6 +199,8 @@ fn process_events<F>(
                             mouse_pos.y = (mouse_pos.y + motion_event.dy() as f32)
                                 .clamp(0., screen_size.height);
                             self.mouse_pos.set(Some(mouse_pos));
+                            // The mouse cursor is drawn by the renderer
+                            self.window.request_redraw();
                             let event = WindowEvent::PointerMoved { position: mouse_pos };
                             self.window.dispatch_event(event);
                         }
@@ -143,6 +213,8 @@ fn process_events<F>(
                                     as _,
                             };
                             self.mouse_pos.set(Some(mouse_pos));
+                            // The mouse cursor is drawn by the renderer
+                            self.window.request_redraw();
                             let event = WindowEvent::PointerMoved { position: mouse_pos };
                             self.window.dispatch_event(event);
                         }
@@ -165,40 +237,36 @@ fn process_events<F>(
                             };
                             self.window.dispatch_event(event);
                         }
-                        input::event::PointerEvent::ScrollWheel(_) => todo!(),
-                        input::event::PointerEvent::ScrollFinger(_) => todo!(),
-                        input::event::PointerEvent::ScrollContinuous(_) => todo!(),
-                        _ => {}
-                    }
-                }
-                input::Event::Touch(touch_event) => {
-                    if let Some(event) = match touch_event {
-                        input::event::TouchEvent::Down(touch_down_event) => {
-                            self.last_touch_pos = LogicalPosition::new(
-                                touch_down_event.x_transformed(screen_size.width as u32) as _,
-                                touch_down_event.y_transformed(screen_size.height as u32) as _,
+                        input::event::PointerEvent::ScrollWheel(scroll_event) => {
+                            // One detent of the wheel is 120
+                            let delta = |axis| {
+                                if scroll_event.has_axis(axis) {
+                                    -scroll_event.scroll_value_v120(axis) as f32 / 120.
+                                        * SCROLL_WHEEL_LINE_HEIGHT
+                                } else {
+                                    0.
+                                }
+                            };
+                            self.dispatch_scroll(delta(Axis::Horizontal), delta(Axis::Vertical));
+                        }
+                        input::event::PointerEvent::ScrollFinger(scroll_event) => {
+                            self.dispatch_scroll(
+                                scroll_delta(&scroll_event, Axis::Horizontal),
+                                scroll_delta(&scroll_event, Axis::Vertical),
                             );
-                            Some(WindowEvent::PointerPressed {
-                                position: self.last_touch_pos,
-                                button: PointerEventButton::Left,
-                            })
                         }
-                        input::event::TouchEvent::Up(..) => Some(WindowEvent::PointerReleased {
-                            position: self.last_touch_pos,
-                            button: PointerEventButton::Left,
-                        }),
-                        input::event::TouchEvent::Motion(touch_motion_event) => {
-                            self.last_touch_pos = LogicalPosition::new(
-                                touch_motion_event.x_transformed(screen_size.width as u32) as _,
-                                touch_motion_event.y_transformed(screen_size.height as u32) as _,
+                        input::event::PointerEvent::ScrollContinuous(scroll_event) => {
+                            self.dispatch_scroll(
+                                scroll_delta(&scroll_event, Axis::Horizontal),
+                                scroll_delta(&scroll_event, Axis::Vertical),
                             );
-                            Some(WindowEvent::PointerMoved { position: self.last_touch_pos })
                         }
-                        _ => None,
-                    } {
-                        self.window.dispatch_event(event);
+                        _ => {}
                     }
                 }
+                input::Event::Touch(touch_event) => {
+                    self.process_touch_event(touch_event, screen_size);
+                }
                 input::Event::Keyboard(input::event::KeyboardEvent::Key(key_event)) => {
                     // On Linux key codes have a fixed offset of 8: https://docs.rs/xkbcommon/0.6.0/xkbcommon/xkb/struct.Keycode.html
                     let key_code = xkb::Keycode::new(key_event.key() + 8);
@@ -247,9 +315,27 @@ fn process_events<F>(
                     }
 
                     if let Some(text) = map_key_sym(sym) {
+                        let modifiers = i_slint_core::platform::KeyboardModifiers {
+                            alt,
+                            control,
+                            shift: xkb_key_state
+                                .mod_name_is_active(xkb::MOD_NAME_SHIFT, xkb::STATE_MODS_EFFECTIVE),
+                            meta: xkb_key_state
+                                .mod_name_is_active(xkb::MOD_NAME_LOGO, xkb::STATE_MODS_EFFECTIVE),
+                        };
+                        let physical_key = map_physical_key(key_event.key()).into();
                         let event = match state {
-                            KeyState::Pressed => WindowEvent::KeyPressed { text },
-                            KeyState::Released => WindowEvent::KeyReleased { text },
+                            KeyState::Pressed => WindowEvent::key_pressed_with_details(
+                                text,
+                                modifiers,
+                                physical_key,
+                                false,
+                            ),
+                            KeyState::Released => WindowEvent::key_released_with_details(
+                                text,
+                                modifiers,
+                                physical_key,
+                            ),
                         };
                         self.window.dispatch_event(event);
                     }
@@ -298,6 +384,119 @@ fn unregister(&mut self, poll: &mut calloop::Poll) -> calloop::Result<()> {
     }
 }
 
+/// Maps the Linux input event code of a key to the name of the physical key, as defined by the
+/// `code` of keyboard events in the W3C UI Events specification.
+fn map_physical_key(code: u32) -> &'static str {
+    match code {
+        1 => "Escape",
+        2 => "Digit1",
+        3 => "Digit2",
+        4 => "Digit3",
+        5 => "Digit4",
+        6 => "Digit5",
+        7 => "Digit6",
+        8 => "Digit7",
+        9 => "Digit8",
+        10 => "Digit9",
+        11 => "Digit0",
+        12 => "Minus",
+        13 => "Equal",
+        14 => "Backspace",
+        15 => "Tab",
+        16 => "KeyQ",
+        17 => "KeyW",
+        18 => "KeyE",
+        19 => "KeyR",
+        20 => "KeyT",
+        21 => "KeyY",
+        22 => "KeyU",
+        23 => "KeyI",
+        24 => "KeyO",
+        25 => "KeyP",
+        26 => "BracketLeft",
+        27 => "BracketRight",
+        28 => "Enter",
+        29 => "ControlLeft",
+        30 => "KeyA",
+        31 => "KeyS",
+        32 => "KeyD",
+        33 => "KeyF",
+        34 => "KeyG",
+        35 => "KeyH",
+        36 => "KeyJ",
+        37 => "KeyK",
+        38 => "KeyL",
+        39 => "Semicolon",
+        40 => "Quote",
+        41 => "Backquote",
+        42 => "ShiftLeft",
+        43 => "Backslash",
+        44 => "KeyZ",
+        45 => "KeyX",
+        46 => "KeyC",
+        47 => "KeyV",
+        48 => "KeyB",
+        49 => "KeyN",
+        50 => "KeyM",
+        51 => "Comma",
+        52 => "Period",
+        53 => "Slash",
+        54 => "ShiftRight",
+        55 => "NumpadMultiply",
+        56 => "AltLeft",
+        57 => "Space",
+        58 => "CapsLock",
+        59 => "F1",
+        60 => "F2",
+        61 => "F3",
+        62 => "F4",
+        63 => "F5",
+        64 => "F6",
+        65 => "F7",
+        66 => "F8",
+        67 => "F9",
+        68 => "F10",
+        69 => "NumLock",
+        70 => "ScrollLock",
+        71 => "Numpad7",
+        72 => "Numpad8",
+        73 => "Numpad9",
+        74 => "NumpadSubtract",
+        75 => "Numpad4",
+        76 => "Numpad5",
+        77 => "Numpad6",
+        78 => "NumpadAdd",
+        79 => "Numpad1",
+        80 => "Numpad2",
+        81 => "Numpad3",
+        82 => "Numpad0",
+        83 => "NumpadDecimal",
+        86 => "IntlBackslash",
+        87 => "F11",
+        88 => "F12",
+        96 => "NumpadEnter",
+        97 => "ControlRight",
+        98 => "NumpadDivide",
+        99 => "PrintScreen",
+        100 => "AltRight",
+        102 => "Home",
+        103 => "ArrowUp",
+        104 => "PageUp",
+        105 => "ArrowLeft",
+        106 => "ArrowRight",
+        107 => "End",
+        108 => "ArrowDown",
+        109 => "PageDown",
+        110 => "Insert",
+        111 => "Delete",
+        119 => "Pause",
+        125 => "MetaLeft",
+        126 => "MetaRight",
+        127 => "ContextMenu",
+        _ => "",
+    }
+}
+
 fn map_key_sym(sym: xkb::Keysym) -> Option<SharedString> {
     macro_rules! keysym_to_string {
         ($($char:literal # $name:ident # $($_qt:ident)|* # $($_winit:ident $(($_pos:ident))?)|* # $($xkb:ident)|*;)*) => {
@@ -0,0 +1,91 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use std::cell::RefCell;
+
+use crate::DeviceOpener;
+use drm::control::Device;
+use i_slint_core::api::PhysicalSize as PhysicalWindowSize;
+use i_slint_core::platform::PlatformError;
+
+use super::drmoutput::{DrmOutput, SharedFd};
+
+/// A dumb buffer, which is memory that can be mapped and written to by the CPU, with a
+/// frame buffer for it so that it can be scanned out.
+struct DumbBuffer {
+    buffer: drm::control::dumbbuffer::DumbBuffer,
+    framebuffer: drm::control::framebuffer::Handle,
+    device: SharedFd,
+}
+
+impl DumbBuffer {
+    fn new(device: &SharedFd, width: u32, height: u32) -> Result<Self, PlatformError> {
+        let buffer = device
+            .create_dumb_buffer((width, height), drm::buffer::DrmFourcc::Xrgb8888, 32)
+            .map_err(|e| format!("Error creating dumb buffer ({width}x{height}): {e}"))?;
+        let framebuffer = device.add_framebuffer(&buffer, 24, 32).map_err(|e| {
+            device.destroy_dumb_buffer(buffer).ok();
+            format!("Error creating framebuffer for dumb buffer: {e}")
+        })?;
+        Ok(Self { buffer, framebuffer, device: device.clone() })
+    }
+}
+
+impl Drop for DumbBuffer {
+    fn drop(&mut self) {
+        self.device.destroy_framebuffer(self.framebuffer).ok();
+        self.device.destroy_dumb_buffer(self.buffer).ok();
+    }
+}
+
+/// A display that is rendered to by the CPU, with two dumb buffers that are swapped by page
+/// flipping.
+pub struct SoftwareBufferDisplay {
+    front_buffer: RefCell<DumbBuffer>,
+    back_buffer: RefCell<DumbBuffer>,
+    drm_output: DrmOutput,
+    pub size: PhysicalWindowSize,
+}
+
+impl SoftwareBufferDisplay {
+    pub fn new(device_opener: &DeviceOpener) -> Result<Self, PlatformError> {
+        let drm_output = DrmOutput::new(device_opener)?;
+        let (width, height) = drm_output.size()?;
+
+        let front_buffer = DumbBuffer::new(&drm_output.drm_device, width.get(), height.get())?;
+        let back_buffer = DumbBuffer::new(&drm_output.drm_device, width.get(), height.get())?;
+
+        Ok(Self {
+            front_buffer: RefCell::new(front_buffer),
+            back_buffer: RefCell::new(back_buffer),
+            drm_output,
+            size: PhysicalWindowSize::new(width.get(), height.get()),
+        })
+    }
+
+    /// Maps the back buffer into memory and calls the callback with its pixels, in the
+    /// XRGB8888 format, and its stride in bytes.
+    pub fn map_back_buffer(
+        &self,
+        callback: &mut dyn FnMut(&mut [u8], usize),
+    ) -> Result<(), PlatformError> {
+        let mut back_buffer = self.back_buffer.borrow_mut();
+        let DumbBuffer { buffer, device, .. } = &mut *back_buffer;
+        let pitch = drm::buffer::Buffer::pitch(buffer) as usize;
+        let mut mapping = device
+            .map_dumb_buffer(buffer)
+            .map_err(|e| format!("Error mapping dumb buffer into memory: {e}"))?;
+        callback(mapping.as_mut(), pitch);
+        Ok(())
+    }
+}
+
+impl super::Presenter for SoftwareBufferDisplay {
+    fn present(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // The back buffer was rendered to and becomes the front buffer. Once the page flip
+        // is complete, the previous front buffer isn't scanned out anymore and is re-used
+        // for the next frame.
+        self.front_buffer.swap(&self.back_buffer);
+        self.drm_output.present(self.front_buffer.borrow().framebuffer)
+    }
+}
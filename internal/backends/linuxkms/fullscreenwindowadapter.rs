@@ -9,7 +9,6 @@
 
 use i_slint_core::api::{LogicalPosition, PhysicalSize as PhysicalWindowSize};
 use i_slint_core::graphics::Image;
-use i_slint_core::item_rendering::ItemRenderer;
 use i_slint_core::platform::WindowEvent;
 use i_slint_core::slice::Slice;
 use i_slint_core::Property;
@@ -17,13 +16,35 @@
 
 pub trait FullscreenRenderer {
     fn as_core_renderer(&self) -> &dyn i_slint_core::renderer::Renderer;
-    fn render_and_present(
-        &self,
-        draw_mouse_cursor_callback: &dyn Fn(&mut dyn ItemRenderer),
-    ) -> Result<(), PlatformError>;
+    fn render_and_present(&self, mouse_cursor: Option<&MouseCursor>) -> Result<(), PlatformError>;
     fn size(&self) -> PhysicalWindowSize;
 }
 
+/// The mouse cursor that the renderers draw on top of the scene
+pub struct MouseCursor {
+    /// The position of the mouse, where the top left corner of the image is drawn
+    pub position: LogicalPosition,
+    /// The scale factor of the window, to map the position to physical pixels
+    pub scale_factor: f32,
+    pub image: Image,
+}
+
+impl MouseCursor {
+    /// Draws the cursor with the item renderer of the renderers that provide one
+    #[cfg(any(
+        feature = "renderer-skia-opengl",
+        feature = "renderer-skia-vulkan",
+        feature = "renderer-femtovg"
+    ))]
+    pub fn draw(&self, item_renderer: &mut dyn i_slint_core::item_rendering::ItemRenderer) {
+        item_renderer.save_state();
+        item_renderer
+            .translate(i_slint_core::lengths::logical_point_from_api(self.position).to_vector());
+        item_renderer.draw_image_direct(self.image.clone());
+        item_renderer.restore_state();
+    }
+}
+
 pub struct FullscreenWindowAdapter {
     window: i_slint_core::api::Window,
     renderer: Box<dyn FullscreenRenderer>,
@@ -73,16 +94,12 @@ pub fn render_if_needed(
         mouse_position: Pin<&Property<Option<LogicalPosition>>>,
     ) -> Result<(), PlatformError> {
         if self.needs_redraw.replace(false) {
-            self.renderer.render_and_present(&|item_renderer| {
-                if let Some(mouse_position) = mouse_position.get() {
-                    item_renderer.save_state();
-                    item_renderer.translate(
-                        i_slint_core::lengths::logical_point_from_api(mouse_position).to_vector(),
-                    );
-                    item_renderer.draw_image_direct(mouse_cursor_image());
-                    item_renderer.restore_state();
-                }
-            })?;
+            let mouse_cursor = mouse_position.get().map(|position| MouseCursor {
+                position,
+                scale_factor: self.window.scale_factor(),
+                image: mouse_cursor_image(),
+            });
+            self.renderer.render_and_present(mouse_cursor.as_ref())?;
         }
         Ok(())
     }
@@ -21,8 +21,16 @@ pub trait Presenter {
         fn present(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
     }
 
+    #[cfg(any(
+        feature = "renderer-skia-opengl",
+        feature = "renderer-femtovg",
+        feature = "renderer-software"
+    ))]
+    pub mod drmoutput;
     #[cfg(any(feature = "renderer-skia-opengl", feature = "renderer-femtovg"))]
     pub mod egldisplay;
+    #[cfg(feature = "renderer-software")]
+    pub mod swdisplay;
     #[cfg(feature = "renderer-skia-vulkan")]
     pub mod vulkandisplay;
 }
@@ -35,7 +43,10 @@ mod renderer {
     #[cfg(feature = "renderer-femtovg")]
     pub mod femtovg;
 
-    pub fn try_skia_then_femtovg(
+    #[cfg(feature = "renderer-software")]
+    pub mod sw;
+
+    pub fn try_skia_then_femtovg_then_software(
         _device_opener: &crate::DeviceOpener,
     ) -> Result<
         Box<dyn crate::fullscreenwindowadapter::FullscreenRenderer>,
@@ -54,6 +65,11 @@ pub fn try_skia_then_femtovg(
             result = femtovg::FemtoVGRendererAdapter::new(_device_opener);
         }
 
+        #[cfg(feature = "renderer-software")]
+        if result.is_err() {
+            result = sw::SoftwareRendererAdapter::new(_device_opener);
+        }
+
         result
     }
 }
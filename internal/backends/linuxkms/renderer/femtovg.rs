@@ -4,7 +4,6 @@
 use std::num::NonZeroU32;
 
 use i_slint_core::api::PhysicalSize as PhysicalWindowSize;
-use i_slint_core::item_rendering::ItemRenderer;
 use i_slint_core::platform::PlatformError;
 use i_slint_renderer_femtovg::FemtoVGRendererExt;
 use raw_window_handle::{
@@ -187,10 +186,12 @@ fn as_core_renderer(&self) -> &dyn i_slint_core::renderer::Renderer {
     }
     fn render_and_present(
         &self,
-        draw_mouse_cursor_callback: &dyn Fn(&mut dyn ItemRenderer),
+        mouse_cursor: Option<&crate::fullscreenwindowadapter::MouseCursor>,
     ) -> Result<(), PlatformError> {
         self.renderer.render_with_post_callback(Some(&|item_renderer| {
-            draw_mouse_cursor_callback(item_renderer);
+            if let Some(mouse_cursor) = mouse_cursor {
+                mouse_cursor.draw(item_renderer);
+            }
         }))
     }
     fn size(&self) -> i_slint_core::api::PhysicalSize {
@@ -0,0 +1,145 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Delegate the rendering to the [`i_slint_core::software_renderer::SoftwareRenderer`]
+
+use std::cell::Cell;
+
+use i_slint_core::api::PhysicalSize as PhysicalWindowSize;
+use i_slint_core::graphics::{SharedImageBuffer, SharedPixelBuffer};
+use i_slint_core::item_rendering::DirtyRegion;
+use i_slint_core::lengths::{LogicalRect, LogicalSize};
+use i_slint_core::platform::PlatformError;
+use i_slint_core::renderer::RendererSealed;
+use i_slint_core::software_renderer::{
+    Bgra8888Pixel, PremultipliedRgbaColor, RepaintBufferType, SoftwareRenderer, TargetPixel,
+};
+
+use crate::display::{swdisplay::SoftwareBufferDisplay, Presenter};
+use crate::fullscreenwindowadapter::MouseCursor;
+
+pub struct SoftwareRendererAdapter {
+    renderer: SoftwareRenderer,
+    display: SoftwareBufferDisplay,
+    size: PhysicalWindowSize,
+    /// The area of the mouse cursor drawn in the previous frame
+    mouse_cursor_rect: Cell<Option<LogicalRect>>,
+}
+
+impl SoftwareRendererAdapter {
+    pub fn new(
+        device_opener: &crate::DeviceOpener,
+    ) -> Result<Box<dyn crate::fullscreenwindowadapter::FullscreenRenderer>, PlatformError> {
+        let display = SoftwareBufferDisplay::new(device_opener)?;
+
+        let size = display.size;
+
+        // The display flips between two buffers, so only the parts that changed since the
+        // frame before the previous one need to be rendered again.
+        let renderer = Box::new(Self {
+            renderer: SoftwareRenderer::new_with_repaint_buffer_type(
+                RepaintBufferType::SwappedBuffers,
+            ),
+            display,
+            size,
+            mouse_cursor_rect: Default::default(),
+        });
+
+        eprintln!("Using Software renderer");
+
+        Ok(renderer)
+    }
+}
+
+impl crate::fullscreenwindowadapter::FullscreenRenderer for SoftwareRendererAdapter {
+    fn as_core_renderer(&self) -> &dyn i_slint_core::renderer::Renderer {
+        &self.renderer
+    }
+    fn render_and_present(&self, mouse_cursor: Option<&MouseCursor>) -> Result<(), PlatformError> {
+        let cursor_image = mouse_cursor.and_then(|cursor| {
+            let inner: &i_slint_core::ImageInner = (&cursor.image).into();
+            inner.render_to_buffer(None)
+        });
+        let cursor_rect = mouse_cursor.zip(cursor_image.as_ref()).map(|(cursor, image)| {
+            let size = image.size().cast::<f32>() / cursor.scale_factor;
+            LogicalRect::new(
+                i_slint_core::lengths::logical_point_from_api(cursor.position),
+                LogicalSize::new(size.width, size.height),
+            )
+        });
+
+        // The cursor is drawn over the rendered scene, so the renderer must render again where it
+        // was and where it goes. The cursor of the back buffer was drawn in the frame before the
+        // previous one, and is covered by the dirty region of the previous frame that the renderer
+        // renders again because of the swapped buffers.
+        let mut dirty_region = DirtyRegion::default();
+        for rect in [self.mouse_cursor_rect.replace(cursor_rect), cursor_rect].into_iter().flatten()
+        {
+            dirty_region.add_rect(rect);
+        }
+        if !dirty_region.is_empty() {
+            self.renderer.mark_dirty_region(dirty_region);
+        }
+
+        self.display.map_back_buffer(&mut |pixels, pitch| {
+            // XRGB8888 is stored as blue, green, red, and the unused byte, in little endian memory
+            let buffer: &mut [Bgra8888Pixel] = bytemuck::cast_slice_mut(pixels);
+            let stride = pitch / core::mem::size_of::<Bgra8888Pixel>();
+            self.renderer.render(buffer, stride);
+            if let Some((cursor, image)) = mouse_cursor.zip(cursor_image.as_ref()) {
+                let position = cursor.position.to_physical(cursor.scale_factor);
+                draw_image(buffer, stride, position.x, position.y, image);
+            }
+        })?;
+        self.display.present()?;
+        Ok(())
+    }
+    fn size(&self) -> i_slint_core::api::PhysicalSize {
+        self.size
+    }
+}
+
+/// Blends the `image` with its top left corner at `x` and `y` in the `buffer`, which has `stride`
+/// pixels between two lines. The parts of the image outside of the buffer are clipped.
+fn draw_image(
+    buffer: &mut [Bgra8888Pixel],
+    stride: usize,
+    x: i32,
+    y: i32,
+    image: &SharedImageBuffer,
+) {
+    fn draw<P: Copy>(
+        buffer: &mut [Bgra8888Pixel],
+        stride: usize,
+        x: i32,
+        y: i32,
+        pixels: &SharedPixelBuffer<P>,
+        to_color: impl Fn(P) -> PremultipliedRgbaColor,
+    ) {
+        let width = pixels.width() as usize;
+        let height = buffer.len() / stride;
+        for (line, image_line) in pixels.as_slice().chunks(width.max(1)).enumerate() {
+            let Ok(target_y) = usize::try_from(y + line as i32) else { continue };
+            if target_y >= height {
+                break;
+            }
+            let target_line = &mut buffer[target_y * stride..][..stride];
+            for (column, pixel) in image_line.iter().enumerate() {
+                let Ok(target_x) = usize::try_from(x + column as i32) else { continue };
+                let Some(target) = target_line.get_mut(target_x) else { break };
+                target.blend(to_color(*pixel));
+            }
+        }
+    }
+    match image {
+        SharedImageBuffer::RGB8(pixels) => draw(buffer, stride, x, y, pixels, |p| {
+            PremultipliedRgbaColor { red: p.r, green: p.g, blue: p.b, alpha: 255 }
+        }),
+        SharedImageBuffer::RGBA8(pixels) => draw(buffer, stride, x, y, pixels, |p| {
+            i_slint_core::Color::from_argb_u8(p.a, p.r, p.g, p.b).into()
+        }),
+        SharedImageBuffer::RGBA8Premultiplied(pixels) => draw(buffer, stride, x, y, pixels, |p| {
+            PremultipliedRgbaColor { red: p.r, green: p.g, blue: p.b, alpha: p.a }
+        }),
+    }
+}
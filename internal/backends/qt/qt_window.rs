@@ -96,6 +96,8 @@ struct SlintWidget : QWidget {
             // to draw the window background which is set on the palette.
             // (But the window background might not be opaque)
             setAttribute(Qt::WA_NoSystemBackground, false);
+            // Touch events are forwarded as such, instead of being synthesized as mouse events
+            setAttribute(Qt::WA_AcceptTouchEvents);
         }
 
         void paintEvent(QPaintEvent *) override {
@@ -189,18 +191,74 @@ struct SlintWidget : QWidget {
             });
         }
 
+        bool event(QEvent *event) override {
+            switch (event->type()) {
+            case QEvent::TouchBegin:
+            case QEvent::TouchUpdate:
+            case QEvent::TouchEnd:
+            case QEvent::TouchCancel: {
+                auto touch_event = static_cast<QTouchEvent *>(event);
+                // The events of the touch pads are handled by Qt, which sends the mouse and gesture events
+                #if QT_VERSION >= QT_VERSION_CHECK(6, 0, 0)
+                bool touch_screen = touch_event->device()->type() == QInputDevice::DeviceType::TouchScreen;
+                #else
+                bool touch_screen = touch_event->device()->type() == QTouchDevice::TouchScreen;
+                #endif
+                if (!touch_screen) {
+                    return QWidget::event(event);
+                }
+                bool cancelled = event->type() == QEvent::TouchCancel;
+                #if QT_VERSION >= QT_VERSION_CHECK(6, 0, 0)
+                const auto &points = touch_event->points();
+                #else
+                const auto &points = touch_event->touchPoints();
+                #endif
+                for (const auto &point : points) {
+                    #if QT_VERSION >= QT_VERSION_CHECK(6, 0, 0)
+                    QPointF pos = point.position();
+                    #else
+                    QPointF pos = point.pos();
+                    #endif
+                    // 0: pressed, 1: moved, 2: released, 3: cancelled
+                    int phase = 1;
+                    if (cancelled) {
+                        phase = 3;
+                    } else if (int(point.state()) == Qt::TouchPointPressed) {
+                        phase = 0;
+                    } else if (int(point.state()) == Qt::TouchPointReleased) {
+                        phase = 2;
+                    } else if (int(point.state()) == Qt::TouchPointStationary) {
+                        continue;
+                    }
+                    uint64_t id = point.id();
+                    rust!(Slint_touchEvent [rust_window: &QtWindow as "void*", id: u64 as "uint64_t", phase: i32 as "int", pos: qttypes::QPointF as "QPointF"] {
+                        rust_window.touch_event(id, phase, pos)
+                    });
+                }
+                event->accept();
+                return true;
+            }
+            default:
+                return QWidget::event(event);
+            }
+        }
+
         void keyPressEvent(QKeyEvent *event) override {
             QString text =  event->text();
             int key = event->key();
-            rust!(Slint_keyPress [rust_window: &QtWindow as "void*", key: i32 as "int", text: qttypes::QString as "QString"] {
-                rust_window.key_event(key, text.clone(), false);
+            uint modifiers = event->modifiers();
+            bool repeat = event->isAutoRepeat();
+            rust!(Slint_keyPress [rust_window: &QtWindow as "void*", key: i32 as "int", text: qttypes::QString as "QString", modifiers: u32 as "uint", repeat: bool as "bool"] {
+                rust_window.key_event(key, text.clone(), modifiers, repeat, false);
             });
         }
         void keyReleaseEvent(QKeyEvent *event) override {
             QString text =  event->text();
             int key = event->key();
-            rust!(Slint_keyRelease [rust_window: &QtWindow as "void*", key: i32 as "int", text: qttypes::QString as "QString"] {
-                rust_window.key_event(key, text.clone(), true);
+            uint modifiers = event->modifiers();
+            bool repeat = event->isAutoRepeat();
+            rust!(Slint_keyRelease [rust_window: &QtWindow as "void*", key: i32 as "int", text: qttypes::QString as "QString", modifiers: u32 as "uint", repeat: bool as "bool"] {
+                rust_window.key_event(key, text.clone(), modifiers, repeat, true);
             });
         }
 
@@ -1474,16 +1532,44 @@ fn mouse_event(&self, event: MouseEvent) {
         timer_event();
     }
 
-    fn key_event(&self, key: i32, text: qttypes::QString, released: bool) {
+    fn touch_event(&self, id: u64, phase: i32, pos: qttypes::QPointF) {
+        let position = i_slint_core::api::LogicalPosition::new(pos.x as _, pos.y as _);
+        let event = match phase {
+            0 => WindowEvent::TouchPressed { id, position },
+            1 => WindowEvent::TouchMoved { id, position },
+            2 => WindowEvent::TouchReleased { id, position },
+            _ => WindowEvent::TouchCancelled { id },
+        };
+        self.window.dispatch_event(event);
+        timer_event();
+    }
+
+    fn key_event(
+        &self,
+        key: i32,
+        text: qttypes::QString,
+        modifiers: u32,
+        repeat: bool,
+        released: bool,
+    ) {
         i_slint_core::animations::update_animations();
         let text: String = text.into();
 
         let text = qt_key_to_string(key as key_generated::Qt_Key, text);
 
+        let modifiers = i_slint_core::platform::KeyboardModifiers {
+            alt: modifiers & key_generated::Qt_KeyboardModifier_AltModifier != 0,
+            control: modifiers & key_generated::Qt_KeyboardModifier_ControlModifier != 0,
+            shift: modifiers & key_generated::Qt_KeyboardModifier_ShiftModifier != 0,
+            meta: modifiers & key_generated::Qt_KeyboardModifier_MetaModifier != 0,
+        };
+
+        // Qt only provides the native scan code, which differs between the platforms, so the
+        // physical key is left empty
         let event = if released {
-            WindowEvent::KeyReleased { text }
+            WindowEvent::key_released_with_details(text, modifiers, Default::default())
         } else {
-            WindowEvent::KeyPressed { text }
+            WindowEvent::key_pressed_with_details(text, modifiers, Default::default(), repeat)
         };
         self.window.dispatch_event(event);
 
@@ -1686,6 +1772,47 @@ fn update_window_properties(&self, properties: i_slint_core::window::WindowPrope
     fn internal(&self, _: i_slint_core::InternalToken) -> Option<&dyn WindowAdapterInternal> {
         Some(self)
     }
+
+    fn input_method_request(&self, request: i_slint_core::window::InputMethodRequest) {
+        let widget_ptr = self.widget_ptr();
+        let props = match request {
+            i_slint_core::window::InputMethodRequest::Enable(props) => {
+                cpp! {unsafe [widget_ptr as "QWidget*"] {
+                    widget_ptr->setAttribute(Qt::WA_InputMethodEnabled, true);
+                }};
+                props
+            }
+            i_slint_core::window::InputMethodRequest::Disable => {
+                cpp! {unsafe [widget_ptr as "SlintWidget*"] {
+                    widget_ptr->ime_text = "";
+                    widget_ptr->ime_cursor = 0;
+                    widget_ptr->ime_anchor = 0;
+                    widget_ptr->setAttribute(Qt::WA_InputMethodEnabled, false);
+                }};
+                return;
+            }
+            i_slint_core::window::InputMethodRequest::Update(props) => props,
+            _ => return,
+        };
+
+        let rect = qttypes::QRectF {
+            x: props.cursor_rect_origin.x as _,
+            y: props.cursor_rect_origin.y as _,
+            width: props.cursor_rect_size.width as _,
+            height: props.cursor_rect_size.height as _,
+        };
+        let cursor: i32 = props.text[..props.cursor_position].encode_utf16().count() as _;
+        let anchor: i32 =
+            props.anchor_position.map_or(cursor, |a| props.text[..a].encode_utf16().count() as _);
+        let text: qttypes::QString = props.text.as_str().into();
+        cpp! {unsafe [widget_ptr as "SlintWidget*", rect as "QRectF", cursor as "int", anchor as "int", text as "QString"]  {
+            widget_ptr->ime_position = rect.toRect();
+            widget_ptr->ime_text = text;
+            widget_ptr->ime_cursor = cursor;
+            widget_ptr->ime_anchor = anchor;
+            QGuiApplication::inputMethod()->update(Qt::ImQueryInput);
+        }};
+    }
 }
 
 fn into_qsize(logical_size: i_slint_core::api::LogicalSize) -> qttypes::QSize {
@@ -1763,47 +1890,6 @@ fn set_mouse_cursor(&self, cursor: MouseCursor) {
         }};
     }
 
-    fn input_method_request(&self, request: i_slint_core::window::InputMethodRequest) {
-        let widget_ptr = self.widget_ptr();
-        let props = match request {
-            i_slint_core::window::InputMethodRequest::Enable(props) => {
-                cpp! {unsafe [widget_ptr as "QWidget*"] {
-                    widget_ptr->setAttribute(Qt::WA_InputMethodEnabled, true);
-                }};
-                props
-            }
-            i_slint_core::window::InputMethodRequest::Disable => {
-                cpp! {unsafe [widget_ptr as "SlintWidget*"] {
-                    widget_ptr->ime_text = "";
-                    widget_ptr->ime_cursor = 0;
-                    widget_ptr->ime_anchor = 0;
-                    widget_ptr->setAttribute(Qt::WA_InputMethodEnabled, false);
-                }};
-                return;
-            }
-            i_slint_core::window::InputMethodRequest::Update(props) => props,
-            _ => return,
-        };
-
-        let rect = qttypes::QRectF {
-            x: props.cursor_rect_origin.x as _,
-            y: props.cursor_rect_origin.y as _,
-            width: props.cursor_rect_size.width as _,
-            height: props.cursor_rect_size.height as _,
-        };
-        let cursor: i32 = props.text[..props.cursor_position].encode_utf16().count() as _;
-        let anchor: i32 =
-            props.anchor_position.map_or(cursor, |a| props.text[..a].encode_utf16().count() as _);
-        let text: qttypes::QString = props.text.as_str().into();
-        cpp! {unsafe [widget_ptr as "SlintWidget*", rect as "QRectF", cursor as "int", anchor as "int", text as "QString"]  {
-            widget_ptr->ime_position = rect.toRect();
-            widget_ptr->ime_text = text;
-            widget_ptr->ime_cursor = cursor;
-            widget_ptr->ime_anchor = anchor;
-            QGuiApplication::inputMethod()->update(Qt::ImQueryInput);
-        }};
-    }
-
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -85,10 +85,6 @@ impl WindowAdapterInternal for TestingWindow {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
-
-    fn input_method_request(&self, request: i_slint_core::window::InputMethodRequest) {
-        self.ime_requests.borrow_mut().push(request)
-    }
 }
 
 impl WindowAdapter for TestingWindow {
@@ -125,6 +121,10 @@ fn update_window_properties(&self, properties: i_slint_core::window::WindowPrope
     fn internal(&self, _: i_slint_core::InternalToken) -> Option<&dyn WindowAdapterInternal> {
         Some(self)
     }
+
+    fn input_method_request(&self, request: i_slint_core::window::InputMethodRequest) {
+        self.ime_requests.borrow_mut().push(request)
+    }
 }
 
 impl RendererSealed for TestingWindow {
@@ -231,7 +231,7 @@ pub fn init_with_event_loop() {
 
 /// This module contains functions useful for unit tests
 mod for_unit_test {
-    use i_slint_core::api::ComponentHandle;
+    use i_slint_core::api::{ComponentHandle, LogicalPosition};
     use i_slint_core::platform::WindowEvent;
     pub use i_slint_core::tests::slint_mock_elapsed_time as mock_elapsed_time;
     use i_slint_core::window::WindowInner;
@@ -256,6 +256,61 @@ pub fn send_mouse_click<
         );
     }
 
+    /// Simulate a finger touching the screen. The `id` identifies the finger until it is
+    /// released, so that several fingers can be on the screen at once.
+    pub fn send_touch_pressed<
+        X: vtable::HasStaticVTable<i_slint_core::item_tree::ItemTreeVTable>,
+        Component: Into<vtable::VRc<i_slint_core::item_tree::ItemTreeVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        id: u64,
+        x: f32,
+        y: f32,
+    ) {
+        let position = LogicalPosition::new(x, y);
+        component.window().dispatch_event(WindowEvent::TouchPressed { id, position });
+    }
+
+    /// Simulate moving a finger that was pressed with [`send_touch_pressed`].
+    pub fn send_touch_moved<
+        X: vtable::HasStaticVTable<i_slint_core::item_tree::ItemTreeVTable>,
+        Component: Into<vtable::VRc<i_slint_core::item_tree::ItemTreeVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        id: u64,
+        x: f32,
+        y: f32,
+    ) {
+        let position = LogicalPosition::new(x, y);
+        component.window().dispatch_event(WindowEvent::TouchMoved { id, position });
+    }
+
+    /// Simulate lifting a finger that was pressed with [`send_touch_pressed`].
+    pub fn send_touch_released<
+        X: vtable::HasStaticVTable<i_slint_core::item_tree::ItemTreeVTable>,
+        Component: Into<vtable::VRc<i_slint_core::item_tree::ItemTreeVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        id: u64,
+        x: f32,
+        y: f32,
+    ) {
+        let position = LogicalPosition::new(x, y);
+        component.window().dispatch_event(WindowEvent::TouchReleased { id, position });
+    }
+
+    /// Simulate the system cancelling the touch of a finger that was pressed with
+    /// [`send_touch_pressed`], for example because it started a system gesture.
+    pub fn send_touch_cancelled<
+        X: vtable::HasStaticVTable<i_slint_core::item_tree::ItemTreeVTable>,
+        Component: Into<vtable::VRc<i_slint_core::item_tree::ItemTreeVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        id: u64,
+    ) {
+        component.window().dispatch_event(WindowEvent::TouchCancelled { id });
+    }
+
     /// Simulate entering a sequence of ascii characters key by (pressed or released).
     pub fn send_keyboard_char<
         X: vtable::HasStaticVTable<i_slint_core::item_tree::ItemTreeVTable>,
@@ -12,7 +12,7 @@
 use copypasta::ClipboardProvider;
 use corelib::api::EventLoopError;
 use corelib::graphics::euclid;
-use corelib::input::{KeyEvent, KeyEventType, MouseEvent};
+use corelib::input::{KeyEvent, KeyEventType, MouseEvent, TouchEvent};
 use corelib::items::PointerEventButton;
 use corelib::lengths::LogicalPoint;
 use corelib::platform::PlatformError;
@@ -258,6 +258,10 @@ pub struct EventLoopState {
     // last seen cursor position
     cursor_pos: LogicalPoint,
     pressed: bool,
+    // the ids of the touch points that are pressed
+    pressed_touches: std::collections::HashSet<u64>,
+    // the modifier keys held down, as last reported by winit
+    modifiers: corelib::platform::KeyboardModifiers,
 
     loop_error: Option<PlatformError>,
 }
@@ -314,15 +318,52 @@ macro_rules! winit_key_to_char {
             }
                 let text = i_slint_common::for_each_special_keys!(winit_key_to_char);
 
+                // winit names the key codes like the W3C UI Events specification, except for the
+                // super keys.
+                let physical_key: corelib::SharedString = match event.physical_key {
+                    winit::keyboard::PhysicalKey::Code(winit::keyboard::KeyCode::SuperLeft) => {
+                        "MetaLeft".into()
+                    }
+                    winit::keyboard::PhysicalKey::Code(winit::keyboard::KeyCode::SuperRight) => {
+                        "MetaRight".into()
+                    }
+                    winit::keyboard::PhysicalKey::Code(code) => format!("{code:?}").into(),
+                    winit::keyboard::PhysicalKey::Unidentified(_) => Default::default(),
+                };
+
                 window.window().dispatch_event(match event.state {
                     winit::event::ElementState::Pressed => {
-                        corelib::platform::WindowEvent::KeyPressed { text }
+                        corelib::platform::WindowEvent::key_pressed_with_details(
+                            text,
+                            self.modifiers,
+                            physical_key,
+                            event.repeat,
+                        )
                     }
                     winit::event::ElementState::Released => {
-                        corelib::platform::WindowEvent::KeyReleased { text }
+                        corelib::platform::WindowEvent::key_released_with_details(
+                            text,
+                            self.modifiers,
+                            physical_key,
+                        )
                     }
                 });
             }
+            WindowEvent::ModifiersChanged(modifiers) => {
+                let state = modifiers.state();
+                // Like for the keys, the control and command keys are swapped on macOS
+                let (control, meta) = if cfg!(target_os = "macos") {
+                    (state.super_key(), state.control_key())
+                } else {
+                    (state.control_key(), state.super_key())
+                };
+                self.modifiers = corelib::platform::KeyboardModifiers {
+                    alt: state.alt_key(),
+                    control,
+                    shift: state.shift_key(),
+                    meta,
+                };
+            }
             WindowEvent::Ime(winit::event::Ime::Preedit(string, preedit_selection)) => {
                 let event = KeyEvent {
                     event_type: KeyEventType::UpdateComposition,
@@ -347,7 +388,9 @@ macro_rules! winit_key_to_char {
             }
             WindowEvent::CursorLeft { .. } => {
                 // On the html canvas, we don't get the mouse move or release event when outside the canvas. So we have no choice but canceling the event
-                if cfg!(target_arch = "wasm32") || !self.pressed {
+                if cfg!(target_arch = "wasm32")
+                    || (!self.pressed && self.pressed_touches.is_empty())
+                {
                     self.pressed = false;
                     runtime_window.process_mouse_input(MouseEvent::Exit);
                 }
@@ -390,26 +433,23 @@ macro_rules! winit_key_to_char {
             WindowEvent::Touch(touch) => {
                 let location = touch.location.to_logical(runtime_window.scale_factor() as f64);
                 let position = euclid::point2(location.x, location.y);
+                let id = touch.id;
                 let ev = match touch.phase {
                     winit::event::TouchPhase::Started => {
-                        self.pressed = true;
-                        MouseEvent::Pressed {
-                            position,
-                            button: PointerEventButton::Left,
-                            click_count: 0,
-                        }
+                        self.pressed_touches.insert(id);
+                        TouchEvent::Pressed { id, position }
                     }
-                    winit::event::TouchPhase::Ended | winit::event::TouchPhase::Cancelled => {
-                        self.pressed = false;
-                        MouseEvent::Released {
-                            position,
-                            button: PointerEventButton::Left,
-                            click_count: 0,
-                        }
+                    winit::event::TouchPhase::Moved => TouchEvent::Moved { id, position },
+                    winit::event::TouchPhase::Ended => {
+                        self.pressed_touches.remove(&id);
+                        TouchEvent::Released { id, position }
+                    }
+                    winit::event::TouchPhase::Cancelled => {
+                        self.pressed_touches.remove(&id);
+                        TouchEvent::Cancelled { id }
                     }
-                    winit::event::TouchPhase::Moved => MouseEvent::Moved { position },
                 };
-                runtime_window.process_mouse_input(ev);
+                runtime_window.process_touch_input(ev);
             }
             WindowEvent::ScaleFactorChanged { scale_factor, inner_size_writer: _ } => {
                 if std::env::var("SLINT_SCALE_FACTOR").is_err() {
@@ -172,7 +172,12 @@ pub fn new(
                 }
 
                 shared_state2.borrow_mut().has_key_down = true;
-                window_adapter.window().dispatch_event(WindowEvent::KeyPressed { text });
+                window_adapter.window().dispatch_event(WindowEvent::key_pressed_with_details(
+                    text,
+                    event_modifiers(&e),
+                    e.code().into(),
+                    e.repeat(),
+                ));
             }
         });
 
@@ -182,7 +187,11 @@ pub fn new(
             if let (Some(window_adapter), Some(text)) = (win.upgrade(), event_text(&e)) {
                 e.prevent_default();
                 shared_state2.borrow_mut().has_key_down = false;
-                window_adapter.window().dispatch_event(WindowEvent::KeyReleased { text });
+                window_adapter.window().dispatch_event(WindowEvent::key_released_with_details(
+                    text,
+                    event_modifiers(&e),
+                    e.code().into(),
+                ));
             }
         });
 
@@ -311,6 +320,15 @@ macro_rules! check_non_printable_code {
     }
 }
 
+fn event_modifiers(e: &web_sys::KeyboardEvent) -> i_slint_core::platform::KeyboardModifiers {
+    i_slint_core::platform::KeyboardModifiers {
+        alt: e.alt_key(),
+        control: e.ctrl_key(),
+        shift: e.shift_key(),
+        meta: e.meta_key(),
+    }
+}
+
 scoped_tls_hkt::scoped_thread_local!(static CURRENT_WASM_CLIPBOARD_DATA : for<'a> &'a RefCell<String>);
 
 pub(crate) fn set_clipboard_text(data: String, clipboard: i_slint_core::platform::Clipboard) {
@@ -90,6 +90,12 @@ struct KeyEvent {
                     text: SharedString,
                     /// The keyboard modifiers active at the time of the key press event.
                     modifiers: KeyboardModifiers,
+                    /// This field is set to true for the repeated key press events sent while a key is held down.
+                    repeat: bool,
+                    /// The physical key, independent of the keyboard layout, named like the `code` of the keyboard
+                    /// events in the W3C UI Events specification, for example `"KeyZ"`. Use it for shortcuts that
+                    /// don't depend on the keyboard layout. It's empty when the platform doesn't report it.
+                    physical_key: SharedString,
                 }
                 private {
                     /// Indicates whether the key was pressed or released
@@ -146,6 +146,18 @@ enum PointerEventButton {
                 Middle,
             }
 
+            /// This enum describes the direction of a swipe recognized by a [`GestureArea`](elements.md#gesturearea).
+            enum SwipeDirection {
+                /// The fingers moved towards the left edge.
+                Left,
+                /// The fingers moved towards the right edge.
+                Right,
+                /// The fingers moved towards the top edge.
+                Up,
+                /// The fingers moved towards the bottom edge.
+                Down,
+            }
+
             /// This enum represents different types of mouse cursors. It's a subset of the mouse cursors available in CSS.
             /// For details and pictograms see the [MDN Documentation for cursor](https://developer.mozilla.org/en-US/docs/Web/CSS/cursor#values).
             /// Depending on the backend and used OS unidirectional resize cursors may be replaced with bidirectional ones.
@@ -260,6 +260,23 @@ pub fn line_column(&self) -> (usize, usize) {
         }
     }
 
+    /// Return the line and column of the end of the token at which the diagnostic is reported,
+    /// or the start position if it can't be found.
+    fn end_line_column(&self) -> (usize, usize) {
+        let (Some(sf), true) = (&self.span.source_file, self.span.span.is_valid()) else {
+            return self.line_column();
+        };
+        let offset = self.span.span.offset;
+        let len = sf
+            .source()
+            .and_then(|source| source.get(offset..))
+            .and_then(|rest| {
+                crate::parser::lex_next_token(rest, &mut crate::lexer::LexState::default())
+            })
+            .map_or(0, |(len, _)| len);
+        sf.line_column(offset + len)
+    }
+
     /// return the path of the source file where this error is attached
     pub fn source_file(&self) -> Option<&Path> {
         self.span.source_file().map(|sf| sf.path())
@@ -277,6 +294,67 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     }
 }
 
+/// The format in which the diagnostics are printed by the command line tools
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum DiagnosticFormat {
+    /// Human readable messages, in the same style as rustc errors
+    #[default]
+    Human,
+    /// A JSON array with one object per diagnostic, meant to be consumed by other tools.
+    ///
+    /// The objects have the `file`, `line`, `column`, `end_line`, `end_column`, `offset`,
+    /// `level`, `message` and `fixes` fields. The end is the end of the token at which the
+    /// diagnostic is reported, and `fixes` is reserved for suggested fixes (currently always
+    /// empty).
+    Json,
+}
+
+impl std::str::FromStr for DiagnosticFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown diagnostic format {}", s)),
+        }
+    }
+}
+
+/// Serialize the diagnostic as a JSON object on a single line
+fn diagnostic_to_json(d: &Diagnostic) -> String {
+    fn escape(s: &str) -> String {
+        let mut result = String::with_capacity(s.len() + 2);
+        result.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => result.push_str("\\\""),
+                '\\' => result.push_str("\\\\"),
+                '\n' => result.push_str("\\n"),
+                '\r' => result.push_str("\\r"),
+                '\t' => result.push_str("\\t"),
+                c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+                c => result.push(c),
+            }
+        }
+        result.push('"');
+        result
+    }
+
+    let file = d.source_file().map_or("null".into(), |p| escape(&p.to_string_lossy()));
+    let (line, column) = d.line_column();
+    let (end_line, end_column) = d.end_line_column();
+    let offset =
+        if d.span.span.is_valid() { d.span.span.offset.to_string() } else { "null".into() };
+    let level = match d.level {
+        DiagnosticLevel::Error => "error",
+        DiagnosticLevel::Warning => "warning",
+    };
+    format!(
+        "{{\"file\":{file},\"line\":{line},\"column\":{column},\"end_line\":{end_line},\"end_column\":{end_column},\"offset\":{offset},\"level\":\"{level}\",\"message\":{},\"fixes\":[]}}",
+        escape(&d.message)
+    )
+}
+
 #[derive(Default)]
 pub struct BuildDiagnostics {
     inner: Vec<Diagnostic>,
@@ -423,6 +501,17 @@ pub fn print(self) {
         });
     }
 
+    #[cfg(feature = "display-diagnostics")]
+    /// Print the diagnostics on the console in the given format.
+    ///
+    /// In the JSON format, the array is printed even if there are no diagnostics.
+    pub fn print_with_format(self, format: DiagnosticFormat) {
+        match format {
+            DiagnosticFormat::Human => self.print(),
+            DiagnosticFormat::Json => eprintln!("{}", self.diagnostics_as_json()),
+        }
+    }
+
     #[cfg(feature = "display-diagnostics")]
     /// Print into a string
     pub fn diagnostics_as_string(self) -> String {
@@ -499,6 +588,22 @@ pub fn to_string_vec(&self) -> Vec<String> {
         self.inner.iter().map(|d| d.to_string()).collect()
     }
 
+    /// Return each diagnostic serialized as a JSON object with the `file`, `line`, `column`,
+    /// `end_line`, `end_column`, `offset`, `level`, `message` and `fixes` fields.
+    /// `file` and `offset` are null, and the lines and columns are 0, if the location is unknown.
+    pub fn to_json_vec(&self) -> Vec<String> {
+        self.inner.iter().map(diagnostic_to_json).collect()
+    }
+
+    /// Return the diagnostics as a JSON array of the objects returned by [`Self::to_json_vec`]
+    pub fn diagnostics_as_json(&self) -> String {
+        let items = self.to_json_vec();
+        if items.is_empty() {
+            return "[]".into();
+        }
+        format!("[\n  {}\n]", items.join(",\n  "))
+    }
+
     pub fn push_diagnostic(
         &mut self,
         message: String,
@@ -519,17 +624,28 @@ pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
     #[cfg(feature = "display-diagnostics")]
     #[must_use]
     pub fn check_and_exit_on_error(self) -> Self {
+        self.check_and_exit_on_error_with_format(DiagnosticFormat::Human)
+    }
+
+    #[cfg(feature = "display-diagnostics")]
+    pub fn print_warnings_and_exit_on_error(self) {
+        self.print_warnings_and_exit_on_error_with_format(DiagnosticFormat::Human)
+    }
+
+    #[cfg(feature = "display-diagnostics")]
+    #[must_use]
+    pub fn check_and_exit_on_error_with_format(self, format: DiagnosticFormat) -> Self {
         if self.has_error() {
-            self.print();
+            self.print_with_format(format);
             std::process::exit(-1);
         }
         self
     }
 
     #[cfg(feature = "display-diagnostics")]
-    pub fn print_warnings_and_exit_on_error(self) {
+    pub fn print_warnings_and_exit_on_error_with_format(self, format: DiagnosticFormat) {
         let has_error = self.has_error();
-        self.print();
+        self.print_with_format(format);
         if has_error {
             std::process::exit(-1);
         }
@@ -584,4 +700,30 @@ fn test_source_file_offset_line_column_mapping() {
             }
         }
     }
+
+    #[test]
+    fn test_diagnostics_as_json() {
+        let mut diag = BuildDiagnostics::default();
+        assert_eq!(diag.diagnostics_as_json(), "[]");
+
+        let sf = Rc::new(SourceFileInner::new(
+            PathBuf::from("/some/\"file\".slint"),
+            "component Foo {\n  bar: 42;\n}\n".into(),
+        ));
+        diag.push_error_with_span(
+            "Unknown property bar in\tFoo".into(),
+            SourceLocation { source_file: Some(sf), span: Span::new(18) },
+        );
+        diag.push_warning_with_span("No location".into(), SourceLocation::default());
+        assert_eq!(
+            diag.to_json_vec(),
+            vec![
+                r#"{"file":"/some/\"file\".slint","line":2,"column":3,"end_line":2,"end_column":6,"offset":18,"level":"error","message":"Unknown property bar in\tFoo","fixes":[]}"#,
+                r#"{"file":null,"line":0,"column":0,"end_line":0,"end_column":0,"offset":null,"level":"warning","message":"No location","fixes":[]}"#,
+            ]
+        );
+        assert_eq!(diag.diagnostics_as_json().lines().count(), 4);
+        assert_eq!("json".parse::<DiagnosticFormat>(), Ok(DiagnosticFormat::Json));
+        assert!("sarif".parse::<DiagnosticFormat>().is_err());
+    }
 }
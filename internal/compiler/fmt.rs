@@ -1,8 +1,12 @@
 // Copyright © SixtyFPS GmbH <info@slint.dev>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
 
-use crate::writer::TokenWriter;
-use i_slint_compiler::parser::{syntax_nodes, NodeOrToken, SyntaxKind, SyntaxNode};
+//! The formatter for .slint files, used by the LSP and by the slint-fmt tool
+
+pub mod writer;
+
+use crate::parser::{syntax_nodes, NodeOrToken, SyntaxKind, SyntaxNode};
+use writer::TokenWriter;
 
 pub fn format_document(
     doc: syntax_nodes::Document,
@@ -783,19 +787,16 @@ fn format_state(
 
 #[cfg(test)]
 mod tests {
+    use super::writer::FileWriter;
     use super::*;
-    use crate::writer::FileWriter;
-    use i_slint_compiler::diagnostics::BuildDiagnostics;
-    use i_slint_compiler::parser::syntax_nodes;
+    use crate::diagnostics::BuildDiagnostics;
+    use crate::parser::syntax_nodes;
 
     // FIXME more descriptive errors when an assertion fails
     fn assert_formatting(unformatted: &str, formatted: &str) {
         // Parse the unformatted string
-        let syntax_node = i_slint_compiler::parser::parse(
-            String::from(unformatted),
-            None,
-            &mut BuildDiagnostics::default(),
-        );
+        let syntax_node =
+            crate::parser::parse(String::from(unformatted), None, &mut BuildDiagnostics::default());
         // Turn the syntax node into a document
         let doc = syntax_nodes::Document::new(syntax_node).unwrap();
         let mut file = Vec::new();
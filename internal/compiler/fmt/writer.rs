@@ -1,7 +1,7 @@
 // Copyright © SixtyFPS GmbH <info@slint.dev>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
 
-use i_slint_compiler::parser::SyntaxToken;
+use crate::parser::SyntaxToken;
 use std::io::Write;
 
 /// The idea is that each token need to go through this, either with no changes,
@@ -693,6 +693,8 @@ pub struct BuiltinElement {
     pub default_size_binding: DefaultSizeBinding,
     /// When true this is an internal type not shown in the auto-completion
     pub is_internal: bool,
+    /// The comment in front of the element in builtins.slint, shown in the editors
+    pub documentation: Option<String>,
 }
 
 impl BuiltinElement {
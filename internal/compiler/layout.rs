@@ -525,6 +525,7 @@ pub fn implicit_layout_info_call(elem: &ElementRc, orientation: Orientation) ->
                         | "Empty"
                         | "TouchArea"
                         | "FocusScope"
+                        | "GestureArea"
                         | "Opacity"
                         | "Layer"
                         | "BoxShadow"
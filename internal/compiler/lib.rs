@@ -20,10 +20,12 @@
 pub mod embedded_resources;
 pub mod expression_tree;
 pub mod fileaccess;
+pub mod fmt;
 pub mod generator;
 pub mod langtype;
 pub mod layout;
 pub mod lexer;
+pub mod lint;
 pub mod literals;
 pub mod llr;
 pub(crate) mod load_builtins;
@@ -0,0 +1,255 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+/*! Opt-in lints over a loaded [`Document`]
+
+The lints are not run as part of the compilation. They are meant to be run by tools like
+`slint-lint` or the language server on documents that were loaded by the [`TypeLoader`](crate::typeloader::TypeLoader).
+
+Each rule has a level that can be configured with a [`LintConfig`]. A diagnostic can also be
+silenced by a comment like `// slint-lint: allow(rule-name)` placed before the element or
+declaration it applies to, or at the end of the line.
+*/
+
+use crate::diagnostics::{BuildDiagnostics, DiagnosticLevel, Spanned};
+use crate::object_tree::Document;
+use crate::parser::{SyntaxKind, TextRange, TextSize};
+use std::collections::HashMap;
+
+mod accessible_label;
+mod in_out_never_written;
+mod magic_colors;
+mod unused_imports;
+mod unused_properties;
+
+/// The level of a lint rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    /// The rule is not checked
+    Allow,
+    /// Violations of the rule are reported as warnings
+    Warn,
+    /// Violations of the rule are reported as errors
+    Deny,
+}
+
+impl std::str::FromStr for LintLevel {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(Self::Allow),
+            "warn" | "warning" => Ok(Self::Warn),
+            "deny" | "error" => Ok(Self::Deny),
+            _ => Err(format!("Unknown lint level '{s}', expected 'allow', 'warn' or 'deny'")),
+        }
+    }
+}
+
+/// A lint rule
+pub struct LintRule {
+    /// The name of the rule, as used in the configuration and in the `allow` comments
+    pub name: &'static str,
+    /// A short description of what the rule checks
+    pub description: &'static str,
+    /// The level of the rule when it is not configured
+    pub default_level: LintLevel,
+    check: fn(&Document, &mut LintContext),
+}
+
+/// All the lint rules
+pub static RULES: &[LintRule] = &[
+    LintRule {
+        name: "unused-property",
+        description: "private properties that are never used",
+        default_level: LintLevel::Warn,
+        check: unused_properties::check,
+    },
+    LintRule {
+        name: "unused-import",
+        description: "imported names that are never used",
+        default_level: LintLevel::Warn,
+        check: unused_imports::check,
+    },
+    LintRule {
+        name: "magic-color",
+        description: "color literals used outside of a global, such as a palette",
+        default_level: LintLevel::Allow,
+        check: magic_colors::check,
+    },
+    LintRule {
+        name: "missing-accessible-label",
+        description: "clickable components based on a TouchArea used without an accessible-label",
+        default_level: LintLevel::Warn,
+        check: accessible_label::check,
+    },
+    LintRule {
+        name: "in-out-never-written",
+        description: "in-out properties that are never written by their component",
+        default_level: LintLevel::Warn,
+        check: in_out_never_written::check,
+    },
+];
+
+/// The levels of the lint rules
+#[derive(Debug, Clone, Default)]
+pub struct LintConfig {
+    levels: HashMap<&'static str, LintLevel>,
+}
+
+impl LintConfig {
+    /// Set the level of the rule. Returns an error if there is no rule with that name
+    pub fn set_level(&mut self, rule: &str, level: LintLevel) -> Result<(), String> {
+        let rule = RULES
+            .iter()
+            .find(|r| r.name == rule)
+            .ok_or_else(|| format!("Unknown lint rule '{rule}'"))?;
+        self.levels.insert(rule.name, level);
+        Ok(())
+    }
+
+    /// The level of the rule, taking the default level into account
+    pub fn level(&self, rule: &LintRule) -> LintLevel {
+        self.levels.get(rule.name).copied().unwrap_or(rule.default_level)
+    }
+}
+
+/// Run all the lints that are not allowed on the document, and add the diagnostics to `diag`
+pub fn lint_document(doc: &Document, config: &LintConfig, diag: &mut BuildDiagnostics) {
+    let allowed = doc.node.as_ref().map(allow_comments).unwrap_or_default();
+    for rule in RULES {
+        let level = match config.level(rule) {
+            LintLevel::Allow => continue,
+            LintLevel::Warn => DiagnosticLevel::Warning,
+            LintLevel::Deny => DiagnosticLevel::Error,
+        };
+        let mut ctx = LintContext { rule, level, allowed: &allowed, diag };
+        (rule.check)(doc, &mut ctx);
+    }
+}
+
+/// Passed to the rules to report their diagnostics
+struct LintContext<'a> {
+    rule: &'static LintRule,
+    level: DiagnosticLevel,
+    /// The rules allowed by a comment, and the range they apply to
+    allowed: &'a [(String, TextRange)],
+    diag: &'a mut BuildDiagnostics,
+}
+
+impl<'a> LintContext<'a> {
+    fn report(&mut self, message: String, source: &dyn Spanned) {
+        let offset = TextSize::from(source.span().offset as u32);
+        if self
+            .allowed
+            .iter()
+            .any(|(rule, range)| rule == self.rule.name && range.contains_inclusive(offset))
+        {
+            return;
+        }
+        self.diag.push_diagnostic(format!("{message} [{}]", self.rule.name), source, self.level);
+    }
+}
+
+/// Find the `// slint-lint: allow(...)` comments of the document and return each allowed rule
+/// with the range it applies to: the rest of the line for a comment that follows some code, or
+/// the next node otherwise.
+fn allow_comments(doc: &crate::parser::syntax_nodes::Document) -> Vec<(String, TextRange)> {
+    let mut result = vec![];
+    for token in doc.descendants_with_tokens().filter_map(|t| t.into_token()) {
+        if token.kind() != SyntaxKind::Comment {
+            continue;
+        }
+        let Some(rules) = parse_allow_comment(token.text()) else { continue };
+
+        let mut trailing = false;
+        let mut line_start = TextSize::default();
+        for t in std::iter::successors(token.prev_token(), |t| t.prev_token()) {
+            if let Some(pos) = t.text().rfind('\n') {
+                line_start = t.text_range().start() + TextSize::from(pos as u32 + 1);
+                break;
+            }
+            trailing |= !matches!(t.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment);
+        }
+        let range = if trailing {
+            let line_end = std::iter::successors(token.next_token(), |t| t.next_token())
+                .find(|t| t.text().contains('\n'))
+                .map_or(doc.text_range().end(), |t| t.text_range().start());
+            TextRange::new(line_start, line_end)
+        } else {
+            std::iter::successors(token.next_sibling_or_token(), |t| t.next_sibling_or_token())
+                .find(|t| !matches!(t.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment))
+                .map_or(token.text_range(), |t| t.text_range())
+        };
+        result.extend(rules.map(|r| (r.to_string(), range)));
+    }
+    result
+}
+
+/// Parse `// slint-lint: allow(a, b)` and return the rules
+fn parse_allow_comment(comment: &str) -> Option<impl Iterator<Item = &str>> {
+    let comment = comment.strip_prefix("//")?.trim();
+    let rules = comment.strip_prefix("slint-lint:")?.trim().strip_prefix("allow(")?;
+    let rules = rules.strip_suffix(')')?;
+    Some(rules.split(',').map(str::trim).filter(|r| !r.is_empty()))
+}
+
+#[cfg(test)]
+fn lint_source(source: &str, config: &LintConfig) -> Vec<(usize, String)> {
+    let path = std::path::Path::new("/lint/test.slint");
+    let mut compiler_config =
+        crate::CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    compiler_config.style = Some("fluent".into());
+    let mut diag = BuildDiagnostics::default();
+    let mut loader = crate::typeloader::TypeLoader::new(
+        crate::typeregister::TypeRegister::builtin(),
+        compiler_config,
+        &mut diag,
+    );
+    spin_on::spin_on(loader.load_file(path, path, source.into(), false, &mut diag));
+    assert!(!diag.has_error(), "{:?}", diag.to_string_vec());
+    let mut diag = BuildDiagnostics::default();
+    lint_document(loader.get_document(path).unwrap(), config, &mut diag);
+    diag.iter().map(|d| (d.line_column().0, d.message().to_string())).collect()
+}
+
+#[test]
+fn test_allow_comments() {
+    let source = r#"
+export component Main {
+    property <int> a;
+    // slint-lint: allow(unused-property)
+    property <int> b;
+    property <int> c; // slint-lint: allow(unused-property, magic-color)
+    // slint-lint: allow(magic-color)
+    property <int> d;
+}"#;
+    assert_eq!(
+        lint_source(source, &LintConfig::default()),
+        vec![
+            (3, "'a' is never used [unused-property]".to_string()),
+            (8, "'d' is never used [unused-property]".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_lint_config() {
+    let source = "export component Main { property <int> a; }";
+    let mut config = LintConfig::default();
+    config.set_level("unused-property", LintLevel::Deny).unwrap();
+    let mut diag = BuildDiagnostics::default();
+    let path = std::path::Path::new("/lint/test.slint");
+    let mut loader = crate::typeloader::TypeLoader::new(
+        crate::typeregister::TypeRegister::builtin(),
+        crate::CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter),
+        &mut diag,
+    );
+    spin_on::spin_on(loader.load_file(path, path, source.into(), false, &mut diag));
+    lint_document(loader.get_document(path).unwrap(), &config, &mut diag);
+    assert!(diag.has_error());
+
+    config.set_level("unused-property", LintLevel::Allow).unwrap();
+    assert_eq!(lint_source(source, &config), vec![]);
+    assert!(config.set_level("no-such-rule", LintLevel::Warn).is_err());
+    assert_eq!("deny".parse::<LintLevel>(), Ok(LintLevel::Deny));
+}
@@ -0,0 +1,91 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Clickable components (with a `clicked` callback and a `TouchArea`) must have an
+//! `accessible-label`, either set by the component itself or where it is used.
+
+use super::LintContext;
+use crate::langtype::{ElementType, Type};
+use crate::object_tree::{recurse_elem, Component, Document};
+use crate::parser::SyntaxKind;
+use std::rc::Rc;
+
+pub(super) fn check(doc: &Document, ctx: &mut LintContext) {
+    for component in &doc.inner_components {
+        recurse_elem(&component.root_element, &(), &mut |elem, _| {
+            // The root element is checked where the component is used
+            if Rc::ptr_eq(elem, &component.root_element) {
+                return;
+            }
+            let e = elem.borrow();
+            let ElementType::Component(c) = &e.base_type else { return };
+            if e.bindings.contains_key("accessible-label") || !is_clickable(c) || has_label(c) {
+                return;
+            }
+            let Some(node) = &e.node else { return };
+            let node =
+                node.child_node(SyntaxKind::QualifiedName).unwrap_or_else(|| (**node).clone());
+            ctx.report(format!("'{}' is clickable but has no accessible-label", c.id), &node);
+        });
+    }
+}
+
+/// The component, or one of its bases, contains a TouchArea and has a `clicked` callback
+fn is_clickable(component: &Rc<Component>) -> bool {
+    if !matches!(
+        component.root_element.borrow().lookup_property("clicked").property_type,
+        Type::Callback { .. }
+    ) {
+        return false;
+    }
+    base_chain(component).any(|c| {
+        let mut found = false;
+        recurse_elem(&c.root_element, &(), &mut |elem, _| {
+            found |= matches!(&elem.borrow().base_type, ElementType::Builtin(b) if b.name == "TouchArea");
+        });
+        found
+    })
+}
+
+/// The component, or one of its bases, sets the `accessible-label` of its root
+fn has_label(component: &Rc<Component>) -> bool {
+    base_chain(component).any(|c| c.root_element.borrow().bindings.contains_key("accessible-label"))
+}
+
+/// The component followed by the components it inherits from
+fn base_chain(component: &Rc<Component>) -> impl Iterator<Item = Rc<Component>> {
+    std::iter::successors(Some(component.clone()), |c| match &c.root_element.borrow().base_type {
+        ElementType::Component(base) => Some(base.clone()),
+        _ => None,
+    })
+}
+
+#[test]
+fn test_accessible_label() {
+    let source = r#"
+import { Button } from "std-widgets.slint";
+component MyButton {
+    callback clicked;
+    TouchArea { clicked => { root.clicked(); } }
+}
+component LabeledButton inherits MyButton {
+    accessible-label: "labeled";
+}
+component NotClickable {
+    TouchArea { }
+}
+export component Main {
+    MyButton { }
+    MyButton { accessible-label: "ok"; }
+    LabeledButton { }
+    NotClickable { }
+    Button { text: "std"; }
+}"#;
+    assert_eq!(
+        super::lint_source(source, &Default::default()),
+        vec![(
+            14,
+            "'MyButton' is clickable but has no accessible-label [missing-accessible-label]".into()
+        )]
+    );
+}
@@ -0,0 +1,103 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! `in-out` properties of a component that the component itself never writes: they could
+//! be declared `in`.
+
+use super::LintContext;
+use crate::expression_tree::Expression;
+use crate::namedreference::NamedReference;
+use crate::object_tree::{
+    recurse_elem, visit_element_expressions, Document, ElementRc, PropertyVisibility,
+};
+use crate::parser::SyntaxKind;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+pub(super) fn check(doc: &Document, ctx: &mut LintContext) {
+    for component in &doc.inner_components {
+        let root = &component.root_element;
+        let written = written_properties(root);
+        let root_elem = root.borrow();
+        for (name, decl) in &root_elem.property_declarations {
+            if decl.visibility != PropertyVisibility::InOut
+                || !decl.property_type.is_property_type()
+                || written.contains(name)
+            {
+                continue;
+            }
+            // `in-out property <int> foo <=> bar;`
+            if root_elem
+                .bindings
+                .get(name)
+                .map_or(false, |b| !b.borrow().two_way_bindings.is_empty())
+            {
+                continue;
+            }
+            let Some(node) =
+                decl.node.as_ref().filter(|n| n.kind() == SyntaxKind::PropertyDeclaration)
+            else {
+                continue;
+            };
+            let node = node.child_node(SyntaxKind::DeclaredIdentifier).unwrap_or(node.clone());
+            ctx.report(
+                format!("'{name}' is declared in-out but is never written by '{}', it could be declared in", component.id),
+                &node,
+            );
+        }
+    }
+}
+
+/// The names of the properties of the root element that are written by the elements of its
+/// component: assigned in code, changed by a state, or in a two way binding
+fn written_properties(root: &ElementRc) -> HashSet<String> {
+    let mut written = HashSet::new();
+    let mut add = |nr: &NamedReference| {
+        if Rc::ptr_eq(&nr.element(), root) {
+            written.insert(nr.name().to_string());
+        }
+    };
+    recurse_elem(root, &(), &mut |elem, _| {
+        for binding in elem.borrow().bindings.values() {
+            binding.borrow().two_way_bindings.iter().for_each(&mut add);
+        }
+        for state in &elem.borrow().states {
+            state.property_changes.iter().for_each(|(nr, _, _)| add(nr));
+        }
+        visit_element_expressions(elem, |expr, _, _| {
+            expr.visit_recursive(&mut |e| {
+                if let Expression::SelfAssignment { lhs, .. } = e {
+                    let mut lhs = &**lhs;
+                    loop {
+                        match lhs {
+                            Expression::StructFieldAccess { base, .. } => lhs = base,
+                            Expression::ArrayIndex { array, .. } => lhs = array,
+                            Expression::PropertyReference(nr) => break add(nr),
+                            _ => break,
+                        }
+                    }
+                }
+            })
+        });
+    });
+    written
+}
+
+#[test]
+fn test_in_out_never_written() {
+    let source = r#"
+export component Main {
+    in-out property <int> counter;
+    in-out property <string> text;
+    in-out property <bool> checked;
+    in-out property <int> never;
+    in-out property <{ x: int }> point;
+    TouchArea { clicked => { counter += 1; point.x = 2; } }
+    TextInput { text <=> root.text; }
+    states [ on when counter > 2: { checked: false; } ]
+}"#;
+    assert_eq!(
+        super::lint_source(source, &Default::default()),
+        vec![(6, "'never' is declared in-out but is never written by 'Main', it could be declared in [in-out-never-written]".into())]
+    );
+}
@@ -0,0 +1,52 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Color literals used in components: the colors should be defined in a global (a palette)
+//! and referenced from there.
+
+use super::LintContext;
+use crate::object_tree::Document;
+use crate::parser::{NodeOrToken, SyntaxKind};
+
+pub(super) fn check(doc: &Document, ctx: &mut LintContext) {
+    for component in &doc.inner_components {
+        if component.is_global() {
+            continue;
+        }
+        let Some(node) = &component.node else { continue };
+        let mut stack = vec![node.clone()];
+        while let Some(node) = stack.pop() {
+            for child in node.children_with_tokens() {
+                match child {
+                    NodeOrToken::Node(n) => stack.push(n),
+                    NodeOrToken::Token(t) if t.kind() == SyntaxKind::ColorLiteral => {
+                        ctx.report(
+                            format!("Color literal '{}' used outside of a global", t.text()),
+                            &t,
+                        );
+                    }
+                    NodeOrToken::Token(_) => {}
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_magic_colors() {
+    let source = r#"
+global Palette {
+    out property <color> background: #102030;
+}
+export component Main inherits Window {
+    background: Palette.background;
+    Rectangle { background: #ff0000; }
+}"#;
+    let mut config = super::LintConfig::default();
+    assert_eq!(super::lint_source(source, &config), vec![]);
+    config.set_level("magic-color", super::LintLevel::Warn).unwrap();
+    assert_eq!(
+        super::lint_source(source, &config),
+        vec![(7, "Color literal '#ff0000' used outside of a global [magic-color]".into())]
+    );
+}
@@ -0,0 +1,56 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Names imported with an `import { ... } from "..."` statement that are never used in the file
+
+use super::LintContext;
+use crate::object_tree::Document;
+use crate::parser::{normalize_identifier, NodeOrToken, SyntaxKind, SyntaxNode};
+use crate::typeloader::ImportedName;
+use std::collections::HashSet;
+
+pub(super) fn check(doc: &Document, ctx: &mut LintContext) {
+    let Some(doc_node) = &doc.node else { return };
+
+    // All the identifiers used outside of the imports
+    let mut used = HashSet::new();
+    let mut stack = vec![(**doc_node).clone()];
+    while let Some(node) = stack.pop() {
+        for child in node.children_with_tokens() {
+            match child {
+                NodeOrToken::Node(n) if n.kind() != SyntaxKind::ImportSpecifier => stack.push(n),
+                NodeOrToken::Token(t) if t.kind() == SyntaxKind::Identifier => {
+                    used.insert(normalize_identifier(t.text()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for import in doc_node.ImportSpecifier() {
+        let Some(list) = import.ImportIdentifierList() else { continue };
+        for identifier in list.ImportIdentifier() {
+            let node: SyntaxNode = identifier.clone().into();
+            let name = ImportedName::from_node(identifier);
+            if !used.contains(&name.internal_name) {
+                ctx.report(format!("'{}' is imported but never used", name.internal_name), &node);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_unused_imports() {
+    let source = r#"import { Button, LineEdit, CheckBox as Check, StandardButton } from "std-widgets.slint";
+export component Main {
+    Button { }
+    if true: Check { }
+}"#;
+    assert_eq!(
+        super::lint_source(source, &Default::default()),
+        vec![
+            (1, "'LineEdit' is imported but never used [unused-import]".into()),
+            (1, "'StandardButton' is imported but never used [unused-import]".into()),
+        ]
+    );
+}
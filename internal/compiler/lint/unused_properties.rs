@@ -0,0 +1,56 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Private properties that are never used: they can't be used from outside of their component,
+//! so nothing reads or sets them.
+
+use super::LintContext;
+use crate::object_tree::{recurse_elem, Document, PropertyVisibility};
+use crate::parser::SyntaxKind;
+
+pub(super) fn check(doc: &Document, ctx: &mut LintContext) {
+    for component in &doc.inner_components {
+        recurse_elem(&component.root_element, &(), &mut |elem, _| {
+            let elem = elem.borrow();
+            for (name, decl) in &elem.property_declarations {
+                if decl.visibility != PropertyVisibility::Private
+                    || !decl.property_type.is_property_type()
+                    || elem.named_references.is_referenced(name)
+                {
+                    continue;
+                }
+                let Some(node) =
+                    decl.node.as_ref().filter(|n| n.kind() == SyntaxKind::PropertyDeclaration)
+                else {
+                    continue;
+                };
+                let node = node.child_node(SyntaxKind::DeclaredIdentifier).unwrap_or(node.clone());
+                ctx.report(format!("'{name}' is never used"), &node);
+            }
+        });
+    }
+}
+
+#[test]
+fn test_unused_properties() {
+    let source = r#"
+component Foo {
+    property <int> used: 42;
+    property <int> unused: used;
+    in property <int> input;
+    private property <int> set-only;
+    Rectangle {
+        property <int> inner;
+        width: parent.width;
+    }
+    TouchArea { clicked => { set-only = 4; } }
+}
+export component Main { Foo { } }"#;
+    assert_eq!(
+        super::lint_source(source, &Default::default()),
+        vec![
+            (4, "'unused' is never used [unused-property]".into()),
+            (8, "'inner' is never used [unused-property]".into())
+        ]
+    );
+}
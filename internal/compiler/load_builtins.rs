@@ -180,6 +180,7 @@ enum Base {
         builtin.is_non_item_type = parse_annotation("is_non_item_type", &e).is_some();
         builtin.is_internal = parse_annotation("is_internal", &e).is_some();
         builtin.accepts_focus = parse_annotation("accepts_focus", &e).is_some();
+        builtin.documentation = crate::parser::doc_comment(&c);
         builtin.default_size_binding = parse_annotation("default_size_binding", &e)
             .map(|size_type| match size_type.as_deref() {
                 Some("expands_to_parent_geometry") => DefaultSizeBinding::ExpandsToParentGeometry,
@@ -949,6 +949,80 @@ pub fn normalize_identifier(ident: &str) -> String {
     ident.replace('_', "-")
 }
 
+/// Return the text of the comments directly preceding the node, with the comment markers removed.
+///
+/// Comments that are separated from the node by an empty line, comments that follow some code
+/// on the same line, and the `//-` annotations of the builtins file are not considered.
+pub fn doc_comment(node: &SyntaxNode) -> Option<String> {
+    // Comments are attached in front of the outermost node starting at the same token
+    // (eg: the `SubElement` of an `Element`, or the `ExportsList` of an exported `Component`)
+    let mut node = node.node.clone();
+    while let Some(parent) = node.parent() {
+        if parent.kind() == SyntaxKind::ExportsList
+            || (parent.kind() != SyntaxKind::Document
+                && parent.text_range().start() == node.text_range().start())
+        {
+            node = parent;
+        } else {
+            break;
+        }
+    }
+
+    let mut comments = Vec::new();
+    let mut it = node.prev_sibling_or_token();
+    while let Some(rowan::NodeOrToken::Token(t)) = it {
+        match t.kind() {
+            SyntaxKind::Whitespace => {
+                if t.text().matches('\n').count() > 1 {
+                    break;
+                }
+            }
+            SyntaxKind::Comment => {
+                if t.text().starts_with("//-") {
+                    break;
+                }
+                // Ignore trailing comments of the previous line
+                let previous = t.prev_sibling_or_token();
+                if previous.as_ref().map_or(false, |p| {
+                    p.kind() != SyntaxKind::Whitespace
+                        || !p.as_token().map_or(false, |w| w.text().contains('\n'))
+                }) {
+                    break;
+                }
+                comments.push(t.text().to_string());
+            }
+            _ => break,
+        }
+        it = t.prev_sibling_or_token();
+    }
+    if comments.is_empty() {
+        return None;
+    }
+
+    let mut lines = Vec::new();
+    for comment in comments.iter().rev() {
+        if let Some(c) = comment.strip_prefix("//") {
+            let c = c.trim_start_matches('/');
+            lines.push(c.strip_prefix(' ').unwrap_or(c).trim_end().to_string());
+        } else {
+            let c = comment.trim_start_matches("/*").trim_start_matches('*');
+            let c = c.strip_suffix("*/").unwrap_or(c);
+            for l in c.lines() {
+                let l = l.trim();
+                let l = l.strip_prefix('*').map_or(l, |l| l.strip_prefix(' ').unwrap_or(l));
+                lines.push(l.to_string());
+            }
+        }
+    }
+    while lines.first().map_or(false, |l| l.is_empty()) {
+        lines.remove(0);
+    }
+    while lines.last().map_or(false, |l| l.is_empty()) {
+        lines.pop();
+    }
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
 // Parse an expression into a BindingExpression. This is used by the LSP to syntax
 // check the values of properties.
 pub fn parse_expression_as_bindingexpression(
@@ -13,7 +13,7 @@
 mod collect_structs_and_enums;
 mod collect_subcomponents;
 mod compile_paths;
-mod const_propagation;
+pub mod const_propagation;
 mod deduplicate_property_read;
 mod default_geometry;
 #[cfg(feature = "software-renderer")]
@@ -6,7 +6,11 @@
 use crate::langtype::ElementType;
 use crate::object_tree::Element;
 
-/// Check that the rotation is only on Image
+/// The native classes of the elements that can be rotated
+const ROTATABLE_CLASSES: &[&str] =
+    &["ClippedImage", "ImageItem", "Text", "Rectangle", "BorderRectangle"];
+
+/// Check that the rotation is only on Image, Text or Rectangle
 pub fn check_rotation(doc: &crate::object_tree::Document, diag: &mut BuildDiagnostics) {
     for cmp in &doc.inner_components {
         crate::object_tree::recurse_elem_including_sub_components(cmp, &(), &mut |elem, _| {
@@ -15,7 +19,10 @@ pub fn check_rotation(doc: &crate::object_tree::Document, diag: &mut BuildDiagno
                 .iter()
                 .any(|(property_name, _)| is_property_set(&e, property_name))
             {
-                if matches!(e.native_class(), Some(native) if native.class_name != "ClippedImage") {
+                let is_rotatable = e
+                    .native_class()
+                    .map_or(true, |native| ROTATABLE_CLASSES.contains(&native.class_name.as_str()));
+                if !is_rotatable {
                     let span = e
                         .bindings
                         .get("rotation-angle")
@@ -23,7 +30,7 @@ pub fn check_rotation(doc: &crate::object_tree::Document, diag: &mut BuildDiagno
                         .unwrap_or_else(|| e.to_source_location());
 
                     diag.push_error_with_span(
-                        "rotation properties can only be applied to the Image element".into(),
+                        "rotation properties can only be applied to the Image, Text and Rectangle elements".into(),
                         span,
                     );
                 } else if has_any_children(&e) {
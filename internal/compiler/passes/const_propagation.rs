@@ -17,6 +17,15 @@ pub fn const_propagation(component: &Component) {
     });
 }
 
+/// Return a simplified copy of the expression, or None if it still depends on
+/// the value of some property.
+///
+/// This is used by tools (eg: the LSP) to show the value of bindings.
+pub fn simplified_constant(expression: &Expression) -> Option<Expression> {
+    let mut expression = expression.clone();
+    simplify_expression(&mut expression).then_some(expression)
+}
+
 /// Returns false if the expression still contains a reference to an element
 fn simplify_expression(expr: &mut Expression) -> bool {
     match expr {
@@ -638,6 +638,32 @@ pub fn get_document<'b>(&'b self, path: &Path) -> Option<&'b object_tree::Docume
         self.all_documents.docs.get(&path)
     }
 
+    /// Remove the document from the cache, so that it is loaded again the next time it is imported.
+    pub fn drop_document(&mut self, path: &Path) -> Option<object_tree::Document> {
+        let path = crate::pathutils::clean_path(path);
+        self.all_documents.docs.remove(&path)
+    }
+
+    /// Return the paths of the files imported by the loaded document, including the modules
+    /// re-exported with `export * from`.
+    pub fn document_dependencies(&self, path: &Path) -> Vec<PathBuf> {
+        let Some(node) = self.get_document(path).and_then(|doc| doc.node.as_ref()) else {
+            return Vec::new();
+        };
+        node.ImportSpecifier()
+            .filter_map(|import| import.child_token(SyntaxKind::StringLiteral))
+            .chain(
+                node.ExportsList()
+                    .flat_map(|exports| exports.ExportModule())
+                    .filter_map(|reexport| reexport.child_token(SyntaxKind::StringLiteral)),
+            )
+            .filter_map(|import_uri| {
+                let file = import_uri.text().trim_matches('"').to_string();
+                self.resolve_import_path(Some(&import_uri.into()), &file).map(|(path, _)| path)
+            })
+            .collect()
+    }
+
     /// Return an iterator over all the loaded file path
     pub fn all_files(&self) -> impl Iterator<Item = &PathBuf> {
         self.all_documents.docs.keys()
@@ -9,7 +9,7 @@
 
 #[cfg(target_has_atomic = "ptr")]
 pub use crate::future::*;
-use crate::input::{KeyEventType, MouseEvent};
+use crate::input::{KeyEventType, MouseEvent, TouchEvent};
 use crate::item_tree::ItemTreeVTable;
 use crate::window::{WindowAdapter, WindowInner};
 use alloc::boxed::Box;
@@ -484,6 +484,27 @@ pub fn dispatch_event(&self, event: crate::platform::WindowEvent) {
             crate::platform::WindowEvent::PointerExited => {
                 self.0.process_mouse_input(MouseEvent::Exit)
             }
+            crate::platform::WindowEvent::TouchPressed { id, position } => {
+                self.0.process_touch_input(TouchEvent::Pressed {
+                    id,
+                    position: position.to_euclid().cast(),
+                })
+            }
+            crate::platform::WindowEvent::TouchMoved { id, position } => {
+                self.0.process_touch_input(TouchEvent::Moved {
+                    id,
+                    position: position.to_euclid().cast(),
+                })
+            }
+            crate::platform::WindowEvent::TouchReleased { id, position } => {
+                self.0.process_touch_input(TouchEvent::Released {
+                    id,
+                    position: position.to_euclid().cast(),
+                })
+            }
+            crate::platform::WindowEvent::TouchCancelled { id } => {
+                self.0.process_touch_input(TouchEvent::Cancelled { id })
+            }
 
             crate::platform::WindowEvent::KeyPressed { text } => {
                 self.0.process_key_input(crate::input::KeyEvent {
@@ -499,6 +520,58 @@ pub fn dispatch_event(&self, event: crate::platform::WindowEvent) {
                     ..Default::default()
                 })
             }
+            crate::platform::WindowEvent::KeyPressedWithDetails {
+                text,
+                modifiers,
+                physical_key,
+                repeat,
+            } => self.0.process_key_input_with_platform_modifiers(
+                crate::input::KeyEvent {
+                    text,
+                    physical_key,
+                    repeat,
+                    event_type: KeyEventType::KeyPressed,
+                    ..Default::default()
+                },
+                modifiers,
+            ),
+            crate::platform::WindowEvent::KeyReleasedWithDetails {
+                text,
+                modifiers,
+                physical_key,
+            } => self.0.process_key_input_with_platform_modifiers(
+                crate::input::KeyEvent {
+                    text,
+                    physical_key,
+                    event_type: KeyEventType::KeyReleased,
+                    ..Default::default()
+                },
+                modifiers,
+            ),
+            crate::platform::WindowEvent::InputMethodPreedit { text, cursor_start, cursor_end } => {
+                // Keep the positions within the pre-edit text and on character boundaries
+                let clamp = |pos: usize| {
+                    let mut pos = pos.min(text.len());
+                    while !text.is_char_boundary(pos) {
+                        pos -= 1;
+                    }
+                    pos as i32
+                };
+                let preedit_selection = clamp(cursor_start)..clamp(cursor_end);
+                self.0.process_key_input(crate::input::KeyEvent {
+                    event_type: KeyEventType::UpdateComposition,
+                    preedit_text: text,
+                    preedit_selection: Some(preedit_selection),
+                    ..Default::default()
+                })
+            }
+            crate::platform::WindowEvent::InputMethodCommit { text } => {
+                self.0.process_key_input(crate::input::KeyEvent {
+                    event_type: KeyEventType::CommitComposition,
+                    text,
+                    ..Default::default()
+                })
+            }
             crate::platform::WindowEvent::ScaleFactorChanged { scale_factor } => {
                 self.0.set_scale_factor(scale_factor);
             }
@@ -5,7 +5,8 @@
 This module contains a cache helper for caching box shadow textures.
 */
 
-use std::{cell::RefCell, collections::BTreeMap};
+use alloc::collections::BTreeMap;
+use core::cell::RefCell;
 
 use super::euclid;
 use crate::items::ItemRc;
@@ -32,23 +33,23 @@ pub struct BoxShadowOptions {
 
 impl Eq for BoxShadowOptions {}
 impl Ord for BoxShadowOptions {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         if (other.width, other.height, other.color, other.blur, other.radius)
             < (self.width, self.height, self.color, self.blur, self.radius)
         {
-            std::cmp::Ordering::Less
+            core::cmp::Ordering::Less
         } else if (self.width, self.height, self.color, self.blur, self.radius)
             < (other.width, other.height, other.color, other.blur, other.radius)
         {
-            std::cmp::Ordering::Greater
+            core::cmp::Ordering::Greater
         } else {
-            std::cmp::Ordering::Equal
+            core::cmp::Ordering::Equal
         }
     }
 }
 
 impl PartialOrd for BoxShadowOptions {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -59,7 +60,7 @@ impl BoxShadowOptions {
     /// alpha on the box shadow would imply that no shadow is to be rendered.
     pub fn new(
         item_rc: &ItemRc,
-        box_shadow: std::pin::Pin<&crate::items::BoxShadow>,
+        box_shadow: core::pin::Pin<&crate::items::BoxShadow>,
         scale_factor: ScaleFactor,
     ) -> Option<Self> {
         let color = box_shadow.color();
@@ -93,11 +94,12 @@ fn default() -> Self {
 
 impl<ImageType: Clone> BoxShadowCache<ImageType> {
     /// Look up a box shadow texture for a given box shadow item, or create a new one if needed.
+    #[cfg(feature = "std")]
     pub fn get_box_shadow(
         &self,
         item_rc: &ItemRc,
         item_cache: &crate::item_rendering::ItemCache<Option<ImageType>>,
-        box_shadow: std::pin::Pin<&crate::items::BoxShadow>,
+        box_shadow: core::pin::Pin<&crate::items::BoxShadow>,
         scale_factor: ScaleFactor,
         shadow_render_fn: impl FnOnce(&BoxShadowOptions) -> ImageType,
     ) -> Option<ImageType> {
@@ -304,7 +304,9 @@ pub fn iter(self) -> Option<PathDataIterator> {
                     ) {
                         Ok(()) => LyonPathIteratorVariant::FromPath(builder.build()),
                         Err(e) => {
-                            eprintln!("Error while parsing path commands '{commands}': {e:?}");
+                            crate::debug_log!(
+                                "Error while parsing path commands '{commands}': {e:?}"
+                            );
                             LyonPathIteratorVariant::FromPath(Default::default())
                         }
                     }
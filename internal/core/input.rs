@@ -19,6 +19,9 @@
 use const_field_offset::FieldOffsets;
 use core::cell::Cell;
 use core::pin::Pin;
+#[cfg(not(feature = "std"))]
+#[allow(unused)]
+use num_traits::Float;
 
 /// A mouse or touch event
 ///
@@ -813,6 +816,316 @@ fn send_mouse_event_to_item(
     }
 }
 
+/// A touch event for one of the fingers in contact with a touch-enabled surface.
+///
+/// Unlike [`MouseEvent`], the position is always in window coordinates because the touch
+/// events are not delivered to the items directly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(missing_docs)]
+pub enum TouchEvent {
+    /// The finger `id` touched the surface
+    Pressed { id: u64, position: LogicalPoint },
+    /// The finger `id` moved
+    Moved { id: u64, position: LogicalPoint },
+    /// The finger `id` was lifted
+    Released { id: u64, position: LogicalPoint },
+    /// The finger `id` is no longer tracked by the windowing system
+    Cancelled { id: u64 },
+}
+
+/// The minimum speed, in logical pixels per millisecond, that the fingers must have when the
+/// gesture ends for it to be reported as a swipe.
+const SWIPE_MIN_VELOCITY: f32 = 0.5;
+/// Only the movement of the fingers during that duration before the end of the gesture is
+/// considered to compute the swipe velocity.
+const SWIPE_VELOCITY_DURATION: core::time::Duration = core::time::Duration::from_millis(100);
+
+/// Where the fingers of a multi-touch gesture are.
+#[derive(Clone, Copy, Debug)]
+struct TouchPointsGeometry {
+    /// The centroid of the fingers, in window coordinates
+    center: LogicalPoint,
+    /// The average distance of the fingers to the center
+    spread: f32,
+    /// The angle of the line from the first to the second finger, in degrees
+    angle: f32,
+}
+
+impl TouchPointsGeometry {
+    fn new(points: &[(u64, LogicalPoint)]) -> Self {
+        let count = points.len().max(1) as f32;
+        let center = points
+            .iter()
+            .fold(euclid::Point2D::origin(), |sum, (_, p)| sum + p.cast::<f32>().to_vector())
+            / count;
+        let spread =
+            points.iter().map(|(_, p)| (p.cast::<f32>() - center).length()).sum::<f32>() / count;
+        let angle = match points {
+            [(_, first), (_, second), ..] => {
+                let v = (*second - *first).cast::<f32>();
+                v.y.atan2(v.x).to_degrees()
+            }
+            _ => 0.,
+        };
+        Self { center: center.cast(), spread, angle }
+    }
+}
+
+#[derive(Default)]
+enum TouchMode {
+    /// No finger is on the surface
+    #[default]
+    Idle,
+    /// A single finger that is delivered to the items like a pointer with the left button
+    Pointer(u64),
+    /// Several fingers whose gesture is reported to a `GestureArea`
+    Gesture {
+        area: ItemWeak,
+        last: TouchPointsGeometry,
+        /// The recent centers of the fingers, to detect swipes
+        history: Vec<(crate::animations::Instant, LogicalPoint)>,
+    },
+    /// Several fingers outside of any `GestureArea`, their movement scrolls like a mouse wheel
+    Scroll { last: TouchPointsGeometry },
+    /// The gesture ended, but some fingers are still on the surface
+    Finished,
+}
+
+/// The state which a window should hold for the touch input
+#[derive(Default)]
+pub struct TouchInputState {
+    /// The fingers on the surface, in the order they were pressed
+    points: Vec<(u64, LogicalPoint)>,
+    mode: TouchMode,
+}
+
+/// Process the `touch_event` on the `component` shown in `window`.
+///
+/// The first finger is sent to the items as a mouse with the left button, through the
+/// `window` so that popups and double clicks work the same. As soon as a second finger touches
+/// the surface, the pointer exits, and the fingers form a gesture reported to the `GestureArea`
+/// under their center, or scroll if there is none.
+/// Returns the new touch input state.
+pub(crate) fn process_touch_input(
+    component: &ItemTreeRc,
+    touch_event: TouchEvent,
+    window: &WindowInner,
+    mut state: TouchInputState,
+) -> TouchInputState {
+    match touch_event {
+        TouchEvent::Pressed { id, position } => {
+            state.points.retain(|(i, _)| *i != id);
+            state.points.push((id, position));
+            state.mode = match core::mem::take(&mut state.mode) {
+                TouchMode::Idle => {
+                    window.process_mouse_input(MouseEvent::Pressed {
+                        position,
+                        button: PointerEventButton::Left,
+                        click_count: 0,
+                    });
+                    TouchMode::Pointer(id)
+                }
+                TouchMode::Pointer(_) => {
+                    window.process_mouse_input(MouseEvent::Exit);
+                    start_multi_touch(component, &state.points)
+                }
+                // The center jumps when the number of fingers changes, so restart from there
+                TouchMode::Gesture { area, .. } => gesture_mode(area, &state.points),
+                TouchMode::Scroll { .. } => {
+                    TouchMode::Scroll { last: TouchPointsGeometry::new(&state.points) }
+                }
+                TouchMode::Finished => TouchMode::Finished,
+            };
+        }
+        TouchEvent::Moved { id, position } => {
+            let Some(point) = state.points.iter_mut().find(|(i, _)| *i == id) else {
+                return state;
+            };
+            point.1 = position;
+            match &mut state.mode {
+                TouchMode::Pointer(pointer_id) if *pointer_id == id => {
+                    window.process_mouse_input(MouseEvent::Moved { position });
+                }
+                TouchMode::Gesture { area, last, history } => {
+                    let current = TouchPointsGeometry::new(&state.points);
+                    if let Some(area) = area.upgrade() {
+                        report_gesture(&area, last, &current);
+                    }
+                    *last = current;
+                    let now = crate::animations::Instant::now();
+                    history.retain(|(time, _)| now - *time <= SWIPE_VELOCITY_DURATION);
+                    history.push((now, current.center));
+                }
+                TouchMode::Scroll { last } => {
+                    let current = TouchPointsGeometry::new(&state.points);
+                    let delta = current.center - last.center;
+                    *last = current;
+                    window.process_mouse_input(MouseEvent::Wheel {
+                        position: current.center,
+                        delta_x: delta.x,
+                        delta_y: delta.y,
+                    });
+                }
+                _ => {}
+            }
+        }
+        TouchEvent::Released { id, position } => {
+            if !matches!(state.mode, TouchMode::Pointer(_)) {
+                state = process_touch_input(
+                    component,
+                    TouchEvent::Moved { id, position },
+                    window,
+                    state,
+                );
+            }
+            state.points.retain(|(i, _)| *i != id);
+            state.mode = match core::mem::take(&mut state.mode) {
+                TouchMode::Pointer(pointer_id) if pointer_id == id => {
+                    window.process_mouse_input(MouseEvent::Released {
+                        position,
+                        button: PointerEventButton::Left,
+                        click_count: 0,
+                    });
+                    TouchMode::Idle
+                }
+                TouchMode::Gesture { area, history, .. } if state.points.len() < 2 => {
+                    end_gesture(&area, &history);
+                    TouchMode::Finished
+                }
+                TouchMode::Gesture { area, .. } => gesture_mode(area, &state.points),
+                TouchMode::Scroll { .. } if state.points.len() < 2 => TouchMode::Finished,
+                TouchMode::Scroll { .. } => {
+                    TouchMode::Scroll { last: TouchPointsGeometry::new(&state.points) }
+                }
+                mode => mode,
+            };
+        }
+        TouchEvent::Cancelled { id } => {
+            state.points.retain(|(i, _)| *i != id);
+            state.mode = match core::mem::take(&mut state.mode) {
+                TouchMode::Pointer(pointer_id) if pointer_id == id => {
+                    window.process_mouse_input(MouseEvent::Exit);
+                    TouchMode::Idle
+                }
+                TouchMode::Gesture { area, .. } => {
+                    end_gesture(&area, &[]);
+                    TouchMode::Finished
+                }
+                TouchMode::Scroll { .. } => TouchMode::Finished,
+                mode => mode,
+            };
+        }
+    }
+    if state.points.is_empty() {
+        state.mode = TouchMode::Idle;
+    }
+    state
+}
+
+/// Starts the gesture of several fingers, on the `GestureArea` under their center if any.
+fn start_multi_touch(component: &ItemTreeRc, points: &[(u64, LogicalPoint)]) -> TouchMode {
+    let last = TouchPointsGeometry::new(points);
+    match find_gesture_area(&ItemRc::new(component.clone(), 0), last.center) {
+        Some(area) => {
+            if let Some(gesture_area) = area.downcast::<crate::items::GestureArea>() {
+                gesture_area.as_pin_ref().active.set(true);
+            }
+            gesture_mode(area.downgrade(), points)
+        }
+        None => TouchMode::Scroll { last },
+    }
+}
+
+/// The gesture on the `area`, starting from where the fingers are now.
+fn gesture_mode(area: ItemWeak, points: &[(u64, LogicalPoint)]) -> TouchMode {
+    let last = TouchPointsGeometry::new(points);
+    TouchMode::Gesture {
+        area,
+        last,
+        history: alloc::vec![(crate::animations::Instant::now(), last.center)],
+    }
+}
+
+/// Returns the top-most enabled `GestureArea` that contains the `position`, which is in the
+/// coordinates of the parent of `item`.
+fn find_gesture_area(item: &ItemRc, position: LogicalPoint) -> Option<ItemRc> {
+    let geometry = item.geometry();
+    let contains = geometry.contains(position);
+    if !contains && crate::item_rendering::is_clipping_item(item.borrow()) {
+        return None;
+    }
+    let position_in_item = position - geometry.origin.to_vector();
+    let mut child = item.last_child();
+    while let Some(c) = child {
+        if let Some(area) = find_gesture_area(&c, position_in_item) {
+            return Some(area);
+        }
+        child = c.previous_sibling();
+    }
+    let enabled = item
+        .downcast::<crate::items::GestureArea>()
+        .map_or(false, |gesture_area| gesture_area.as_pin_ref().enabled());
+    (contains && enabled).then(|| item.clone())
+}
+
+/// Calls the callbacks of the `GestureArea` for the movement of the fingers from `last` to `current`.
+fn report_gesture(area: &ItemRc, last: &TouchPointsGeometry, current: &TouchPointsGeometry) {
+    let Some(area) = area.downcast::<crate::items::GestureArea>() else { return };
+    let area = area.as_pin_ref();
+    if !area.enabled() {
+        return;
+    }
+    if last.spread > 0. && current.spread > 0. && current.spread != last.spread {
+        area.scaled.call(&(current.spread / last.spread,));
+    }
+    let mut rotation = current.angle - last.angle;
+    if rotation > 180. {
+        rotation -= 360.;
+    } else if rotation < -180. {
+        rotation += 360.;
+    }
+    if rotation != 0. {
+        area.rotated.call(&(rotation,));
+    }
+    let pan = current.center - last.center;
+    if pan != LogicalVector::zero() {
+        area.panned.call(&(pan.to_point().to_untyped(),));
+    }
+}
+
+/// Ends the gesture on the `GestureArea`, and reports a swipe if the fingers moved fast enough
+/// during the last part of the `history`.
+fn end_gesture(area: &ItemWeak, history: &[(crate::animations::Instant, LogicalPoint)]) {
+    let Some(area) = area.upgrade() else { return };
+    let Some(area) = area.downcast::<crate::items::GestureArea>() else { return };
+    let area = area.as_pin_ref();
+    area.active.set(false);
+    let (Some((start_time, start)), Some((end_time, end))) = (history.first(), history.last())
+    else {
+        return;
+    };
+    let duration = end_time.duration_since(*start_time).as_millis() as f32;
+    if duration <= 0. || !area.enabled() {
+        return;
+    }
+    let velocity = (*end - *start).cast::<f32>() / duration;
+    if velocity.length() < SWIPE_MIN_VELOCITY {
+        return;
+    }
+    let direction = if velocity.x.abs() > velocity.y.abs() {
+        if velocity.x > 0. {
+            crate::items::SwipeDirection::Right
+        } else {
+            crate::items::SwipeDirection::Left
+        }
+    } else if velocity.y > 0. {
+        crate::items::SwipeDirection::Down
+    } else {
+        crate::items::SwipeDirection::Up
+    };
+    area.swiped.call(&(direction,));
+}
+
 /// The TextCursorBlinker takes care of providing a toggled boolean property
 /// that can be used to animate a blinking cursor. It's typically stored in the
 /// Window using a Weak and set_binding() can be used to set up a binding on a given
@@ -304,7 +304,6 @@ fn draw_text_input(
         _self_rc: &ItemRc,
         _size: LogicalSize,
     );
-    #[cfg(feature = "std")]
     fn draw_path(&mut self, path: Pin<&Path>, _self_rc: &ItemRc, _size: LogicalSize);
     fn draw_box_shadow(
         &mut self,
@@ -420,11 +419,132 @@ fn metrics(&self) -> crate::graphics::rendering_metrics_collector::RenderingMetr
     }
 }
 
+/// The geometry of an item, as cached by the [`PartialRenderer`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CachedItemGeometry {
+    /// The geometry of the item, relative to its parent
+    pub geometry: LogicalRect,
+    /// The area, relative to the parent, where the item draws. This is larger than the geometry
+    /// for items that draw outside of it, like box shadows.
+    pub bounding_rect: LogicalRect,
+}
+
+impl CachedItemGeometry {
+    fn new(item_rc: &ItemRc) -> Self {
+        let geometry = item_rc.geometry();
+        let item = item_rc.borrow();
+        let bounding_rect = if let Some(shadow) = ItemRef::downcast_pin::<BoxShadow>(item) {
+            let blur = shadow.blur().get().max(0 as Coord);
+            geometry
+                .translate(LogicalVector::from_lengths(shadow.offset_x(), shadow.offset_y()))
+                .inflate(blur, blur)
+        } else if let Some(rotate) = ItemRef::downcast_pin::<Rotate>(item) {
+            let origin =
+                LogicalVector::from_lengths(rotate.rotation_origin_x(), rotate.rotation_origin_y())
+                    .cast::<f32>();
+            let transform = euclid::default::Transform2D::translation(-origin.x, -origin.y)
+                .then_rotate(euclid::Angle::degrees(rotate.rotation_angle()))
+                .then_translate(origin.to_untyped());
+            let rect = transform
+                .outer_transformed_rect(&LogicalRect::from(geometry.size).cast().to_untyped());
+            LogicalRect::from_untyped(&rect.cast()).translate(geometry.origin.to_vector())
+        } else {
+            geometry
+        };
+        Self { geometry, bounding_rect }
+    }
+}
+
 /// The cache that needs to be held by the Window for the partial rendering
-pub type PartialRenderingCache = RenderingCache<LogicalRect>;
+pub type PartialRenderingCache = RenderingCache<CachedItemGeometry>;
 
-/// FIXME: Should actually be a region and not just a rectangle
-pub type DirtyRegion = euclid::Box2D<Coord, LogicalPx>;
+/// A region of the window that needs to be redrawn, made of a few disjoint rectangles
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DirtyRegion {
+    rectangles: [euclid::Box2D<Coord, LogicalPx>; Self::MAX_COUNT],
+    count: usize,
+}
+
+impl DirtyRegion {
+    /// The maximum number of rectangles. When more rectangles are added, the closest ones are merged.
+    pub const MAX_COUNT: usize = 3;
+
+    /// An iterator over the rectangles of this region
+    pub fn iter(&self) -> impl Iterator<Item = euclid::Box2D<Coord, LogicalPx>> + '_ {
+        self.rectangles[..self.count].iter().copied()
+    }
+
+    /// Add a rectangle to this region
+    pub fn add_rect(&mut self, rect: LogicalRect) {
+        add_box_to_region(&mut self.rectangles, &mut self.count, rect.to_box2d());
+    }
+
+    /// Returns the union of this region with another one
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = *self;
+        for r in other.iter() {
+            add_box_to_region(&mut result.rectangles, &mut result.count, r);
+        }
+        result
+    }
+
+    /// Returns the bounding rectangle of this region
+    pub fn bounding_rect(&self) -> LogicalRect {
+        self.iter().fold(euclid::Box2D::zero(), |acc, r| acc.union(&r)).to_rect()
+    }
+
+    /// Returns true if the region is empty
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+impl From<euclid::Box2D<Coord, LogicalPx>> for DirtyRegion {
+    fn from(rect: euclid::Box2D<Coord, LogicalPx>) -> Self {
+        let mut region = Self::default();
+        add_box_to_region(&mut region.rectangles, &mut region.count, rect);
+        region
+    }
+}
+
+/// Add `rect` to the `count` first rectangles of `rectangles`, while keeping them disjoint.
+/// The rectangles that overlap are merged, and so are the ones that grow the least when there
+/// are too many rectangles.
+pub(crate) fn add_box_to_region<T, U>(
+    rectangles: &mut [euclid::Box2D<T, U>],
+    count: &mut usize,
+    mut rect: euclid::Box2D<T, U>,
+) where
+    T: Copy + PartialOrd + num_traits::NumCast + core::ops::Sub<Output = T>,
+{
+    if rect.is_empty() {
+        return;
+    }
+    let area = |r: &euclid::Box2D<T, U>| {
+        let size = r.size();
+        size.width.to_f32().unwrap_or_default() * size.height.to_f32().unwrap_or_default()
+    };
+    loop {
+        let existing = &rectangles[..*count];
+        let merge_with = if let Some(i) = existing.iter().position(|r| r.intersects(&rect)) {
+            i
+        } else if *count < rectangles.len() {
+            rectangles[*count] = rect;
+            *count += 1;
+            return;
+        } else {
+            (0..*count)
+                .map(|i| (i, area(&existing[i].union(&rect)) - area(&existing[i])))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map_or(0, |(i, _)| i)
+        };
+        // The merged rectangle may now overlap with other rectangles, so it is added again
+        rect = rect.union(&rectangles[merge_with]);
+        *count -= 1;
+        rectangles[merge_with] = rectangles[*count];
+    }
+}
 
 /// Put this structure in the renderer to help with partial rendering
 pub struct PartialRenderer<'a, T> {
@@ -453,6 +573,9 @@ struct ComputeDirtyRegionState {
             old_offset: euclid::Vector2D<Coord, LogicalPx>,
             clipped: LogicalRect,
             must_refresh_children: bool,
+            /// The bounding rect, in window coordinates, of the transformed item that contains
+            /// this item. The item may draw anywhere within it.
+            transformed_bounds: Option<LogicalRect>,
         }
 
         crate::item_tree::visit_items(
@@ -471,14 +594,25 @@ struct ComputeDirtyRegionState {
                         if tr.is_dirty() {
                             let old_geom = *cached_geom;
                             drop(borrowed);
-                            let geom =
-                                crate::properties::evaluate_no_tracking(|| item_rc.geometry());
-
-                            self.mark_dirty_rect(old_geom, state.old_offset, &state.clipped);
-                            self.mark_dirty_rect(geom, state.offset, &state.clipped);
-
-                            new_state.offset += geom.origin.to_vector();
-                            new_state.old_offset += old_geom.origin.to_vector();
+                            let geom = crate::properties::evaluate_no_tracking(|| {
+                                CachedItemGeometry::new(&item_rc)
+                            });
+
+                            self.mark_dirty_rect(
+                                old_geom.bounding_rect,
+                                state.old_offset,
+                                &state.clipped,
+                                state.transformed_bounds,
+                            );
+                            self.mark_dirty_rect(
+                                geom.bounding_rect,
+                                state.offset,
+                                &state.clipped,
+                                state.transformed_bounds,
+                            );
+
+                            new_state.offset += geom.geometry.origin.to_vector();
+                            new_state.old_offset += old_geom.geometry.origin.to_vector();
                             if ItemRef::downcast_pin::<Clip>(item).is_some()
                                 || ItemRef::downcast_pin::<Opacity>(item).is_some()
                             {
@@ -486,6 +620,11 @@ struct ComputeDirtyRegionState {
                                 // the ones outside the element, regardless if they are themselves dirty or not.
                                 new_state.must_refresh_children = true;
                             }
+                            if ItemRef::downcast_pin::<Rotate>(item).is_some() {
+                                new_state.transformed_bounds = state
+                                    .transformed_bounds
+                                    .or(Some(geom.bounding_rect.translate(state.offset)));
+                            }
 
                             ItemVisitorResult::Continue(new_state)
                         } else {
@@ -495,13 +634,25 @@ struct ComputeDirtyRegionState {
                                 || new_state.offset != new_state.old_offset
                             {
                                 self.mark_dirty_rect(
-                                    *cached_geom,
+                                    cached_geom.bounding_rect,
                                     state.old_offset,
                                     &state.clipped,
+                                    state.transformed_bounds,
+                                );
+                                self.mark_dirty_rect(
+                                    cached_geom.bounding_rect,
+                                    state.offset,
+                                    &state.clipped,
+                                    state.transformed_bounds,
                                 );
-                                self.mark_dirty_rect(*cached_geom, state.offset, &state.clipped);
+                            }
+                            if ItemRef::downcast_pin::<Rotate>(item).is_some() {
+                                new_state.transformed_bounds = state
+                                    .transformed_bounds
+                                    .or(Some(cached_geom.bounding_rect.translate(state.offset)));
                             }
 
+                            let cached_geom = cached_geom.geometry;
                             new_state.offset += cached_geom.origin.to_vector();
                             new_state.old_offset += cached_geom.origin.to_vector();
                             if crate::properties::evaluate_no_tracking(|| is_clipping_item(item)) {
@@ -520,18 +671,28 @@ struct ComputeDirtyRegionState {
                     _ => {
                         drop(borrowed);
                         let geom = crate::properties::evaluate_no_tracking(|| {
-                            let geom = item_rc.geometry();
-                            new_state.offset += geom.origin.to_vector();
-                            new_state.old_offset += geom.origin.to_vector();
+                            let geom = CachedItemGeometry::new(&item_rc);
+                            new_state.offset += geom.geometry.origin.to_vector();
+                            new_state.old_offset += geom.geometry.origin.to_vector();
                             if is_clipping_item(item) {
                                 new_state.clipped = new_state
                                     .clipped
-                                    .intersection(&geom.translate(state.offset))
+                                    .intersection(&geom.geometry.translate(state.offset))
                                     .unwrap_or_default();
                             }
                             geom
                         });
-                        self.mark_dirty_rect(geom, state.offset, &state.clipped);
+                        self.mark_dirty_rect(
+                            geom.bounding_rect,
+                            state.offset,
+                            &state.clipped,
+                            state.transformed_bounds,
+                        );
+                        if ItemRef::downcast_pin::<Rotate>(item).is_some() {
+                            new_state.transformed_bounds = state
+                                .transformed_bounds
+                                .or(Some(geom.bounding_rect.translate(state.offset)));
+                        }
                         ItemVisitorResult::Continue(new_state)
                     }
                 }
@@ -541,6 +702,7 @@ struct ComputeDirtyRegionState {
                 old_offset: origin.to_vector(),
                 clipped: euclid::rect(0 as Coord, 0 as Coord, Coord::MAX, Coord::MAX),
                 must_refresh_children: false,
+                transformed_bounds: None,
             },
         );
     }
@@ -550,10 +712,12 @@ fn mark_dirty_rect(
         rect: LogicalRect,
         offset: euclid::Vector2D<Coord, LogicalPx>,
         clip_rect: &LogicalRect,
+        transformed_bounds: Option<LogicalRect>,
     ) {
         if !rect.is_empty() {
-            if let Some(rect) = rect.translate(offset).intersection(clip_rect) {
-                self.dirty_region = self.dirty_region.union(&rect.to_box2d());
+            let rect = transformed_bounds.unwrap_or_else(|| rect.translate(offset));
+            if let Some(rect) = rect.intersection(clip_rect) {
+                self.dirty_region.add_rect(rect);
             }
         }
     }
@@ -561,17 +725,21 @@ fn mark_dirty_rect(
     fn do_rendering(
         cache: &RefCell<PartialRenderingCache>,
         rendering_data: &CachedRenderingData,
-        render_fn: impl FnOnce() -> LogicalRect,
+        render_fn: impl FnOnce() -> CachedItemGeometry,
     ) {
-        let mut cache = cache.borrow_mut();
-        if let Some(entry) = rendering_data.get_entry(&mut cache) {
-            entry
-                .dependency_tracker
-                .get_or_insert_with(|| Box::pin(PropertyTracker::default()))
-                .as_ref()
-                .evaluate(render_fn);
+        // The cache is not borrowed during the rendering, because the renderer may render the
+        // children of the item itself, for example in a layer, with another PartialRenderer
+        let tracker = rendering_data.get_entry(&mut cache.borrow_mut()).map(|entry| {
+            entry.dependency_tracker.take().unwrap_or_else(|| Box::pin(PropertyTracker::default()))
+        });
+        if let Some(tracker) = tracker {
+            tracker.as_ref().evaluate(render_fn);
+            if let Some(entry) = rendering_data.get_entry(&mut cache.borrow_mut()) {
+                entry.dependency_tracker = Some(tracker);
+            }
         } else {
             let cache_entry = crate::graphics::CachedGraphicsData::new(render_fn);
+            let mut cache = cache.borrow_mut();
             rendering_data.cache_index.set(cache.insert(cache_entry));
             rendering_data.cache_generation.set(cache.generation());
         }
@@ -589,7 +757,7 @@ fn $fn(&mut self, obj: Pin<&$Ty>, item_rc: &ItemRc, size: LogicalSize) $(-> $Ret
             let mut ret = None;
             Self::do_rendering(&self.cache, &obj.cached_rendering_data, || {
                 ret = Some(self.actual_renderer.$fn(obj, item_rc, size));
-                item_rc.geometry()
+                CachedItemGeometry::new(item_rc)
             });
             ret.unwrap_or_default()
         }
@@ -604,7 +772,7 @@ fn filter_item(&mut self, item_rc: &ItemRc) -> (bool, LogicalRect) {
                 // Make sure we register a dependency on the clip
                 clip.clip();
             }
-            item_rc.geometry()
+            CachedItemGeometry::new(item_rc)
         };
 
         let rendering_data = item.cached_rendering_data_offset();
@@ -629,8 +797,8 @@ fn filter_item(&mut self, item_rc: &ItemRc) -> (bool, LogicalRect) {
         //let clip = self.get_current_clip().intersection(&self.dirty_region.to_rect());
         //let draw = clip.map_or(false, |r| r.intersects(&item_geometry));
         //FIXME: the dirty_region is in global coordinate but item_geometry and current_clip is not
-        let draw = self.get_current_clip().intersects(&item_geometry);
-        (draw, item_geometry)
+        let draw = self.get_current_clip().intersects(&item_geometry.bounding_rect);
+        (draw, item_geometry.geometry)
     }
 
     forward_rendering_call!(fn draw_rectangle(Rectangle));
@@ -639,12 +807,12 @@ fn filter_item(&mut self, item_rc: &ItemRc) -> (bool, LogicalRect) {
     forward_rendering_call!(fn draw_clipped_image(ClippedImage));
     forward_rendering_call!(fn draw_text(Text));
     forward_rendering_call!(fn draw_text_input(TextInput));
-    #[cfg(feature = "std")]
     forward_rendering_call!(fn draw_path(Path));
     forward_rendering_call!(fn draw_box_shadow(BoxShadow));
 
     forward_rendering_call!(fn visit_clip(Clip) -> RenderingResult);
     forward_rendering_call!(fn visit_opacity(Opacity) -> RenderingResult);
+    forward_rendering_call!(fn visit_layer(Layer) -> RenderingResult);
 
     fn combine_clip(
         &mut self,
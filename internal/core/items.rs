@@ -50,9 +50,7 @@
 pub use text::*;
 mod image;
 pub use self::image::*;
-#[cfg(feature = "std")]
 mod path;
-#[cfg(feature = "std")]
 pub use path::*;
 
 /// Alias for `&mut dyn ItemRenderer`. Required so cbindgen generates the ItemVTable
@@ -65,6 +63,8 @@
 type PointerEventArg = (PointerEvent,);
 type PointerScrollEventArg = (PointerScrollEvent,);
 type PointArg = (Point,);
+type FloatArg = (f32,);
+type SwipeDirectionArg = (SwipeDirection,);
 
 #[cfg(all(feature = "ffi", windows))]
 #[macro_export]
@@ -717,6 +717,92 @@ impl ItemConsts for FocusScope {
     fn slint_get_FocusScopeVTable() -> FocusScopeVTable for FocusScope
 }
 
+/// The implementation of the `GestureArea` element
+///
+/// The gestures themselves are recognized from the touch points by the window, see
+/// [`crate::input::TouchInputState`]. The item only receives the result.
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct GestureArea {
+    pub enabled: Property<bool>,
+    pub active: Property<bool>,
+    pub scaled: Callback<FloatArg>,
+    pub rotated: Callback<FloatArg>,
+    pub panned: Callback<PointArg>,
+    pub swiped: Callback<SwipeDirectionArg>,
+    /// FIXME: remove this
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for GestureArea {
+    fn init(self: Pin<&Self>, _self_rc: &ItemRc) {}
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo { stretch: 1., ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+        _size: LogicalSize,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl ItemConsts for GestureArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        GestureArea,
+        CachedRenderingData,
+    > = GestureArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn slint_get_GestureAreaVTable() -> GestureAreaVTable for GestureArea
+}
+
 #[repr(C)]
 #[derive(FieldOffsets, Default, SlintElement)]
 #[pin]
@@ -1312,7 +1398,6 @@ fn slint_get_ImageItemVTable() -> ImageItemVTable for ImageItem
     fn slint_get_ClippedImageVTable() -> ClippedImageVTable for ClippedImage
 }
 
-#[cfg(feature = "std")]
 declare_item_vtable! {
     fn slint_get_PathVTable() -> PathVTable for Path
 }
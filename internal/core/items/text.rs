@@ -580,11 +580,9 @@ fn focus_event(
                 WindowInner::from_pub(window_adapter.window()).set_text_input_focused(true);
                 // FIXME: This should be tracked by a PropertyTracker in window and toggled when read_only() toggles.
                 if !self.read_only() {
-                    if let Some(w) = window_adapter.internal(crate::InternalToken) {
-                        w.input_method_request(InputMethodRequest::Enable(
-                            self.ime_properties(window_adapter, self_rc),
-                        ));
-                    }
+                    window_adapter.input_method_request(InputMethodRequest::Enable(
+                        self.ime_properties(window_adapter, self_rc),
+                    ));
                 }
             }
             FocusEvent::FocusOut | FocusEvent::WindowLostFocus => {
@@ -597,10 +595,8 @@ fn focus_event(
                 }
                 WindowInner::from_pub(window_adapter.window()).set_text_input_focused(false);
                 if !self.read_only() {
-                    if let Some(window_adapter) = window_adapter.internal(crate::InternalToken) {
-                        window_adapter.input_method_request(InputMethodRequest::Disable);
-                        self.preedit_text.set(Default::default());
-                    }
+                    window_adapter.input_method_request(InputMethodRequest::Disable);
+                    self.preedit_text.set(Default::default());
                 }
             }
         }
@@ -912,11 +908,9 @@ fn update_ime(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>, self_rc:
         if self.read_only() {
             return;
         }
-        if let Some(w) = window_adapter.internal(crate::InternalToken) {
-            w.input_method_request(InputMethodRequest::Update(
-                self.ime_properties(window_adapter, self_rc),
-            ));
-        }
+        window_adapter.input_method_request(InputMethodRequest::Update(
+            self.ime_properties(window_adapter, self_rc),
+        ));
     }
 
     fn select_and_delete(
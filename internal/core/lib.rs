@@ -72,7 +72,6 @@
 #[doc(inline)]
 pub use graphics::RgbaColor;
 
-#[cfg(feature = "std")]
 #[doc(inline)]
 pub use graphics::PathData;
 
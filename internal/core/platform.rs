@@ -14,7 +14,9 @@
 pub use crate::software_renderer;
 #[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
 use crate::unsafe_single_threaded::{thread_local, OnceCell};
-pub use crate::window::{LayoutConstraints, WindowAdapter, WindowProperties};
+pub use crate::window::{
+    InputMethodProperties, InputMethodRequest, LayoutConstraints, WindowAdapter, WindowProperties,
+};
 use crate::SharedString;
 use alloc::boxed::Box;
 use alloc::rc::Rc;
@@ -240,7 +242,9 @@ pub fn duration_until_next_timer_update() -> Option<core::time::Duration> {
 
 // reexport key enum to the public api
 pub use crate::input::key_codes::Key;
+pub use crate::input::KeyboardModifiers;
 pub use crate::input::PointerEventButton;
+pub use crate::items::InputType;
 
 /// A event that describes user input or windowing system events.
 ///
@@ -302,6 +306,38 @@ pub enum WindowEvent {
         /// ```
         text: SharedString,
     },
+    /// A key was pressed, with the details that the windowing system reports about the key.
+    ///
+    /// Create it with [`WindowEvent::key_pressed_with_details()`].
+    #[non_exhaustive]
+    KeyPressedWithDetails {
+        /// The unicode representation of the key pressed, see [`WindowEvent::KeyPressed::text`].
+        text: SharedString,
+        /// The modifier keys held down when the key was pressed.
+        ///
+        /// With [`WindowEvent::KeyPressed`], Slint tracks the modifiers from the key events of the
+        /// modifier keys, such as [`Key::Shift`]. These are used instead.
+        modifiers: KeyboardModifiers,
+        /// The physical key, independent of the keyboard layout. It's named like the `code` of the
+        /// keyboard events of the W3C UI Events specification, for example `"KeyZ"` for the key
+        /// right of the left shift key on a QWERTY keyboard, or empty if the windowing system doesn't
+        /// report it, like with the Qt backend.
+        physical_key: SharedString,
+        /// True if the key is held down and this event is an automatic repetition of the key press.
+        repeat: bool,
+    },
+    /// A key was released, with the details that the windowing system reports about the key.
+    ///
+    /// Create it with [`WindowEvent::key_released_with_details()`].
+    #[non_exhaustive]
+    KeyReleasedWithDetails {
+        /// The unicode representation of the key released, see [`WindowEvent::KeyReleased::text`].
+        text: SharedString,
+        /// The modifier keys held down when the key was released, see [`WindowEvent::KeyPressedWithDetails::modifiers`].
+        modifiers: KeyboardModifiers,
+        /// The physical key, independent of the keyboard layout, see [`WindowEvent::KeyPressedWithDetails::physical_key`].
+        physical_key: SharedString,
+    },
     /// The window's scale factor has changed. This can happen for example when the display's resolution
     /// changes, the user selects a new scale factor in the system settings, or the window is moved to a
     /// different screen.
@@ -332,9 +368,87 @@ pub enum WindowEvent {
     /// The backend should dispatch this event with true when the window gains focus
     /// and false when the window loses focus.
     WindowActiveChanged(bool),
+
+    /// A finger touched a touch-enabled surface.
+    ///
+    /// Backends that can tell the contact points of a touch screen apart should dispatch the touch
+    /// variants instead of the pointer variants. The first finger is delivered to the items like a
+    /// pointer with the left button pressed, and further fingers form the gestures recognized by
+    /// the `GestureArea` element.
+    TouchPressed {
+        /// Identifies the finger for as long as it is in contact with the surface.
+        id: u64,
+        position: LogicalPosition,
+    },
+    /// A finger moved on a touch-enabled surface.
+    TouchMoved {
+        /// The identifier that the finger had in [`WindowEvent::TouchPressed`].
+        id: u64,
+        position: LogicalPosition,
+    },
+    /// A finger was lifted from a touch-enabled surface.
+    TouchReleased {
+        /// The identifier that the finger had in [`WindowEvent::TouchPressed`].
+        id: u64,
+        position: LogicalPosition,
+    },
+    /// The windowing system stopped tracking a finger, for example because the window lost the focus.
+    TouchCancelled {
+        /// The identifier that the finger had in [`WindowEvent::TouchPressed`].
+        id: u64,
+    },
+    /// The input method changed the text it's composing, also called the pre-edit text. The focused
+    /// text input field shows it at its cursor position, until the input method commits the text
+    /// or the field loses the focus.
+    ///
+    /// Dispatch this event with an empty `text` to cancel the composition.
+    InputMethodPreedit {
+        /// The text being composed, which replaces the previous pre-edit text.
+        text: SharedString,
+        /// The position, in bytes within `text`, where the selection within the pre-edit text starts.
+        cursor_start: usize,
+        /// The position, in bytes within `text`, where the selection within the pre-edit text ends and
+        /// the cursor is shown. Set both positions to the length of `text` to show the cursor at the end.
+        ///
+        /// Positions past the end of `text` are clamped to its length, and positions within a
+        /// character are moved to the start of that character.
+        cursor_end: usize,
+    },
+    /// The input method finished composing. The focused text input field replaces the pre-edit text
+    /// with `text` and inserts it at its cursor position.
+    InputMethodCommit {
+        /// The text to insert.
+        text: SharedString,
+    },
 }
 
 impl WindowEvent {
+    /// Creates a [`WindowEvent::KeyPressedWithDetails`] event.
+    ///
+    /// # Example
+    /// ```rust
+    /// use slint::platform::{KeyboardModifiers, WindowEvent};
+    /// let modifiers = KeyboardModifiers { control: true, ..Default::default() };
+    /// let _ = WindowEvent::key_pressed_with_details("z".into(), modifiers, "KeyZ".into(), false);
+    /// ```
+    pub fn key_pressed_with_details(
+        text: SharedString,
+        modifiers: KeyboardModifiers,
+        physical_key: SharedString,
+        repeat: bool,
+    ) -> Self {
+        Self::KeyPressedWithDetails { text, modifiers, physical_key, repeat }
+    }
+
+    /// Creates a [`WindowEvent::KeyReleasedWithDetails`] event.
+    pub fn key_released_with_details(
+        text: SharedString,
+        modifiers: KeyboardModifiers,
+        physical_key: SharedString,
+    ) -> Self {
+        Self::KeyReleasedWithDetails { text, modifiers, physical_key }
+    }
+
     /// The position of the cursor for this event, if any
     pub fn position(&self) -> Option<LogicalPosition> {
         match self {
@@ -342,6 +456,9 @@ pub fn position(&self) -> Option<LogicalPosition> {
             WindowEvent::PointerReleased { position, .. } => Some(*position),
             WindowEvent::PointerMoved { position } => Some(*position),
             WindowEvent::PointerScrolled { position, .. } => Some(*position),
+            WindowEvent::TouchPressed { position, .. } => Some(*position),
+            WindowEvent::TouchMoved { position, .. } => Some(*position),
+            WindowEvent::TouchReleased { position, .. } => Some(*position),
             _ => None,
         }
     }
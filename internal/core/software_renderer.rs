@@ -7,15 +7,20 @@
 
 #![warn(missing_docs)]
 
+mod box_shadow;
 mod draw_functions;
 mod fonts;
+mod item_cache;
+mod packed_pixels;
+mod path;
 
 use self::fonts::GlyphRenderer;
 use crate::api::Window;
+use crate::graphics::boxshadowcache::BoxShadowOptions;
 use crate::graphics::rendering_metrics_collector::{RefreshMode, RenderingMetricsCollector};
 use crate::graphics::{IntRect, PixelFormat, SharedImageBuffer, SharedPixelBuffer};
 use crate::item_rendering::ItemRenderer;
-use crate::items::{ImageFit, ItemRc, TextOverflow};
+use crate::items::{ImageFit, ItemRc, RenderingResult, TextOverflow};
 use crate::lengths::{
     LogicalLength, LogicalPoint, LogicalRect, LogicalSize, LogicalVector, PhysicalPx, PointLengths,
     RectLengths, ScaleFactor, SizeLengths,
@@ -34,15 +39,16 @@
 use num_traits::Float;
 use num_traits::NumCast;
 
-pub use draw_functions::{PremultipliedRgbaColor, Rgb565Pixel, TargetPixel};
+pub use draw_functions::{
+    Bgra8888Pixel, Gray8Pixel, PremultipliedRgbaColor, Rgb565Pixel, TargetPixel,
+};
+pub use packed_pixels::{Dithering, GrayscalePacker, PackedLineBuffer, PackedPixelFormat};
 
 type PhysicalLength = euclid::Length<i16, PhysicalPx>;
 type PhysicalRect = euclid::Rect<i16, PhysicalPx>;
 type PhysicalSize = euclid::Size2D<i16, PhysicalPx>;
 type PhysicalPoint = euclid::Point2D<i16, PhysicalPx>;
 
-type DirtyRegion = PhysicalRect;
-
 /// This enum describes which parts of the buffer passed to the [`SoftwareRenderer`] may be re-used to speed up painting.
 // FIXME: #[non_exhaustive] #3023
 #[derive(PartialEq, Eq, Debug, Clone, Default, Copy)]
@@ -175,7 +181,10 @@ pub trait LineBufferProvider {
     /// The pixel type of the buffer
     type TargetPixel: TargetPixel;
 
-    /// Called once per line, you will have to call the render_fn back with the buffer.
+    /// Called for each line, you will have to call the render_fn back with the buffer.
+    ///
+    /// When the dirty region is made of several rectangles that share some lines, this function
+    /// is called several times for the same line, once per rectangle, with disjoint ranges.
     ///
     /// The `line` is the y position of the line to be drawn.
     /// The `range` is the range within the line that is going to be rendered (eg, within the dirty region)
@@ -193,16 +202,77 @@ fn process_line(
 ///
 /// The region may be composed of multiple sub-regions.
 #[derive(Clone, Debug, Default)]
-pub struct PhysicalRegion(PhysicalRect);
+pub struct PhysicalRegion {
+    rectangles: [euclid::Box2D<i16, PhysicalPx>; crate::item_rendering::DirtyRegion::MAX_COUNT],
+    count: usize,
+}
 
 impl PhysicalRegion {
+    fn from_rect(rect: PhysicalRect) -> Self {
+        let mut region = Self::default();
+        region.add_rect(rect);
+        region
+    }
+
+    fn add_rect(&mut self, rect: PhysicalRect) {
+        crate::item_rendering::add_box_to_region(
+            &mut self.rectangles,
+            &mut self.count,
+            rect.to_box2d(),
+        );
+    }
+
+    fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for r in other.iter_rects() {
+            result.add_rect(r);
+        }
+        result
+    }
+
+    fn intersection(&self, rect: &PhysicalRect) -> Self {
+        let mut result = Self::default();
+        for r in self.iter_rects() {
+            if let Some(r) = r.intersection(rect) {
+                result.add_rect(r);
+            }
+        }
+        result
+    }
+
+    fn iter_rects(&self) -> impl Iterator<Item = PhysicalRect> + '_ {
+        self.rectangles[..self.count].iter().map(|r| r.to_rect())
+    }
+
+    fn bounding_rect(&self) -> PhysicalRect {
+        self.rectangles[..self.count]
+            .iter()
+            .fold(euclid::Box2D::zero(), |acc, r| acc.union(r))
+            .to_rect()
+    }
+
     /// Returns the size of the bounding box of this region.
     pub fn bounding_box_size(&self) -> crate::api::PhysicalSize {
-        crate::api::PhysicalSize { width: self.0.width() as _, height: self.0.height() as _ }
+        let rect = self.bounding_rect();
+        crate::api::PhysicalSize { width: rect.width() as _, height: rect.height() as _ }
     }
     /// Returns the origin of the bounding box of this region.
     pub fn bounding_box_origin(&self) -> crate::api::PhysicalPosition {
-        crate::api::PhysicalPosition { x: self.0.origin.x as _, y: self.0.origin.y as _ }
+        let rect = self.bounding_rect();
+        crate::api::PhysicalPosition { x: rect.origin.x as _, y: rect.origin.y as _ }
+    }
+
+    /// Returns an iterator over the rectangles of this region, as their origin and size.
+    /// The rectangles don't overlap.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (crate::api::PhysicalPosition, crate::api::PhysicalSize)> + '_ {
+        self.iter_rects().map(|r| {
+            (
+                crate::api::PhysicalPosition { x: r.origin.x as _, y: r.origin.y as _ },
+                crate::api::PhysicalSize { width: r.width() as _, height: r.height() as _ },
+            )
+        })
     }
 }
 
@@ -217,6 +287,15 @@ pub fn bounding_box_origin(&self) -> crate::api::PhysicalPosition {
 ///     in one single buffer
 pub struct SoftwareRenderer {
     partial_cache: RefCell<crate::item_rendering::PartialRenderingCache>,
+    /// The alpha maps of the box shadows, with the options they were rendered with
+    box_shadow_cache: item_cache::ItemCache<(BoxShadowOptions, box_shadow::BoxShadowImage)>,
+    /// The pixels of the Opacity and Layer elements that are rendered in a layer
+    layer_cache: item_cache::ItemCache<LayerPixels>,
+    /// The pixels of the items drawn with `SceneBuilder::draw_indirect`, before they are
+    /// transformed and clipped
+    indirect_cache: item_cache::ItemCache<LayerPixels>,
+    /// The pixmaps drawn with draw_cached_pixmap
+    pixmap_cache: item_cache::ItemCache<Option<SharedImageBuffer>>,
     repaint_buffer_type: Cell<RepaintBufferType>,
     /// This is the area which we are going to redraw in the next frame, no matter if the items are dirty or not
     force_dirty: Cell<crate::item_rendering::DirtyRegion>,
@@ -224,7 +303,7 @@ pub struct SoftwareRenderer {
     force_screen_refresh: Cell<bool>,
     /// This is the area which was dirty on the previous frame.
     /// Only used if repaint_buffer_type == RepaintBufferType::SwappedBuffers
-    prev_frame_dirty: Cell<DirtyRegion>,
+    prev_frame_dirty: Cell<PhysicalRegion>,
     maybe_window_adapter: RefCell<Option<Weak<dyn crate::window::WindowAdapter>>>,
     rotation: Cell<WindowRotation>,
     rendering_metrics_collector: Option<Rc<RenderingMetricsCollector>>,
@@ -234,6 +313,10 @@ impl Default for SoftwareRenderer {
     fn default() -> Self {
         Self {
             partial_cache: Default::default(),
+            box_shadow_cache: Default::default(),
+            layer_cache: Default::default(),
+            indirect_cache: Default::default(),
+            pixmap_cache: Default::default(),
             repaint_buffer_type: Default::default(),
             force_dirty: Default::default(),
             force_screen_refresh: Default::default(),
@@ -292,26 +375,40 @@ pub fn window_rotation(&self) -> WindowRotation {
     /// Returns the region to actually draw.
     fn apply_dirty_region(
         &self,
-        mut dirty_region: DirtyRegion,
+        mut dirty_region: PhysicalRegion,
         screen_size: PhysicalSize,
-    ) -> DirtyRegion {
+    ) -> PhysicalRegion {
         let screen_region = PhysicalRect { origin: euclid::point2(0, 0), size: screen_size };
 
         if self.force_screen_refresh.take() {
-            dirty_region = screen_region;
+            dirty_region = PhysicalRegion::from_rect(screen_region);
         }
 
         match self.repaint_buffer_type() {
-            RepaintBufferType::NewBuffer => {
-                PhysicalRect { origin: euclid::point2(0, 0), size: screen_size }
-            }
+            RepaintBufferType::NewBuffer => PhysicalRegion::from_rect(screen_region),
             RepaintBufferType::ReusedBuffer => dirty_region,
             RepaintBufferType::SwappedBuffers => {
-                dirty_region.union(&self.prev_frame_dirty.replace(dirty_region))
+                dirty_region.union(&self.prev_frame_dirty.replace(dirty_region.clone()))
             }
         }
         .intersection(&screen_region)
-        .unwrap_or_default()
+    }
+
+    /// Convert the dirty region computed by the partial renderer to physical pixels
+    fn physical_dirty_region(
+        dirty_region: &crate::item_rendering::DirtyRegion,
+        factor: ScaleFactor,
+    ) -> PhysicalRegion {
+        let mut region = PhysicalRegion::default();
+        for rect in dirty_region.iter() {
+            if let Some(rect) = (rect.to_rect().cast() * factor)
+                .round_out()
+                .intersection(&euclid::rect(0., 0., i16::MAX as f32, i16::MAX as f32))
+            {
+                region.add_rect(rect.cast());
+            }
+        }
+        region
     }
 
     /// Render the window to the given frame buffer.
@@ -366,6 +463,7 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], pixel_stride: usize) -> Ph
             window_inner,
             RenderToBuffer { buffer, stride: pixel_stride },
             rotation,
+            self,
         );
         let mut renderer = crate::item_rendering::PartialRenderer::new(
             &self.partial_cache,
@@ -379,37 +477,35 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], pixel_stride: usize) -> Ph
                     renderer.compute_dirty_regions(component, *origin);
                 }
 
-                let dirty_region = (renderer.dirty_region.to_rect().cast() * factor)
-                    .round_out()
-                    .intersection(&euclid::rect(0., 0., i16::MAX as f32, i16::MAX as f32))
-                    .unwrap_or_default()
-                    .cast();
-
+                let dirty_region = Self::physical_dirty_region(&renderer.dirty_region, factor);
                 let to_draw = self.apply_dirty_region(dirty_region, size);
 
-                renderer.combine_clip(
-                    (to_draw.cast() / factor).cast(),
-                    LogicalLength::zero(),
-                    LogicalLength::zero(),
-                );
+                let bg = background_fill_color(&background);
 
-                let mut bg = TargetPixel::background();
-                // TODO: gradient background
-                TargetPixel::blend(&mut bg, background.color().into());
-                let to_draw_tr = to_draw.transformed(renderer.actual_renderer.rotation);
-                for line in to_draw_tr.min_y()..to_draw_tr.max_y() {
-                    let begin = line as usize * pixel_stride + to_draw_tr.origin.x as usize;
-                    renderer.actual_renderer.processor.buffer[begin..]
-                        [..to_draw_tr.width() as usize]
-                        .fill(bg);
-                }
+                for rect in to_draw.iter_rects() {
+                    let rect_tr = rect.transformed(renderer.actual_renderer.rotation);
+                    for line in rect_tr.min_y()..rect_tr.max_y() {
+                        let begin = line as usize * pixel_stride + rect_tr.origin.x as usize;
+                        renderer.actual_renderer.processor.buffer[begin..]
+                            [..rect_tr.width() as usize]
+                            .fill(bg);
+                    }
 
-                for (component, origin) in components {
-                    crate::item_rendering::render_component_items(
-                        component,
-                        &mut renderer,
-                        *origin,
+                    renderer.save_state();
+                    renderer.combine_clip(
+                        (rect.cast() / factor).cast(),
+                        LogicalLength::zero(),
+                        LogicalLength::zero(),
                     );
+                    renderer.actual_renderer.draw_window_background(&background);
+                    for (component, origin) in components {
+                        crate::item_rendering::render_component_items(
+                            component,
+                            &mut renderer,
+                            *origin,
+                        );
+                    }
+                    renderer.restore_state();
                 }
 
                 if let Some(metrics) = &self.rendering_metrics_collector {
@@ -419,7 +515,7 @@ pub fn render(&self, buffer: &mut [impl TargetPixel], pixel_stride: usize) -> Ph
                     }
                 }
 
-                PhysicalRegion(to_draw)
+                to_draw
             })
             .unwrap_or_default()
     }
@@ -480,8 +576,90 @@ pub fn render_by_line(&self, line_buffer: impl LineBufferProvider) -> PhysicalRe
                 line_buffer,
             )
         } else {
-            PhysicalRegion(Default::default())
+            Default::default()
+        }
+    }
+
+    /// Render the window to the given frame buffer, like [`Self::render()`], but rasterize the
+    /// dirty region with `thread_count` threads in parallel, each in a horizontal band of the buffer.
+    ///
+    /// The items are still only accessed from the calling thread, which prepares the scene
+    /// before it is rasterized by the other threads.
+    ///
+    /// Returns the dirty region for this frame, in the window frame of reference.
+    #[cfg(feature = "software-renderer-threads")]
+    pub fn render_with_threads(
+        &self,
+        buffer: &mut [impl TargetPixel + Send],
+        pixel_stride: usize,
+        thread_count: usize,
+    ) -> PhysicalRegion {
+        let Some(window) = self.maybe_window_adapter.borrow().as_ref().and_then(|w| w.upgrade())
+        else {
+            return Default::default();
+        };
+        let window_inner = WindowInner::from_pub(window.window());
+        let Some(window_item) = window_inner.window_item() else { return Default::default() };
+        let window_item = window_item.as_pin_ref();
+        let factor = ScaleFactor::new(window_inner.scale_factor());
+        let size: PhysicalSize =
+            (LogicalSize::from_lengths(window_item.width(), window_item.height()).cast() * factor)
+                .cast();
+        if size.is_empty() {
+            return Default::default();
         }
+        let rotation = RotationInfo { orientation: self.rotation.get(), screen_size: size };
+        assert!(
+            if rotation.orientation.is_transpose() {
+                pixel_stride >= size.height as usize && buffer.len() >= (size.width as usize * pixel_stride + size.height as usize) - pixel_stride
+            } else {
+                pixel_stride >= size.width as usize && buffer.len() >= (size.height as usize * pixel_stride + size.width as usize) - pixel_stride
+            },
+            "buffer of size {} with stride {pixel_stride} is too small to handle a window of size {size:?}", buffer.len()
+        );
+
+        let (dirty_region, scenes) = prepare_scene(window_inner, size, self);
+        let scenes = scenes
+            .iter()
+            .filter(|(scene, _)| !scene.dirty_region.is_empty())
+            .map(|(scene, vectors)| (scene, vectors.data()))
+            .collect::<Vec<_>>();
+
+        let background_color = background_fill_color(&window_item.background());
+
+        // The threads rasterize horizontal bands of the buffer, each with the part of all the
+        // rectangles of the region within the band, since these may share lines.
+        let first_line = scenes.iter().map(|(scene, _)| scene.dirty_region.min_y()).min();
+        let last_line = scenes.iter().map(|(scene, _)| scene.dirty_region.max_y()).max();
+        let (Some(first_line), Some(last_line)) = (first_line, last_line) else {
+            return dirty_region;
+        };
+        let band_height = {
+            let thread_count = thread_count.max(1);
+            ((last_line - first_line) as usize + thread_count - 1) / thread_count
+        };
+        let scenes = &scenes;
+        std::thread::scope(|scope| {
+            let mut bands = buffer[first_line as usize * pixel_stride..]
+                .chunks_mut(band_height * pixel_stride)
+                .zip((first_line..last_line).step_by(band_height))
+                .map(|(band_buffer, band_y)| {
+                    let lines = band_y..(band_y + band_height as i16).min(last_line);
+                    (band_buffer, lines)
+                })
+                .collect::<Vec<_>>();
+            // The calling thread rasterizes the last band
+            let last_band = bands.pop();
+            for (band_buffer, lines) in bands {
+                scope.spawn(move || {
+                    render_scenes_band(scenes, lines, background_color, band_buffer, pixel_stride)
+                });
+            }
+            if let Some((band_buffer, lines)) = last_band {
+                render_scenes_band(scenes, lines, background_color, band_buffer, pixel_stride)
+            }
+        });
+        dirty_region
     }
 }
 
@@ -624,12 +802,16 @@ fn text_input_cursor_rect_for_byte_offset(
 
     fn free_graphics_resources(
         &self,
-        _component: crate::item_tree::ItemTreeRef,
+        component: crate::item_tree::ItemTreeRef,
         items: &mut dyn Iterator<Item = Pin<crate::items::ItemRef<'_>>>,
     ) -> Result<(), crate::platform::PlatformError> {
         for item in items {
             item.cached_rendering_data_offset().release(&mut self.partial_cache.borrow_mut());
         }
+        self.box_shadow_cache.component_destroyed(component);
+        self.layer_cache.component_destroyed(component);
+        self.indirect_cache.component_destroyed(component);
+        self.pixmap_cache.component_destroyed(component);
         // We don't have a way to determine the screen region of the delete items, what's in the cache is relative. So
         // as a last resort, refresh everything.
         self.force_screen_refresh.set(true);
@@ -667,9 +849,23 @@ fn default_font_size(&self) -> LogicalLength {
     fn set_window_adapter(&self, window_adapter: &Rc<dyn WindowAdapter>) {
         *self.maybe_window_adapter.borrow_mut() = Some(Rc::downgrade(window_adapter));
         self.partial_cache.borrow_mut().clear();
+        self.box_shadow_cache.clear();
+        self.layer_cache.clear();
+        self.indirect_cache.clear();
+        self.pixmap_cache.clear();
     }
 }
 
+/// The color the lines are filled with before the items are drawn. A gradient is drawn over
+/// the transparent background by [`SceneBuilder::draw_window_background`].
+fn background_fill_color<T: TargetPixel>(background: &Brush) -> T {
+    let mut color = T::background();
+    if let Brush::SolidColor(background) = background {
+        color.blend((*background).into());
+    }
+    color
+}
+
 fn render_window_frame_by_line(
     window: &WindowInner,
     background: Brush,
@@ -677,102 +873,166 @@ fn render_window_frame_by_line(
     renderer: &SoftwareRenderer,
     mut line_buffer: impl LineBufferProvider,
 ) -> PhysicalRegion {
-    let rotation = RotationInfo { orientation: renderer.rotation.get(), screen_size: size };
-    let mut scene = prepare_scene(window, size, renderer);
-
-    let dirty_region = scene.dirty_region;
-    let to_draw_tr = dirty_region.transformed(rotation);
-
-    debug_assert!(scene.current_line >= to_draw_tr.origin.y_length());
-
-    let mut background_color = TargetPixel::background();
-    // FIXME gradient
-    TargetPixel::blend(&mut background_color, background.color().into());
-
-    while scene.current_line < to_draw_tr.origin.y_length() + to_draw_tr.size.height_length() {
-        line_buffer.process_line(
-            scene.current_line.get() as usize,
-            to_draw_tr.min_x() as usize..to_draw_tr.max_x() as usize,
-            |line_buffer| {
-                let offset = to_draw_tr.min_x() as usize;
-
-                line_buffer.fill(background_color);
-                for span in scene.items[0..scene.current_items_index].iter().rev() {
-                    debug_assert!(scene.current_line >= span.pos.y_length());
-                    debug_assert!(
-                        scene.current_line < span.pos.y_length() + span.size.height_length(),
+    let (dirty_region, mut scenes) = prepare_scene(window, size, renderer);
+    // Within a line, the rectangles are processed from left to right
+    scenes.sort_unstable_by_key(|(scene, _)| scene.dirty_region.min_x());
+    let (mut scenes, vectors): (Vec<_>, Vec<_>) = scenes.into_iter().unzip();
+    let scene_data = vectors.iter().map(SceneVectors::data).collect::<Vec<_>>();
+
+    let background_color = background_fill_color(&background);
+
+    let first_line = scenes.iter().map(|scene| scene.dirty_region.min_y()).min().unwrap_or(0);
+    let last_line = scenes.iter().map(|scene| scene.dirty_region.max_y()).max().unwrap_or(0);
+    for line in first_line..last_line {
+        for (scene, data) in scenes.iter_mut().zip(&scene_data) {
+            let to_draw_tr = scene.dirty_region;
+            if line < to_draw_tr.min_y() || line >= to_draw_tr.max_y() {
+                continue;
+            }
+            debug_assert_eq!(scene.current_line.get(), line);
+            line_buffer.process_line(
+                line as usize,
+                to_draw_tr.min_x() as usize..to_draw_tr.max_x() as usize,
+                |line_buffer| {
+                    line_buffer.fill(background_color);
+                    draw_scene_line(
+                        &scene.items[0..scene.current_items_index],
+                        data,
+                        scene.current_line,
+                        to_draw_tr.min_x() as usize,
+                        line_buffer,
                     );
-                    match span.command {
-                        SceneCommand::Rectangle { color } => {
-                            TargetPixel::blend_slice(
-                                &mut line_buffer[span.pos.x as usize - offset
-                                    ..(span.pos.x_length() + span.size.width_length()).get()
-                                        as usize
-                                        - offset],
-                                color,
-                            );
-                        }
-                        SceneCommand::Texture { texture_index } => {
-                            let texture = &scene.vectors.textures[texture_index as usize];
-                            draw_functions::draw_texture_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                texture,
-                                line_buffer,
-                            );
-                        }
-                        SceneCommand::SharedBuffer { shared_buffer_index } => {
-                            let texture = scene.vectors.shared_buffers
-                                [shared_buffer_index as usize]
-                                .as_texture();
-                            draw_functions::draw_texture_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                &texture,
-                                line_buffer,
-                            );
-                        }
-                        SceneCommand::RoundedRectangle { rectangle_index } => {
-                            let rr = &scene.vectors.rounded_rectangles[rectangle_index as usize];
-                            draw_functions::draw_rounded_rectangle_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                rr,
-                                line_buffer,
-                            );
-                        }
-                        SceneCommand::Gradient { gradient_index } => {
-                            let g = &scene.vectors.gradients[gradient_index as usize];
-
-                            draw_functions::draw_gradient_line(
-                                &PhysicalRect {
-                                    origin: span.pos - euclid::vec2(offset as i16, 0),
-                                    size: span.size,
-                                },
-                                scene.current_line,
-                                g,
-                                line_buffer,
-                            );
-                        }
-                    }
-                }
-            },
+                },
+            );
+            if line + 1 < to_draw_tr.max_y() {
+                scene.next_line();
+            }
+        }
+    }
+    dirty_region
+}
+
+/// Rasterize the `lines` of the scenes, in the buffer that starts at the first of these lines.
+#[cfg(feature = "software-renderer-threads")]
+fn render_scenes_band<T: TargetPixel>(
+    scenes: &[(&Scene, SceneData)],
+    lines: core::ops::Range<i16>,
+    background_color: T,
+    buffer: &mut [T],
+    pixel_stride: usize,
+) {
+    for (scene, data) in scenes {
+        let to_draw_tr = scene.dirty_region;
+        let band = PhysicalRect::new(
+            PhysicalPoint::new(to_draw_tr.min_x(), lines.start),
+            PhysicalSize::new(to_draw_tr.width(), lines.end - lines.start),
         );
+        let Some(band) = to_draw_tr.intersection(&band) else { continue };
+        let buffer = &mut buffer[(band.min_y() - lines.start) as usize * pixel_stride..];
+        render_scene_band(&scene.items, data, band, background_color, buffer, pixel_stride);
+    }
+}
 
-        if scene.current_line < to_draw_tr.origin.y_length() + to_draw_tr.size.height_length() {
+/// Rasterize the `band` of the scene, in the buffer that starts at the first line of the band.
+#[cfg(feature = "software-renderer-threads")]
+fn render_scene_band<T: TargetPixel>(
+    items: &[SceneItem],
+    data: &SceneData,
+    band: PhysicalRect,
+    background_color: T,
+    buffer: &mut [T],
+    pixel_stride: usize,
+) {
+    let mut scene = Scene::new(items.to_vec(), band);
+    for line in 0..band.height() as usize {
+        let line_buffer =
+            &mut buffer[line * pixel_stride + band.min_x() as usize..][..band.width() as usize];
+        line_buffer.fill(background_color);
+        draw_scene_line(
+            &scene.items[0..scene.current_items_index],
+            data,
+            scene.current_line,
+            band.min_x() as usize,
+            line_buffer,
+        );
+        if line + 1 < band.height() as usize {
             scene.next_line();
         }
     }
-    PhysicalRegion(dirty_region)
+}
+
+/// Draw the `items` of the scene that are on the `current_line` in the `line_buffer`, whose first
+/// pixel is at the x coordinate `offset`.
+/// The items must be sorted by z, from the front to the back.
+fn draw_scene_line(
+    items: &[SceneItem],
+    data: &SceneData,
+    current_line: PhysicalLength,
+    offset: usize,
+    line_buffer: &mut [impl TargetPixel],
+) {
+    for span in items.iter().rev() {
+        debug_assert!(current_line >= span.pos.y_length());
+        debug_assert!(current_line < span.pos.y_length() + span.size.height_length());
+        match span.command {
+            SceneCommand::Rectangle { color } => {
+                TargetPixel::blend_slice(
+                    &mut line_buffer[span.pos.x as usize - offset
+                        ..(span.pos.x_length() + span.size.width_length()).get() as usize - offset],
+                    color,
+                );
+            }
+            SceneCommand::Texture { texture_index } => {
+                let texture = &data.textures[texture_index as usize];
+                draw_functions::draw_texture_line(
+                    &PhysicalRect {
+                        origin: span.pos - euclid::vec2(offset as i16, 0),
+                        size: span.size,
+                    },
+                    current_line,
+                    texture,
+                    line_buffer,
+                );
+            }
+            SceneCommand::SharedBuffer { shared_buffer_index } => {
+                let texture = &data.shared_textures[shared_buffer_index as usize];
+                draw_functions::draw_texture_line(
+                    &PhysicalRect {
+                        origin: span.pos - euclid::vec2(offset as i16, 0),
+                        size: span.size,
+                    },
+                    current_line,
+                    texture,
+                    line_buffer,
+                );
+            }
+            SceneCommand::RoundedRectangle { rectangle_index } => {
+                let rr = &data.rounded_rectangles[rectangle_index as usize];
+                draw_functions::draw_rounded_rectangle_line(
+                    &PhysicalRect {
+                        origin: span.pos - euclid::vec2(offset as i16, 0),
+                        size: span.size,
+                    },
+                    current_line,
+                    rr,
+                    line_buffer,
+                );
+            }
+            SceneCommand::Gradient { gradient_index } => {
+                let g = &data.gradients[gradient_index as usize];
+
+                draw_functions::draw_gradient_line(
+                    &PhysicalRect {
+                        origin: span.pos - euclid::vec2(offset as i16, 0),
+                        size: span.size,
+                    },
+                    current_line,
+                    g,
+                    line_buffer,
+                );
+            }
+        }
+    }
 }
 
 #[derive(Default)]
@@ -783,6 +1043,31 @@ struct SceneVectors {
     gradients: Vec<GradientCommand>,
 }
 
+impl SceneVectors {
+    fn data(&self) -> SceneData<'_> {
+        SceneData {
+            textures: &self.textures,
+            shared_textures: self
+                .shared_buffers
+                .iter()
+                .map(SharedBufferCommand::as_texture)
+                .collect(),
+            rounded_rectangles: &self.rounded_rectangles,
+            gradients: &self.gradients,
+        }
+    }
+}
+
+/// The data of the [`SceneVectors`] that is read to rasterize the scene. It only borrows the
+/// pixels of the shared buffers, so that it can be shared with other threads.
+struct SceneData<'a> {
+    textures: &'a [SceneTexture<'static>],
+    /// The textures of the [`SceneVectors::shared_buffers`]
+    shared_textures: Vec<SceneTexture<'a>>,
+    rounded_rectangles: &'a [RoundedRectangle],
+    gradients: &'a [GradientCommand],
+}
+
 struct Scene {
     /// the next line to be processed
     current_line: PhysicalLength,
@@ -794,20 +1079,15 @@ struct Scene {
     ///   sorted by z (front to back)
     items: Vec<SceneItem>,
 
-    vectors: SceneVectors,
-
     future_items_index: usize,
     current_items_index: usize,
 
-    dirty_region: DirtyRegion,
+    /// The rectangle to draw, in the (rotated) frame of reference of the buffer
+    dirty_region: PhysicalRect,
 }
 
 impl Scene {
-    pub fn new(
-        mut items: Vec<SceneItem>,
-        vectors: SceneVectors,
-        dirty_region: DirtyRegion,
-    ) -> Self {
+    pub fn new(mut items: Vec<SceneItem>, dirty_region: PhysicalRect) -> Self {
         let current_line = dirty_region.origin.y_length();
         items.retain(|i| i.pos.y_length() + i.size.height_length() > current_line);
         items.sort_unstable_by(compare_scene_item);
@@ -818,7 +1098,6 @@ pub fn new(
             current_line,
             current_items_index,
             future_items_index: current_items_index,
-            vectors,
             dirty_region,
         }
     }
@@ -1101,11 +1380,12 @@ struct GradientCommand {
     bottom_clip: PhysicalLength,
 }
 
+/// Prepare one scene, with the vectors it refers to, for each rectangle of the dirty region
 fn prepare_scene(
     window: &WindowInner,
     size: PhysicalSize,
     software_renderer: &SoftwareRenderer,
-) -> Scene {
+) -> (PhysicalRegion, Vec<(Scene, SceneVectors)>) {
     let factor = ScaleFactor::new(window.scale_factor());
     let prepare_scene = SceneBuilder::new(
         size,
@@ -1113,6 +1393,7 @@ fn prepare_scene(
         window,
         PrepareScene::default(),
         software_renderer.rotation.get(),
+        software_renderer,
     );
     let mut renderer = crate::item_rendering::PartialRenderer::new(
         &software_renderer.partial_cache,
@@ -1120,22 +1401,38 @@ fn prepare_scene(
         prepare_scene,
     );
 
-    let mut dirty_region = PhysicalRect::default();
+    let background =
+        window.window_item().map(|item| item.as_pin_ref().background()).unwrap_or_default();
+    let mut dirty_region = PhysicalRegion::default();
+    let mut scenes = Vec::new();
     window.draw_contents(|components| {
         for (component, origin) in components {
             renderer.compute_dirty_regions(component, *origin);
         }
 
-        dirty_region = (renderer.dirty_region.to_rect().cast() * factor).round_out().cast();
-        dirty_region = software_renderer.apply_dirty_region(dirty_region, size);
-
-        renderer.combine_clip(
-            (dirty_region.cast() / factor).cast(),
-            LogicalLength::zero(),
-            LogicalLength::zero(),
+        dirty_region = software_renderer.apply_dirty_region(
+            SoftwareRenderer::physical_dirty_region(&renderer.dirty_region, factor),
+            size,
         );
-        for (component, origin) in components {
-            crate::item_rendering::render_component_items(component, &mut renderer, *origin);
+
+        for rect in dirty_region.iter_rects() {
+            renderer.save_state();
+            renderer.combine_clip(
+                (rect.cast() / factor).cast(),
+                LogicalLength::zero(),
+                LogicalLength::zero(),
+            );
+            renderer.actual_renderer.draw_window_background(&background);
+            for (component, origin) in components {
+                crate::item_rendering::render_component_items(component, &mut renderer, *origin);
+            }
+            renderer.restore_state();
+            let PrepareScene { items, vectors } =
+                core::mem::take(&mut renderer.actual_renderer.processor);
+            scenes.push((
+                Scene::new(items, rect.transformed(renderer.actual_renderer.rotation)),
+                vectors,
+            ));
         }
     });
 
@@ -1146,9 +1443,7 @@ fn prepare_scene(
         }
     }
 
-    let prepare_scene = renderer.into_inner();
-
-    Scene::new(prepare_scene.processor.items, prepare_scene.processor.vectors, dirty_region)
+    (dirty_region, scenes)
 }
 
 trait ProcessScene {
@@ -1297,6 +1592,7 @@ struct SceneBuilder<'a, T> {
     scale_factor: ScaleFactor,
     window: &'a WindowInner,
     rotation: RotationInfo,
+    software_renderer: &'a SoftwareRenderer,
 }
 
 impl<'a, T: ProcessScene> SceneBuilder<'a, T> {
@@ -1306,6 +1602,7 @@ fn new(
         window: &'a WindowInner,
         processor: T,
         orientation: WindowRotation,
+        software_renderer: &'a SoftwareRenderer,
     ) -> Self {
         Self {
             processor,
@@ -1317,10 +1614,13 @@ fn new(
                     LogicalPoint::default(),
                     (screen_size.cast() / scale_factor).cast(),
                 ),
+                transform: None,
+                rounded_clip: None,
             },
             scale_factor,
             window,
             rotation: RotationInfo { orientation, screen_size },
+            software_renderer,
         }
     }
 
@@ -1336,8 +1636,24 @@ fn draw_image_impl(
         source: &crate::graphics::Image,
         mut source_rect: IntRect,
         image_fit: ImageFit,
-        colorize: Color,
+        colorize: Brush,
     ) {
+        let colorize = match colorize {
+            Brush::SolidColor(colorize) => colorize,
+            brush => {
+                // The image is drawn in white, and used as a mask for the gradient
+                self.draw_with_gradient(geom, geom, &brush, |r| {
+                    r.draw_image_impl(
+                        geom,
+                        source,
+                        source_rect,
+                        image_fit,
+                        Color::from_rgb_u8(255, 255, 255).into(),
+                    )
+                });
+                return;
+            }
+        };
         let global_alpha_u16 = (self.current_state.alpha * 255.) as u16;
         let image_inner: &ImageInner = source.into();
         let size: euclid::default::Size2D<u32> = source_rect.size.cast();
@@ -1534,6 +1850,9 @@ fn draw_text_paragraph<Font: AbstractFont>(
                             }
                             _ => color,
                         };
+                        if color.alpha() == 0 {
+                            continue;
+                        }
 
                         if let Some(clipped_src) = src_rect.intersection(&physical_clip) {
                             let geometry = clipped_src.translate(offset).round();
@@ -1591,339 +1910,452 @@ fn draw_text_paragraph<Font: AbstractFont>(
             .ok();
     }
 
-    /// Returns the color, mixed with the current_state's alpha
-    fn alpha_color(&self, color: Color) -> Color {
-        if self.current_state.alpha < 1.0 {
-            Color::from_argb_u8(
-                (color.alpha() as f32 * self.current_state.alpha) as u8,
-                color.red(),
-                color.green(),
-                color.blue(),
-            )
-        } else {
-            color
+    /// Rasterize the outline (in physical pixels) within the current clip, and draw it with the color
+    fn draw_outline(
+        &mut self,
+        outline: &path::Outline,
+        fill_rule: crate::items::FillRule,
+        color: Color,
+    ) {
+        let physical_clip =
+            (self.current_state.clip.translate(self.current_state.offset.to_vector()).cast()
+                * self.scale_factor)
+                .round();
+        let Some(geometry) = outline
+            .bounding_box()
+            .and_then(|b| b.to_rect().round_out().intersection(&physical_clip))
+        else {
+            return;
+        };
+        let geometry: PhysicalRect = geometry.cast();
+        if geometry.is_empty() {
+            return;
         }
+
+        let data = outline.rasterize(geometry, fill_rule);
+        self.processor.process_shared_image_buffer(
+            geometry.transformed(self.rotation),
+            SharedBufferCommand {
+                buffer: SharedBufferData::AlphaMap {
+                    data: data.into(),
+                    width: geometry.width() as u16,
+                },
+                source_rect: PhysicalRect::from_size(geometry.size),
+                colorize: color,
+                // color already is mixed with global alpha
+                alpha: color.alpha(),
+                rotation: self.rotation.orientation,
+            },
+        );
     }
-}
 
-struct SelectionInfo {
-    selection_color: Color,
-    selection_background: Color,
-    selection: core::ops::Range<usize>,
-}
+    /// Transform the items that are drawn next. The transformation is applied in the
+    /// coordinates of the current item.
+    fn apply_transform(&mut self, transform: euclid::default::Transform2D<f32>) {
+        let state = &mut self.current_state;
+        let current = state.transform.unwrap_or_else(|| ItemTransform {
+            transform: Default::default(),
+            clip: state.clip.translate(state.offset.to_vector()),
+        });
+        state.transform = Some(ItemTransform {
+            transform: transform
+                .then_translate(state.offset.to_vector().cast().to_untyped())
+                .then(&current.transform),
+            clip: current.clip,
+        });
+        state.clip = transform.inverse().map_or_else(LogicalRect::default, |inverse| {
+            LogicalRect::from_untyped(
+                &inverse.outer_transformed_rect(&state.clip.cast().to_untyped()).cast(),
+            )
+        });
+        state.offset = LogicalPoint::default();
+    }
 
-#[derive(Clone, Copy)]
-struct RenderState {
-    alpha: f32,
-    offset: LogicalPoint,
-    clip: LogicalRect,
-}
+    /// True if the item, whose drawing is within `bounds` relative to the item, cannot be drawn
+    /// directly in the scene and must be drawn with [`Self::draw_indirect`]. This is the case
+    /// when the item is transformed, or when it crosses a rounded corner of the clip.
+    fn needs_indirect_drawing(&self, bounds: LogicalRect) -> bool {
+        if self.current_state.transform.is_some() {
+            return true;
+        }
+        let Some(rounded_clip) = &self.current_state.rounded_clip else { return false };
+        // The bounds are not reduced to the clip, so that the result doesn't depend on the
+        // dirty region, and partial rendering gives the same pixels
+        let bounds = bounds.translate(self.current_state.offset.to_vector());
+        rounded_clip.iter().any(|clip| {
+            // The rounded corners don't affect the items within the cross made of these two
+            // rectangles
+            let radius = clip.radius;
+            clip.from_window.is_some()
+                || (!clip.rect.inflate(0 as Coord, -radius).contains_rect(&bounds)
+                    && !clip.rect.inflate(-radius, 0 as Coord).contains_rect(&bounds))
+        })
+    }
 
-impl<'a, T: ProcessScene> crate::item_rendering::ItemRenderer for SceneBuilder<'a, T> {
-    #[allow(clippy::unnecessary_cast)] // Coord!
-    fn draw_rectangle(
+    /// Draw an item with the current transformation and rounded clip. The item is drawn by
+    /// `draw` in an intermediate buffer, which is then transformed and masked into the scene.
+    /// `bounds` is the area where the item draws, relative to the item.
+    ///
+    /// The whole item is drawn in the buffer, and not only the part within the clip, so that
+    /// the pixels can be kept in the cache until the item changes. They are not transformed
+    /// yet, so they stay valid while the transformation changes, for example when the item is
+    /// rotated by an animation. Items bigger than the window are not cached: only the part
+    /// within the clip is drawn, in a buffer that is dropped after the frame.
+    fn draw_indirect(
         &mut self,
-        rect: Pin<&crate::items::Rectangle>,
-        _: &ItemRc,
-        size: LogicalSize,
+        item_rc: &ItemRc,
+        bounds: LogicalRect,
+        draw: impl FnOnce(&mut SceneBuilder<'a, RenderToBuffer<'_, PremultipliedRgbaColor>>),
     ) {
-        let geom = LogicalRect::from(size);
-        if self.should_draw(&geom) {
-            let clipped = match geom.intersection(&self.current_state.clip) {
-                Some(geom) => geom,
-                None => return,
-            };
-
-            let background = rect.background();
-            if let Brush::LinearGradient(g) = background {
-                let geom2 = (geom.cast() * self.scale_factor).transformed(self.rotation);
-                let clipped2 = (clipped.cast() * self.scale_factor).transformed(self.rotation);
-                let act_rect = (clipped.translate(self.current_state.offset.to_vector()).cast()
-                    * self.scale_factor)
-                    .round()
-                    .cast()
-                    .transformed(self.rotation);
-                let angle = g.angle() - self.rotation.orientation.angle();
-                let tan = angle.to_radians().tan().abs();
-                let start = if !tan.is_finite() {
-                    255.
-                } else {
-                    let h = tan * geom2.width() as f32;
-                    255. * h / (h + geom2.height() as f32)
-                } as u8;
-                let mut angle = angle as i32 % 360;
-                if angle < 0 {
-                    angle += 360;
-                }
-                let mut stops = g.stops().copied().peekable();
-                let mut idx = 0;
-                let stop_count = g.stops().count();
-                while let (Some(mut s1), Some(mut s2)) = (stops.next(), stops.peek().copied()) {
-                    let mut flags = 0;
-                    if (angle % 180) > 90 {
-                        flags |= 0b1;
-                    }
-                    if angle <= 90 || angle > 270 {
-                        core::mem::swap(&mut s1, &mut s2);
-                        s1.position = 1. - s1.position;
-                        s2.position = 1. - s2.position;
-                        if idx == 0 {
-                            flags |= 0b100;
-                        }
-                        if idx == stop_count - 2 {
-                            flags |= 0b010;
-                        }
-                    } else {
-                        if idx == 0 {
-                            flags |= 0b010;
-                        }
-                        if idx == stop_count - 2 {
-                            flags |= 0b100;
-                        }
-                    }
-
-                    idx += 1;
-
-                    let (adjust_left, adjust_right) = if (angle % 180) > 90 {
-                        (
-                            (geom2.width() * s1.position).floor() as i16,
-                            (geom2.width() * (1. - s2.position)).ceil() as i16,
-                        )
-                    } else {
-                        (
-                            (geom2.width() * (1. - s2.position)).ceil() as i16,
-                            (geom2.width() * s1.position).floor() as i16,
-                        )
-                    };
+        let Some(visible_bounds) = bounds.intersection(&self.current_state.clip) else { return };
+        if self.current_state.alpha < 0.01 {
+            return;
+        }
+        let buffer_rect = self.indirect_buffer_rect(bounds);
+        let size: PhysicalSize = buffer_rect.size.round().cast();
+        if size.is_empty() {
+            return;
+        }
+        if size.area() <= self.rotation.screen_size.area() {
+            let pixels = self.software_renderer.indirect_cache.get_or_update(
+                item_rc,
+                |pixels| pixels.buffer_rect == buffer_rect,
+                || {
+                    let mut buffer = vec![PremultipliedRgbaColor::default(); size.area() as usize];
+                    draw(&mut self.indirect_scene_builder(buffer_rect, &mut buffer));
+                    LayerPixels { buffer_rect, pixels: buffer.into() }
+                },
+            );
+            self.composite_indirect(buffer_rect, &pixels.pixels);
+            return;
+        }
 
-                    let gr = GradientCommand {
-                        color1: self.alpha_color(s1.color).into(),
-                        color2: self.alpha_color(s2.color).into(),
-                        start,
-                        flags,
-                        top_clip: Length::new(
-                            (clipped2.min_y() - geom2.min_y()) as i16
-                                - (geom2.height() * s1.position).floor() as i16,
-                        ),
-                        bottom_clip: Length::new(
-                            (geom2.max_y() - clipped2.max_y()) as i16
-                                - (geom2.height() * (1. - s2.position)).ceil() as i16,
-                        ),
-                        left_clip: Length::new(
-                            (clipped2.min_x() - geom2.min_x()) as i16 - adjust_left,
-                        ),
-                        right_clip: Length::new(
-                            (geom2.max_x() - clipped2.max_x()) as i16 - adjust_right,
-                        ),
-                    };
+        self.software_renderer.indirect_cache.release(item_rc);
+        let buffer_rect = self.indirect_buffer_rect(visible_bounds);
+        let size: PhysicalSize = buffer_rect.size.round().cast();
+        if size.is_empty() {
+            return;
+        }
+        let mut buffer = vec![PremultipliedRgbaColor::default(); size.area() as usize];
+        draw(&mut self.indirect_scene_builder(buffer_rect, &mut buffer));
+        self.composite_indirect(buffer_rect, &buffer);
+    }
 
-                    let size_y = act_rect.height_length() + gr.top_clip + gr.bottom_clip;
-                    let size_x = act_rect.width_length() + gr.left_clip + gr.right_clip;
-                    if size_x.get() == 0 || size_y.get() == 0 {
-                        // the position are too close to each other
-                        // FIXME: For the first or the last, we should draw a plain color to the end
-                        continue;
-                    }
+    /// Render the children of the item in a layer, which is then composited in the scene with
+    /// the opacity. Unlike [`Self::apply_opacity`], the overlapping children don't show through
+    /// each other. The pixels of the layer are kept in the cache until the children change.
+    fn render_and_blend_layer(
+        &mut self,
+        opacity: f32,
+        item_rc: &ItemRc,
+        size: LogicalSize,
+    ) -> RenderingResult {
+        self.current_state.alpha *= opacity;
+        if self.current_state.alpha < 0.01 {
+            return RenderingResult::ContinueRenderingWithoutChildren;
+        }
 
-                    self.processor.process_gradient(act_rect, gr);
-                }
-                return;
+        // The layer is not reduced to the clip, which contains the dirty region, so that the
+        // cached pixels are complete. Only the part within the window is rendered.
+        let screen = LogicalRect::from_size(
+            (self.rotation.screen_size.cast() / self.scale_factor).cast::<Coord>(),
+        );
+        let window_area = match &self.current_state.transform {
+            Some(item_transform) => {
+                let Some(inverse) = item_transform.transform.inverse() else {
+                    return RenderingResult::ContinueRenderingWithoutChildren;
+                };
+                LogicalRect::from_untyped(
+                    &inverse.outer_transformed_rect(&screen.cast().to_untyped()).cast(),
+                )
             }
+            None => screen,
+        }
+        .translate(-self.current_state.offset.to_vector());
+        let Some(bounds) = crate::properties::evaluate_no_tracking(|| {
+            LogicalRect::from(size)
+                .union(&crate::item_rendering::item_children_bounding_rect(
+                    item_rc.item_tree(),
+                    item_rc.index() as isize,
+                    &window_area,
+                ))
+                .intersection(&window_area)
+        }) else {
+            return RenderingResult::ContinueRenderingWithoutChildren;
+        };
 
-            let color = self.alpha_color(background.color());
+        let buffer_rect = self.indirect_buffer_rect(bounds);
+        let size: PhysicalSize = buffer_rect.size.round().cast();
+        if size.is_empty() {
+            return RenderingResult::ContinueRenderingWithoutChildren;
+        }
+        let layer = self.software_renderer.layer_cache.get_or_update(
+            item_rc,
+            |layer| layer.buffer_rect == buffer_rect,
+            || {
+                let mut buffer = vec![PremultipliedRgbaColor::default(); size.area() as usize];
+                let mut renderer = crate::item_rendering::PartialRenderer::new(
+                    &self.software_renderer.partial_cache,
+                    Default::default(),
+                    self.indirect_scene_builder(buffer_rect, &mut buffer),
+                );
+                crate::item_rendering::render_item_children(
+                    &mut renderer,
+                    item_rc.item_tree(),
+                    item_rc.index() as isize,
+                );
+                LayerPixels { buffer_rect, pixels: buffer.into() }
+            },
+        );
+        self.composite_indirect(buffer_rect, &layer.pixels);
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
 
-            if color.alpha() == 0 {
-                return;
+    /// The area, in physical pixels relative to the item, of the intermediate buffer in which
+    /// the `bounds` of the item are drawn. Without transformation, the buffer is aligned with the
+    /// pixels of the window, so that its pixels can be copied as is.
+    fn indirect_buffer_rect(&self, bounds: LogicalRect) -> euclid::Rect<f32, PhysicalPx> {
+        match self.current_state.transform {
+            Some(_) => (bounds.cast() * self.scale_factor).round_out(),
+            None => {
+                let offset = self.current_state.offset.to_vector();
+                let window_bounds =
+                    (bounds.translate(offset).cast() * self.scale_factor).round_out();
+                window_bounds.translate(-(offset.cast() * self.scale_factor))
             }
-            let geometry = (clipped.translate(self.current_state.offset.to_vector()).cast()
-                * self.scale_factor)
-                .round()
-                .cast()
-                .transformed(self.rotation);
-
-            self.processor.process_rectangle(geometry, color.into());
         }
     }
 
+    /// Create a SceneBuilder which renders the area `buffer_rect` of the current item in `buffer`
+    fn indirect_scene_builder<'b>(
+        &self,
+        buffer_rect: euclid::Rect<f32, PhysicalPx>,
+        buffer: &'b mut [PremultipliedRgbaColor],
+    ) -> SceneBuilder<'a, RenderToBuffer<'b, PremultipliedRgbaColor>> {
+        let size: PhysicalSize = buffer_rect.size.round().cast();
+        let mut builder = SceneBuilder::new(
+            size,
+            self.scale_factor,
+            self.window,
+            RenderToBuffer { buffer, stride: size.width as usize },
+            WindowRotation::NoRotation,
+            self.software_renderer,
+        );
+        builder.translate(-(buffer_rect.origin / self.scale_factor).to_vector().cast::<Coord>());
+        builder
+    }
+
+    /// Draw the `buffer`, which contains the area `buffer_rect` of the current item, in the
+    /// scene with the current transformation, rounded clip and alpha.
     #[allow(clippy::unnecessary_cast)] // Coord
-    fn draw_border_rectangle(
+    fn composite_indirect(
         &mut self,
-        rect: Pin<&crate::items::BorderRectangle>,
-        _: &ItemRc,
-        size: LogicalSize,
+        buffer_rect: euclid::Rect<f32, PhysicalPx>,
+        buffer: &[PremultipliedRgbaColor],
     ) {
-        let geom = LogicalRect::from(size);
-        if self.should_draw(&geom) {
-            let mut border = rect.border_width();
-            let radius = rect.border_radius();
-            // FIXME: gradients
-            let color = self.alpha_color(rect.background().color());
-            let border_color = if border.get() as f32 > 0.01 {
-                self.alpha_color(rect.border_color().color())
-            } else {
-                Color::default()
-            };
-
-            let mut border_color = PremultipliedRgbaColor::from(border_color);
-            let color = PremultipliedRgbaColor::from(color);
-            if border_color.alpha == 0 {
-                border = LogicalLength::new(0 as _);
-            } else if border_color.alpha < 255 {
-                // Find a color for the border which is an equivalent to blend the background and then the border.
-                // In the end, the resulting of blending the background and the color is
-                // (A + B) + C, where A is the buffer color, B is the background, and C is the border.
-                // which expands to (A*(1-Bα) + B*Bα)*(1-Cα) + C*Cα = A*(1-(Bα+Cα-Bα*Cα)) + B*Bα*(1-Cα) + C*Cα
-                // so let the new alpha be: Nα = Bα+Cα-Bα*Cα, then this is A*(1-Nα) + N*Nα
-                // with N = (B*Bα*(1-Cα) + C*Cα)/Nα
-                // N being the equivalent color of the border that mixes the background and the border
-                // In pre-multiplied space, the formula simplifies further N' = B'*(1-Cα) + C'
-                let b = border_color;
-                let b_alpha_16 = b.alpha as u16;
-                border_color = PremultipliedRgbaColor {
-                    red: ((color.red as u16 * (255 - b_alpha_16)) / 255) as u8 + b.red,
-                    green: ((color.green as u16 * (255 - b_alpha_16)) / 255) as u8 + b.green,
-                    blue: ((color.blue as u16 * (255 - b_alpha_16)) / 255) as u8 + b.blue,
-                    alpha: (color.alpha as u16 + b_alpha_16
-                        - (color.alpha as u16 * b_alpha_16) / 255) as u8,
-                }
-            }
-
-            if radius.get() > 0 as _ {
-                let radius = radius
-                    .min(geom.width_length() / 2 as Coord)
-                    .min(geom.height_length() / 2 as Coord);
-                if let Some(clipped) = geom.intersection(&self.current_state.clip) {
-                    let geom2 = (geom.cast() * self.scale_factor).transformed(self.rotation);
-                    let clipped2 = (clipped.cast() * self.scale_factor).transformed(self.rotation);
-                    let geometry =
-                        (clipped.translate(self.current_state.offset.to_vector()).cast()
-                            * self.scale_factor)
-                            .round()
-                            .cast()
-                            .transformed(self.rotation);
-                    // Add a small value to make sure that the clip is always positive despite floating point shenanigans
-                    const E: f32 = 0.00001;
-
-                    self.processor.process_rounded_rectangle(
-                        geometry,
-                        RoundedRectangle {
-                            radius: (radius.cast() * self.scale_factor).cast(),
-                            width: (border.cast() * self.scale_factor).cast(),
-                            border_color,
-                            inner_color: color,
-                            top_clip: PhysicalLength::new(
-                                (clipped2.min_y() - geom2.min_y() + E) as _,
-                            ),
-                            bottom_clip: PhysicalLength::new(
-                                (geom2.max_y() - clipped2.max_y() + E) as _,
-                            ),
-                            left_clip: PhysicalLength::new(
-                                (clipped2.min_x() - geom2.min_x() + E) as _,
-                            ),
-                            right_clip: PhysicalLength::new(
-                                (geom2.max_x() - clipped2.max_x() + E) as _,
-                            ),
-                        },
-                    );
-                }
-                return;
+        let scale = self.scale_factor.get();
+        let offset = self.current_state.offset.to_vector();
+        let size: PhysicalSize = buffer_rect.size.round().cast();
+
+        // The transformation from the pixels of the buffer to the physical pixels of the window
+        let (to_window, window_clip) = match self.current_state.transform {
+            Some(item_transform) => {
+                let to_window = euclid::default::Transform2D::translation(
+                    buffer_rect.origin.x,
+                    buffer_rect.origin.y,
+                )
+                .then_scale(1. / scale, 1. / scale)
+                .then_translate(offset.cast().to_untyped())
+                .then(&item_transform.transform)
+                .then_scale(scale, scale);
+                (Some(to_window), item_transform.clip)
             }
+            None => (None, self.current_state.clip.translate(offset)),
+        };
 
-            if color.alpha > 0 {
-                if let Some(r) = geom
-                    .inflate(-border.get(), -border.get())
-                    .intersection(&self.current_state.clip)
-                {
-                    let geometry = (r.translate(self.current_state.offset.to_vector()).cast()
-                        * self.scale_factor)
-                        .round()
-                        .cast()
-                        .transformed(self.rotation);
-                    self.processor.process_rectangle(geometry, color);
-                }
+        let physical_clip: PhysicalRect = (window_clip.cast() * self.scale_factor).round().cast();
+        let buffer_in_window: PhysicalRect = match &to_window {
+            Some(to_window) => PhysicalRect::from_untyped(
+                &to_window
+                    .outer_transformed_rect(&euclid::rect(
+                        0.,
+                        0.,
+                        size.width as f32,
+                        size.height as f32,
+                    ))
+                    .round_out()
+                    .cast(),
+            ),
+            None => buffer_rect.translate(offset.cast() * self.scale_factor).round().cast(),
+        };
+        let Some(geometry) = buffer_in_window.intersection(&physical_clip) else {
+            return;
+        };
+        if geometry.is_empty() {
+            return;
+        }
+        let from_window = match to_window {
+            Some(to_window) => {
+                let Some(from_window) = to_window.inverse() else { return };
+                Some(from_window)
             }
+            None => None,
+        };
+        // The masks of the clips, with the transformation from the physical pixels of the
+        // window to the physical pixels of the clip
+        let masks = self.current_state.rounded_clip.as_ref().map_or_else(Vec::new, |clip| {
+            clip.iter()
+                .map(|clip| {
+                    let coverage = draw_functions::RoundedRectCoverage::new(
+                        (clip.rect.cast() * self.scale_factor).to_untyped(),
+                        clip.radius as f32 * scale,
+                    );
+                    let to_clip = clip.from_window.map(|from_window| {
+                        euclid::default::Transform2D::scale(1. / scale, 1. / scale)
+                            .then(&from_window)
+                            .then_scale(scale, scale)
+                    });
+                    (coverage, to_clip)
+                })
+                .collect::<Vec<_>>()
+        });
 
-            // FIXME: gradients
-            if border_color.alpha > 0 {
-                let mut add_border = |r: LogicalRect| {
-                    if let Some(r) = r.intersection(&self.current_state.clip) {
-                        let geometry = (r.translate(self.current_state.offset.to_vector()).cast()
-                            * self.scale_factor)
-                            .round()
-                            .cast()
-                            .transformed(self.rotation);
-                        self.processor.process_rectangle(geometry, border_color);
+        let mut pixels = SharedPixelBuffer::<crate::graphics::Rgba8Pixel>::new(
+            geometry.width() as u32,
+            geometry.height() as u32,
+        );
+        for (y, line) in
+            pixels.make_mut_slice().chunks_exact_mut(geometry.width() as usize).enumerate()
+        {
+            let window_y = geometry.origin.y as i32 + y as i32;
+            for (x, pixel) in line.iter_mut().enumerate() {
+                let window_x = geometry.origin.x as i32 + x as i32;
+                let mut c = match &from_window {
+                    Some(from_window) => {
+                        let p = from_window.transform_point(euclid::point2(
+                            window_x as f32 + 0.5,
+                            window_y as f32 + 0.5,
+                        ));
+                        draw_functions::sample_bilinear(buffer, size, p.x - 0.5, p.y - 0.5)
+                    }
+                    None => {
+                        let buffer_x = (window_x - buffer_in_window.origin.x as i32) as usize;
+                        let buffer_y = (window_y - buffer_in_window.origin.y as i32) as usize;
+                        buffer[buffer_y * size.width as usize + buffer_x]
                     }
                 };
-                let b = border.get();
-                add_border(euclid::rect(0 as _, 0 as _, geom.width(), b));
-                add_border(euclid::rect(0 as _, geom.height() - b, geom.width(), b));
-                add_border(euclid::rect(0 as _, b, b, geom.height() - b - b));
-                add_border(euclid::rect(geom.width() - b, b, b, geom.height() - b - b));
+                if !masks.is_empty() {
+                    let coverage = masks.iter().fold(255, |coverage, (mask, to_clip)| {
+                        let (x, y) = match to_clip {
+                            Some(to_clip) => {
+                                let p = to_clip.transform_point(euclid::point2(
+                                    window_x as f32 + 0.5,
+                                    window_y as f32 + 0.5,
+                                ));
+                                (p.x - 0.5, p.y - 0.5)
+                            }
+                            None => (window_x as f32, window_y as f32),
+                        };
+                        (coverage * mask.at(x, y) as u32 + 127) / 255
+                    });
+                    let mask = |c: u8| ((c as u32 * coverage + 127) / 255) as u8;
+                    c = PremultipliedRgbaColor {
+                        red: mask(c.red),
+                        green: mask(c.green),
+                        blue: mask(c.blue),
+                        alpha: mask(c.alpha),
+                    };
+                }
+                *pixel = crate::graphics::Rgba8Pixel::new(c.red, c.green, c.blue, c.alpha);
             }
         }
+
+        self.processor.process_shared_image_buffer(
+            geometry.transformed(self.rotation),
+            SharedBufferCommand {
+                buffer: SharedBufferData::SharedImage(SharedImageBuffer::RGBA8Premultiplied(
+                    pixels,
+                )),
+                source_rect: PhysicalRect::from_size(geometry.size),
+                colorize: Default::default(),
+                alpha: (self.current_state.alpha * 255.) as u8,
+                rotation: self.rotation.orientation,
+            },
+        );
     }
 
-    fn draw_image(&mut self, image: Pin<&crate::items::ImageItem>, _: &ItemRc, size: LogicalSize) {
-        let geom = LogicalRect::from(size);
-        if self.should_draw(&geom) {
-            let source = image.source();
-            self.draw_image_impl(
-                geom,
-                &source,
-                euclid::Rect::new(Default::default(), source.size().cast()),
-                image.image_fit(),
-                image.colorize().color(),
+    /// Draw the pixels of a cached pixmap at the origin of the item, in physical pixels
+    fn draw_pixmap(&mut self, img: SharedImageBuffer) {
+        let physical_clip = self.current_state.clip.cast() * self.scale_factor;
+        let src_rect = euclid::rect(0., 0., img.width() as f32, img.height() as f32);
+
+        if let Some(clipped_src) = src_rect.intersection(&physical_clip) {
+            let offset = self.current_state.offset.to_vector().cast() * self.scale_factor;
+            let geometry = clipped_src.translate(offset).round();
+            let origin = (geometry.origin - offset.round()).cast::<usize>();
+            let actual_x = origin.x - src_rect.origin.x as usize;
+            let actual_y = origin.y - src_rect.origin.y as usize;
+
+            self.processor.process_shared_image_buffer(
+                geometry.cast().transformed(self.rotation),
+                SharedBufferCommand {
+                    buffer: SharedBufferData::SharedImage(img),
+                    source_rect: PhysicalRect::new(
+                        PhysicalPoint::new(actual_x as _, actual_y as _),
+                        geometry.size.cast(),
+                    ),
+                    colorize: Default::default(),
+                    alpha: (self.current_state.alpha * 255.) as u8,
+                    rotation: self.rotation.orientation,
+                },
             );
         }
     }
 
-    fn draw_clipped_image(
-        &mut self,
-        image: Pin<&crate::items::ClippedImage>,
-        _: &ItemRc,
-        size: LogicalSize,
-    ) {
-        let geom = LogicalRect::from(size);
-        if self.should_draw(&geom) {
-            let source = image.source();
-
-            let source_clip_x = image.source_clip_x();
-            let source_clip_y = image.source_clip_y();
-            let source_size = source.size();
-            let mut source_clip_width = image.source_clip_width();
-            // when the source_clip size is empty, make it full
-            if source_clip_width == 0 {
-                source_clip_width = source_size.width as i32 - source_clip_x;
-            }
-            let mut source_clip_height = image.source_clip_height();
-            if source_clip_height == 0 {
-                source_clip_height = source_size.height as i32 - source_clip_y;
-            }
-
-            self.draw_image_impl(
-                geom,
-                &source,
-                euclid::rect(source_clip_x, source_clip_y, source_clip_width, source_clip_height),
-                image.image_fit(),
-                image.colorize().color(),
+    /// Draw the window background if it is a gradient. A plain color is filled directly in the
+    /// lines, see [`background_fill_color`].
+    fn draw_window_background(&mut self, background: &Brush) {
+        if !matches!(background, Brush::SolidColor(_)) {
+            let window_rect = LogicalRect::from_size(
+                (self.rotation.screen_size.cast() / self.scale_factor).cast::<Coord>(),
             );
+            self.draw_gradient_rectangle(window_rect, background);
         }
     }
 
-    fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc, size: LogicalSize) {
-        let string = text.text();
-        if string.trim().is_empty() {
+    /// Fill the rectangle, relative to the current item, with a plain color
+    fn draw_plain_rectangle(&mut self, geom: LogicalRect, color: Color) {
+        if color.alpha() == 0 {
             return;
         }
-        let geom = LogicalRect::from(size);
-        if !self.should_draw(&geom) {
-            return;
+        let Some(clipped) = geom.intersection(&self.current_state.clip) else { return };
+        let geometry = (clipped.translate(self.current_state.offset.to_vector()).cast()
+            * self.scale_factor)
+            .round()
+            .cast()
+            .transformed(self.rotation);
+        self.processor.process_rectangle(geometry, color.into());
+    }
+
+    /// Returns the color, mixed with the current_state's alpha
+    fn alpha_color(&self, color: Color) -> Color {
+        if self.current_state.alpha < 1.0 {
+            Color::from_argb_u8(
+                (color.alpha() as f32 * self.current_state.alpha) as u8,
+                color.red(),
+                color.green(),
+                color.blue(),
+            )
+        } else {
+            color
         }
+    }
 
+    /// Draw the text of the Text item, whose geometry is `geom`, with a plain color
+    fn draw_text_impl(&mut self, text: Pin<&crate::items::Text>, geom: LogicalRect, color: Color) {
+        let string = text.text();
         let font_request = text.font_request(self.window);
 
-        let color = self.alpha_color(text.color().color());
         let max_size = (geom.size.cast() * self.scale_factor).cast();
 
         // Clip glyphs not only against the global clip but also against the Text's geometry to avoid drawing outside
@@ -1978,20 +2410,18 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc, size: Logica
         }
     }
 
-    fn draw_text_input(
+    /// Draw the text and the cursor of the TextInput item, whose geometry is `geom`, with a
+    /// plain color. The selection is only drawn if `draw_selection` is true, and the selected
+    /// text is left out otherwise.
+    fn draw_text_input_impl(
         &mut self,
         text_input: Pin<&crate::items::TextInput>,
-        _: &ItemRc,
-        size: LogicalSize,
+        geom: LogicalRect,
+        color: Color,
+        draw_selection: bool,
     ) {
-        let geom = LogicalRect::from(size);
-        if !self.should_draw(&geom) {
-            return;
-        }
-
         let font_request = text_input.font_request(&self.window.window_adapter());
 
-        let color = self.alpha_color(text_input.color().color());
         let max_size = (geom.size.cast() * self.scale_factor).cast();
 
         // Clip glyphs not only against the global clip but also against the Text's geometry to avoid drawing outside
@@ -2010,9 +2440,17 @@ fn draw_text_input(
         let text_visual_representation = text_input.visual_representation(None);
 
         let selection =
-            (!text_visual_representation.selection_range.is_empty()).then_some(SelectionInfo {
-                selection_background: self.alpha_color(text_input.selection_background_color()),
-                selection_color: self.alpha_color(text_input.selection_foreground_color()),
+            (!text_visual_representation.selection_range.is_empty()).then(|| SelectionInfo {
+                selection_background: if draw_selection {
+                    self.alpha_color(text_input.selection_background_color())
+                } else {
+                    Color::default()
+                },
+                selection_color: if draw_selection {
+                    self.alpha_color(text_input.selection_foreground_color())
+                } else {
+                    Color::default()
+                },
                 selection: text_visual_representation.selection_range.clone(),
             });
 
@@ -2067,33 +2505,731 @@ fn draw_text_input(
                 ),
             );
 
-            if let Some(clipped_src) = cursor_rect.intersection(&physical_clip.cast()) {
+            if let Some(clipped_src) =
+                cursor_rect.intersection(&physical_clip.cast()).filter(|_| color.alpha() > 0)
+            {
                 let geometry = clipped_src.translate(offset.cast()).transformed(self.rotation);
                 self.processor.process_rectangle(geometry, color.into());
             }
         }
     }
 
-    #[cfg(feature = "std")]
-    fn draw_path(&mut self, _path: Pin<&crate::items::Path>, _: &ItemRc, _size: LogicalSize) {
-        // TODO
+    /// Draw the fill and the stroke of the Path item with plain colors
+    #[allow(clippy::unnecessary_cast)] // Coord
+    fn draw_path_impl(
+        &mut self,
+        path: Pin<&crate::items::Path>,
+        item_rc: &ItemRc,
+        fill: Color,
+        stroke: Color,
+    ) {
+        use lyon_path::iterator::PathIterator;
+
+        let stroke_width = path.stroke_width().get() as f32 * self.scale_factor.get();
+        if fill.alpha() == 0 && (stroke.alpha() == 0 || stroke_width <= 0.) {
+            return;
+        }
+
+        let Some((offset, path_events)) = path.fitted_path_events(item_rc) else { return };
+        let offset = (offset + self.current_state.offset.to_vector()).cast::<f32>();
+        let transform = lyon_path::math::Transform::translation(offset.x, offset.y)
+            .then_scale(self.scale_factor.get(), self.scale_factor.get());
+
+        let mut polylines: Vec<path::Polyline> = Vec::new();
+        for event in path_events.iter().transformed(&transform).flattened(0.1) {
+            match event {
+                lyon_path::Event::Begin { at } => {
+                    polylines.push(path::Polyline { points: vec![at.cast_unit()], closed: false })
+                }
+                lyon_path::Event::Line { to, .. } => {
+                    if let Some(polyline) = polylines.last_mut() {
+                        polyline.points.push(to.cast_unit());
+                    }
+                }
+                lyon_path::Event::End { close, .. } => {
+                    if let Some(polyline) = polylines.last_mut() {
+                        polyline.closed = close;
+                    }
+                }
+                // The flattened iterator only produces lines
+                _ => {}
+            }
+        }
+
+        if fill.alpha() > 0 {
+            self.draw_outline(&path::Outline::fill(&polylines), path.fill_rule(), fill);
+        }
+        if stroke.alpha() > 0 && stroke_width > 0. {
+            // The Path element has no property for the joins: use miter joins with the default
+            // limit of SVG, like the other renderers
+            let join = path::LineJoin::Miter { limit: 4. };
+            let outline = path::Outline::stroke(&polylines, stroke_width, join);
+            self.draw_outline(&outline, crate::items::FillRule::Nonzero, stroke);
+        }
+    }
+
+    /// Draw a rectangle with a border and rounded corners, relative to the current item.
+    /// The colors are already mixed with the alpha of the current state.
+    #[allow(clippy::unnecessary_cast)] // Coord
+    fn draw_border_rectangle_impl(
+        &mut self,
+        geom: LogicalRect,
+        mut border: LogicalLength,
+        radius: LogicalLength,
+        color: Color,
+        border_color: Color,
+    ) {
+        let mut border_color = PremultipliedRgbaColor::from(border_color);
+        let color = PremultipliedRgbaColor::from(color);
+        if border_color.alpha == 0 {
+            border = LogicalLength::new(0 as _);
+        } else if border_color.alpha < 255 {
+            // Find a color for the border which is an equivalent to blend the background and then the border.
+            // In the end, the resulting of blending the background and the color is
+            // (A + B) + C, where A is the buffer color, B is the background, and C is the border.
+            // which expands to (A*(1-Bα) + B*Bα)*(1-Cα) + C*Cα = A*(1-(Bα+Cα-Bα*Cα)) + B*Bα*(1-Cα) + C*Cα
+            // so let the new alpha be: Nα = Bα+Cα-Bα*Cα, then this is A*(1-Nα) + N*Nα
+            // with N = (B*Bα*(1-Cα) + C*Cα)/Nα
+            // N being the equivalent color of the border that mixes the background and the border
+            // In pre-multiplied space, the formula simplifies further N' = B'*(1-Cα) + C'
+            let b = border_color;
+            let b_alpha_16 = b.alpha as u16;
+            border_color = PremultipliedRgbaColor {
+                red: ((color.red as u16 * (255 - b_alpha_16)) / 255) as u8 + b.red,
+                green: ((color.green as u16 * (255 - b_alpha_16)) / 255) as u8 + b.green,
+                blue: ((color.blue as u16 * (255 - b_alpha_16)) / 255) as u8 + b.blue,
+                alpha: (color.alpha as u16 + b_alpha_16 - (color.alpha as u16 * b_alpha_16) / 255)
+                    as u8,
+            }
+        }
+
+        if radius.get() > 0 as _ {
+            let radius =
+                radius.min(geom.width_length() / 2 as Coord).min(geom.height_length() / 2 as Coord);
+            if let Some(clipped) = geom.intersection(&self.current_state.clip) {
+                let geom2 = (geom.cast() * self.scale_factor).transformed(self.rotation);
+                let clipped2 = (clipped.cast() * self.scale_factor).transformed(self.rotation);
+                let geometry = (clipped.translate(self.current_state.offset.to_vector()).cast()
+                    * self.scale_factor)
+                    .round()
+                    .cast()
+                    .transformed(self.rotation);
+                // Add a small value to make sure that the clip is always positive despite floating point shenanigans
+                const E: f32 = 0.00001;
+
+                self.processor.process_rounded_rectangle(
+                    geometry,
+                    RoundedRectangle {
+                        radius: (radius.cast() * self.scale_factor).cast(),
+                        width: (border.cast() * self.scale_factor).cast(),
+                        border_color,
+                        inner_color: color,
+                        top_clip: PhysicalLength::new((clipped2.min_y() - geom2.min_y() + E) as _),
+                        bottom_clip: PhysicalLength::new(
+                            (geom2.max_y() - clipped2.max_y() + E) as _,
+                        ),
+                        left_clip: PhysicalLength::new((clipped2.min_x() - geom2.min_x() + E) as _),
+                        right_clip: PhysicalLength::new(
+                            (geom2.max_x() - clipped2.max_x() + E) as _,
+                        ),
+                    },
+                );
+            }
+            return;
+        }
+
+        if color.alpha > 0 {
+            if let Some(r) =
+                geom.inflate(-border.get(), -border.get()).intersection(&self.current_state.clip)
+            {
+                let geometry = (r.translate(self.current_state.offset.to_vector()).cast()
+                    * self.scale_factor)
+                    .round()
+                    .cast()
+                    .transformed(self.rotation);
+                self.processor.process_rectangle(geometry, color);
+            }
+        }
+
+        if border_color.alpha > 0 {
+            let mut add_border = |r: LogicalRect| {
+                if let Some(r) = r.intersection(&self.current_state.clip) {
+                    let geometry = (r.translate(self.current_state.offset.to_vector()).cast()
+                        * self.scale_factor)
+                        .round()
+                        .cast()
+                        .transformed(self.rotation);
+                    self.processor.process_rectangle(geometry, border_color);
+                }
+            };
+            let b = border.get();
+            add_border(euclid::rect(0 as _, 0 as _, geom.width(), b));
+            add_border(euclid::rect(0 as _, geom.height() - b, geom.width(), b));
+            add_border(euclid::rect(0 as _, b, b, geom.height() - b - b));
+            add_border(euclid::rect(geom.width() - b, b, b, geom.height() - b - b));
+        }
+    }
+
+    /// Fill the rectangle, relative to the current item, with a gradient
+    #[allow(clippy::unnecessary_cast)] // Coord
+    fn draw_gradient_rectangle(&mut self, geom: LogicalRect, brush: &Brush) {
+        let Brush::LinearGradient(g) = brush else {
+            self.draw_with_gradient(geom, geom, brush, |r| {
+                r.draw_plain_rectangle(geom, Color::from_rgb_u8(255, 255, 255))
+            });
+            return;
+        };
+        let Some(clipped) = geom.intersection(&self.current_state.clip) else { return };
+        let geom2 = (geom.cast() * self.scale_factor).transformed(self.rotation);
+        let clipped2 = (clipped.cast() * self.scale_factor).transformed(self.rotation);
+        let act_rect = (clipped.translate(self.current_state.offset.to_vector()).cast()
+            * self.scale_factor)
+            .round()
+            .cast()
+            .transformed(self.rotation);
+        let angle = g.angle() - self.rotation.orientation.angle();
+        let tan = angle.to_radians().tan().abs();
+        let start = if !tan.is_finite() {
+            255.
+        } else {
+            let h = tan * geom2.width() as f32;
+            255. * h / (h + geom2.height() as f32)
+        } as u8;
+        let mut angle = angle as i32 % 360;
+        if angle < 0 {
+            angle += 360;
+        }
+        let mut stops = g.stops().copied().peekable();
+        let mut idx = 0;
+        let stop_count = g.stops().count();
+        while let (Some(mut s1), Some(mut s2)) = (stops.next(), stops.peek().copied()) {
+            let mut flags = 0;
+            if (angle % 180) > 90 {
+                flags |= 0b1;
+            }
+            if angle <= 90 || angle > 270 {
+                core::mem::swap(&mut s1, &mut s2);
+                s1.position = 1. - s1.position;
+                s2.position = 1. - s2.position;
+                if idx == 0 {
+                    flags |= 0b100;
+                }
+                if idx == stop_count - 2 {
+                    flags |= 0b010;
+                }
+            } else {
+                if idx == 0 {
+                    flags |= 0b010;
+                }
+                if idx == stop_count - 2 {
+                    flags |= 0b100;
+                }
+            }
+
+            idx += 1;
+
+            let (adjust_left, adjust_right) = if (angle % 180) > 90 {
+                (
+                    (geom2.width() * s1.position).floor() as i16,
+                    (geom2.width() * (1. - s2.position)).ceil() as i16,
+                )
+            } else {
+                (
+                    (geom2.width() * (1. - s2.position)).ceil() as i16,
+                    (geom2.width() * s1.position).floor() as i16,
+                )
+            };
+
+            let gr = GradientCommand {
+                color1: self.alpha_color(s1.color).into(),
+                color2: self.alpha_color(s2.color).into(),
+                start,
+                flags,
+                top_clip: Length::new(
+                    (clipped2.min_y() - geom2.min_y()) as i16
+                        - (geom2.height() * s1.position).floor() as i16,
+                ),
+                bottom_clip: Length::new(
+                    (geom2.max_y() - clipped2.max_y()) as i16
+                        - (geom2.height() * (1. - s2.position)).ceil() as i16,
+                ),
+                left_clip: Length::new((clipped2.min_x() - geom2.min_x()) as i16 - adjust_left),
+                right_clip: Length::new((geom2.max_x() - clipped2.max_x()) as i16 - adjust_right),
+            };
+
+            let size_y = act_rect.height_length() + gr.top_clip + gr.bottom_clip;
+            let size_x = act_rect.width_length() + gr.left_clip + gr.right_clip;
+            if size_x.get() == 0 || size_y.get() == 0 {
+                // the position are too close to each other
+                // FIXME: For the first or the last, we should draw a plain color to the end
+                continue;
+            }
+
+            self.processor.process_gradient(act_rect, gr);
+        }
+    }
+
+    /// Draw a shape filled with the gradient `brush`. `draw` draws the shape in opaque white in
+    /// an intermediate buffer, whose pixels are then used as a mask for the gradient.
+    /// `bounds` is the area where the shape is drawn and `gradient_rect` the area the gradient
+    /// spans, both relative to the item.
+    fn draw_with_gradient(
+        &mut self,
+        bounds: LogicalRect,
+        gradient_rect: LogicalRect,
+        brush: &Brush,
+        draw: impl FnOnce(&mut SceneBuilder<'a, RenderToBuffer<'_, PremultipliedRgbaColor>>),
+    ) {
+        let Some(bounds) = bounds.intersection(&self.current_state.clip) else { return };
+        if self.current_state.alpha < 0.01 {
+            return;
+        }
+        let Some(shader) = draw_functions::GradientShader::new(
+            brush,
+            (gradient_rect.cast() * self.scale_factor).to_untyped(),
+        ) else {
+            return;
+        };
+        let buffer_rect = self.indirect_buffer_rect(bounds);
+        let size: PhysicalSize = buffer_rect.size.round().cast();
+        if size.is_empty() {
+            return;
+        }
+        let mut buffer = vec![PremultipliedRgbaColor::default(); size.area() as usize];
+        draw(&mut self.indirect_scene_builder(buffer_rect, &mut buffer));
+        for (y, line) in buffer.chunks_exact_mut(size.width as usize).enumerate() {
+            let pos_y = buffer_rect.origin.y + y as f32 + 0.5;
+            for (x, pixel) in line.iter_mut().enumerate() {
+                if pixel.alpha == 0 {
+                    continue;
+                }
+                let color = shader.color_at(buffer_rect.origin.x + x as f32 + 0.5, pos_y);
+                let coverage = pixel.alpha as u32;
+                let mask = |c: u8| ((c as u32 * coverage + 127) / 255) as u8;
+                *pixel = PremultipliedRgbaColor {
+                    red: mask(color.red),
+                    green: mask(color.green),
+                    blue: mask(color.blue),
+                    alpha: mask(color.alpha),
+                };
+            }
+        }
+        self.composite_indirect(buffer_rect, &buffer);
+    }
+}
+
+struct SelectionInfo {
+    selection_color: Color,
+    selection_background: Color,
+    selection: core::ops::Range<usize>,
+}
+
+#[derive(Clone)]
+struct RenderState {
+    alpha: f32,
+    offset: LogicalPoint,
+    clip: LogicalRect,
+    /// Set when the items are rotated. The clip is then the bounding box of the actual clip.
+    transform: Option<ItemTransform>,
+    /// Set when the items are clipped by a rectangle with rounded corners, or by a rectangle
+    /// within a transformation. The clip is then the bounding box of these clips.
+    rounded_clip: Option<Rc<RoundedClip>>,
+}
+
+/// A clip that is applied with a mask: a clip with rounded corners, applied by a `Clip` item
+/// with a border radius, or any clip within a transformation
+struct RoundedClip {
+    /// The rectangle in the logical coordinates of the window, or in the coordinates of the
+    /// transformed items if `from_window` is set
+    rect: LogicalRect,
+    radius: Coord,
+    /// Maps the logical coordinates of the window to the coordinates of `rect`
+    from_window: Option<euclid::default::Transform2D<f32>>,
+    /// The enclosing clip that is also applied with a mask
+    parent: Option<Rc<RoundedClip>>,
+}
+
+impl RoundedClip {
+    /// This clip and the enclosing ones
+    fn iter(self: &Rc<Self>) -> impl Iterator<Item = &Self> {
+        core::iter::successors(Some(&**self), |clip| clip.parent.as_deref())
+    }
+}
+
+/// The pixels of an item rendered in an intermediate buffer: the children of an `Opacity` or
+/// `Layer` element, or an item drawn with `SceneBuilder::draw_indirect`
+#[derive(Clone)]
+struct LayerPixels {
+    /// The area of the buffer, in physical pixels relative to the item
+    buffer_rect: euclid::Rect<f32, PhysicalPx>,
+    pixels: Rc<[PremultipliedRgbaColor]>,
+}
+
+/// A transformation of the items, applied by a `Rotate` item
+#[derive(Clone, Copy)]
+struct ItemTransform {
+    /// Maps the coordinates of the items, including the offset of the RenderState, to the
+    /// logical coordinates of the window
+    transform: euclid::default::Transform2D<f32>,
+    /// The clip in the logical coordinates of the window
+    clip: LogicalRect,
+}
+
+impl<'a, T: ProcessScene> crate::item_rendering::ItemRenderer for SceneBuilder<'a, T> {
+    #[allow(clippy::unnecessary_cast)] // Coord!
+    fn draw_rectangle(
+        &mut self,
+        rect: Pin<&crate::items::Rectangle>,
+        self_rc: &ItemRc,
+        size: LogicalSize,
+    ) {
+        if self.needs_indirect_drawing(size.into()) {
+            self.draw_indirect(self_rc, size.into(), |r| r.draw_rectangle(rect, self_rc, size));
+            return;
+        }
+        let geom = LogicalRect::from(size);
+        if self.should_draw(&geom) {
+            let background = rect.background();
+            if matches!(background, Brush::SolidColor(_)) {
+                self.draw_plain_rectangle(geom, self.alpha_color(background.color()));
+            } else {
+                self.draw_gradient_rectangle(geom, &background);
+            }
+        }
+    }
+
+    #[allow(clippy::unnecessary_cast)] // Coord
+    fn draw_border_rectangle(
+        &mut self,
+        rect: Pin<&crate::items::BorderRectangle>,
+        self_rc: &ItemRc,
+        size: LogicalSize,
+    ) {
+        if self.needs_indirect_drawing(size.into()) {
+            self.draw_indirect(self_rc, size.into(), |r| {
+                r.draw_border_rectangle(rect, self_rc, size)
+            });
+            return;
+        }
+        let geom = LogicalRect::from(size);
+        if self.should_draw(&geom) {
+            let border = rect.border_width();
+            let radius = rect.border_radius();
+            let background = rect.background();
+            let border_brush =
+                if border.get() as f32 > 0.01 { rect.border_color() } else { Brush::default() };
+            if matches!(background, Brush::SolidColor(_))
+                && matches!(border_brush, Brush::SolidColor(_))
+            {
+                self.draw_border_rectangle_impl(
+                    geom,
+                    border,
+                    radius,
+                    self.alpha_color(background.color()),
+                    self.alpha_color(border_brush.color()),
+                );
+                return;
+            }
+
+            // The background and the border are drawn separately, each with its own brush
+            let white = Color::from_rgb_u8(255, 255, 255);
+            if matches!(background, Brush::SolidColor(_)) {
+                let color = self.alpha_color(background.color());
+                self.draw_border_rectangle_impl(geom, border, radius, color, Color::default());
+            } else {
+                self.draw_with_gradient(geom, geom, &background, |r| {
+                    r.draw_border_rectangle_impl(geom, border, radius, white, Color::default())
+                });
+            }
+            if matches!(border_brush, Brush::SolidColor(_)) {
+                let border_color = self.alpha_color(border_brush.color());
+                self.draw_border_rectangle_impl(
+                    geom,
+                    border,
+                    radius,
+                    Color::default(),
+                    border_color,
+                );
+            } else {
+                self.draw_with_gradient(geom, geom, &border_brush, |r| {
+                    r.draw_border_rectangle_impl(geom, border, radius, Color::default(), white)
+                });
+            }
+        }
+    }
+
+    fn draw_image(
+        &mut self,
+        image: Pin<&crate::items::ImageItem>,
+        self_rc: &ItemRc,
+        size: LogicalSize,
+    ) {
+        if self.needs_indirect_drawing(size.into()) {
+            self.draw_indirect(self_rc, size.into(), |r| r.draw_image(image, self_rc, size));
+            return;
+        }
+        let geom = LogicalRect::from(size);
+        if self.should_draw(&geom) {
+            let source = image.source();
+            self.draw_image_impl(
+                geom,
+                &source,
+                euclid::Rect::new(Default::default(), source.size().cast()),
+                image.image_fit(),
+                image.colorize(),
+            );
+        }
+    }
+
+    fn draw_clipped_image(
+        &mut self,
+        image: Pin<&crate::items::ClippedImage>,
+        self_rc: &ItemRc,
+        size: LogicalSize,
+    ) {
+        if self.needs_indirect_drawing(size.into()) {
+            self.draw_indirect(self_rc, size.into(), |r| {
+                r.draw_clipped_image(image, self_rc, size)
+            });
+            return;
+        }
+        let geom = LogicalRect::from(size);
+        if self.should_draw(&geom) {
+            let source = image.source();
+
+            let source_clip_x = image.source_clip_x();
+            let source_clip_y = image.source_clip_y();
+            let source_size = source.size();
+            let mut source_clip_width = image.source_clip_width();
+            // when the source_clip size is empty, make it full
+            if source_clip_width == 0 {
+                source_clip_width = source_size.width as i32 - source_clip_x;
+            }
+            let mut source_clip_height = image.source_clip_height();
+            if source_clip_height == 0 {
+                source_clip_height = source_size.height as i32 - source_clip_y;
+            }
+
+            self.draw_image_impl(
+                geom,
+                &source,
+                euclid::rect(source_clip_x, source_clip_y, source_clip_width, source_clip_height),
+                image.image_fit(),
+                image.colorize(),
+            );
+        }
+    }
+
+    fn draw_text(&mut self, text: Pin<&crate::items::Text>, self_rc: &ItemRc, size: LogicalSize) {
+        if self.needs_indirect_drawing(size.into()) {
+            self.draw_indirect(self_rc, size.into(), |r| r.draw_text(text, self_rc, size));
+            return;
+        }
+        let string = text.text();
+        if string.trim().is_empty() {
+            return;
+        }
+        let geom = LogicalRect::from(size);
+        if !self.should_draw(&geom) {
+            return;
+        }
+
+        let brush = text.color();
+        if matches!(brush, Brush::SolidColor(_)) {
+            self.draw_text_impl(text, geom, self.alpha_color(brush.color()));
+        } else {
+            self.draw_with_gradient(geom, geom, &brush, |r| {
+                r.draw_text_impl(text, geom, Color::from_rgb_u8(255, 255, 255))
+            });
+        }
+    }
+
+    fn draw_text_input(
+        &mut self,
+        text_input: Pin<&crate::items::TextInput>,
+        self_rc: &ItemRc,
+        size: LogicalSize,
+    ) {
+        if self.needs_indirect_drawing(size.into()) {
+            self.draw_indirect(self_rc, size.into(), |r| {
+                r.draw_text_input(text_input, self_rc, size)
+            });
+            return;
+        }
+        let geom = LogicalRect::from(size);
+        if !self.should_draw(&geom) {
+            return;
+        }
+
+        let brush = text_input.color();
+        if matches!(brush, Brush::SolidColor(_)) {
+            self.draw_text_input_impl(text_input, geom, self.alpha_color(brush.color()), true);
+        } else {
+            // The selection is drawn with its plain colors, then the rest of the text and the
+            // cursor are drawn with the gradient
+            self.draw_text_input_impl(text_input, geom, Color::default(), true);
+            self.draw_with_gradient(geom, geom, &brush, |r| {
+                r.draw_text_input_impl(text_input, geom, Color::from_rgb_u8(255, 255, 255), false)
+            });
+        }
+    }
+
+    #[allow(clippy::unnecessary_cast)] // Coord
+    fn draw_path(&mut self, path: Pin<&crate::items::Path>, item_rc: &ItemRc, size: LogicalSize) {
+        use lyon_path::iterator::PathIterator;
+
+        if self.needs_indirect_drawing(size.into()) {
+            self.draw_indirect(item_rc, size.into(), |r| r.draw_path(path, item_rc, size));
+            return;
+        }
+        if !self.should_draw(&LogicalRect::from(size)) {
+            return;
+        }
+        let fill = path.fill();
+        let stroke = path.stroke();
+        if matches!(fill, Brush::SolidColor(_)) && matches!(stroke, Brush::SolidColor(_)) {
+            let (fill, stroke) = (self.alpha_color(fill.color()), self.alpha_color(stroke.color()));
+            self.draw_path_impl(path, item_rc, fill, stroke);
+            return;
+        }
+
+        // The fill and the stroke are drawn separately, each with its own brush that spans the
+        // bounding box of the path
+        let Some((offset, path_events)) = path.fitted_path_events(item_rc) else { return };
+        let gradient_rect = LogicalRect::from_untyped(
+            &lyon_path::math::Box2D::from_points(
+                path_events.iter().flattened(0.1).map(|event| event.to()),
+            )
+            .to_rect()
+            .cast(),
+        )
+        .translate(offset);
+        // The stroke may be drawn outside of the item
+        let stroke_width = path.stroke_width();
+        let bounds = LogicalRect::from(size)
+            .union(&gradient_rect)
+            .inflate(stroke_width.get(), stroke_width.get());
+        let white = Color::from_rgb_u8(255, 255, 255);
+        if matches!(fill, Brush::SolidColor(_)) {
+            self.draw_path_impl(path, item_rc, self.alpha_color(fill.color()), Color::default());
+        } else {
+            self.draw_with_gradient(bounds, gradient_rect, &fill, |r| {
+                r.draw_path_impl(path, item_rc, white, Color::default())
+            });
+        }
+        if matches!(stroke, Brush::SolidColor(_)) {
+            self.draw_path_impl(path, item_rc, Color::default(), self.alpha_color(stroke.color()));
+        } else {
+            self.draw_with_gradient(bounds, gradient_rect, &stroke, |r| {
+                r.draw_path_impl(path, item_rc, Color::default(), white)
+            });
+        }
     }
 
     fn draw_box_shadow(
         &mut self,
-        _box_shadow: Pin<&crate::items::BoxShadow>,
-        _: &ItemRc,
-        _size: LogicalSize,
+        box_shadow: Pin<&crate::items::BoxShadow>,
+        self_rc: &ItemRc,
+        size: LogicalSize,
     ) {
-        // TODO
+        let offset = LogicalVector::from_lengths(box_shadow.offset_x(), box_shadow.offset_y());
+        if offset.x == 0 as Coord && offset.y == 0 as Coord && box_shadow.blur() <= Zero::zero() {
+            return;
+        }
+        let blur = box_shadow.blur().get();
+        let bounds = LogicalRect::from(size).translate(offset).inflate(blur, blur);
+        if self.needs_indirect_drawing(bounds) {
+            self.draw_indirect(self_rc, bounds, |r| r.draw_box_shadow(box_shadow, self_rc, size));
+            return;
+        }
+        let color = self.alpha_color(box_shadow.color());
+        if color.alpha() == 0 {
+            return;
+        }
+        let Some(mut shadow_options) =
+            BoxShadowOptions::new(self_rc, box_shadow, self.scale_factor)
+        else {
+            return;
+        };
+        // The alpha map doesn't depend on the color, so it is kept when only the color changes
+        shadow_options.color = Color::default();
+        let blur = shadow_options.blur.get().max(0.);
+        let (_, shadow) = self.software_renderer.box_shadow_cache.get_or_update(
+            self_rc,
+            |(options, _)| *options == shadow_options,
+            || (shadow_options.clone(), box_shadow::render_box_shadow(&shadow_options)),
+        );
+
+        let origin = ((self.current_state.offset + offset).cast() * self.scale_factor
+            - euclid::vec2(blur, blur))
+        .round()
+        .cast();
+        let shadow_rect = PhysicalRect::new(origin, shadow.size);
+        let physical_clip =
+            (self.current_state.clip.translate(self.current_state.offset.to_vector()).cast()
+                * self.scale_factor)
+                .round()
+                .cast();
+        let Some(geometry) = shadow_rect.intersection(&physical_clip) else {
+            return;
+        };
+        if geometry.is_empty() {
+            return;
+        }
+
+        self.processor.process_shared_image_buffer(
+            geometry.transformed(self.rotation),
+            SharedBufferCommand {
+                buffer: SharedBufferData::AlphaMap {
+                    data: shadow.data,
+                    width: shadow.size.width as u16,
+                },
+                source_rect: geometry.translate(-origin.to_vector()),
+                colorize: color,
+                // color already is mixed with global alpha
+                alpha: color.alpha(),
+                rotation: self.rotation.orientation,
+            },
+        );
     }
 
     fn combine_clip(
         &mut self,
         other: LogicalRect,
-        _radius: LogicalLength,
-        _border_width: LogicalLength,
+        radius: LogicalLength,
+        border_width: LogicalLength,
     ) -> bool {
+        if let Some(item_transform) = &mut self.current_state.transform {
+            // The clip of the window is a rectangle, so this clips to the bounding box of the
+            // transformed rectangle
+            let other_in_window = item_transform.transform.outer_transformed_rect(
+                &other.translate(self.current_state.offset.to_vector()).cast().to_untyped(),
+            );
+            item_transform.clip = item_transform
+                .clip
+                .intersection(&LogicalRect::from_untyped(&other_in_window.cast()))
+                .unwrap_or_default();
+        }
+        // The clip of the window is only a rectangle, so the rounded corners, and the clips
+        // within a transformation, are applied with a mask when compositing the items
+        let from_window = match &self.current_state.transform {
+            Some(item_transform) => item_transform.transform.inverse().map(Some),
+            None => Some(None),
+        };
+        if let Some(from_window) = from_window.filter(|f| radius.get() > 0 as Coord || f.is_some())
+        {
+            // Like the other renderers, the clip is within the middle of the border
+            let rect = other.translate(self.current_state.offset.to_vector());
+            let border_width =
+                border_width.get().clamp(0 as Coord, rect.width().min(rect.height()) / 2 as Coord);
+            let rect = rect.inflate(-border_width / 2 as Coord, -border_width / 2 as Coord);
+            self.current_state.rounded_clip = Some(Rc::new(RoundedClip {
+                rect,
+                radius: radius.get(),
+                from_window,
+                parent: self.current_state.rounded_clip.take(),
+            }));
+        }
         match self.current_state.clip.intersection(&other) {
             Some(r) => {
                 self.current_state.clip = r;
@@ -2104,7 +3240,6 @@ fn combine_clip(
                 false
             }
         }
-        // TODO: handle radius and border
     }
 
     fn get_current_clip(&self) -> LogicalRect {
@@ -2116,16 +3251,48 @@ fn translate(&mut self, distance: LogicalVector) {
         self.current_state.clip = self.current_state.clip.translate(-distance)
     }
 
-    fn rotate(&mut self, _angle_in_degrees: f32) {
-        todo!()
+    fn rotate(&mut self, angle_in_degrees: f32) {
+        self.apply_transform(euclid::Transform2D::rotation(euclid::Angle::degrees(
+            angle_in_degrees,
+        )));
     }
 
     fn apply_opacity(&mut self, opacity: f32) {
         self.current_state.alpha *= opacity;
     }
 
+    fn visit_opacity(
+        &mut self,
+        opacity_item: Pin<&crate::items::Opacity>,
+        item_rc: &ItemRc,
+        size: LogicalSize,
+    ) -> RenderingResult {
+        let opacity = opacity_item.opacity();
+        if crate::items::Opacity::need_layer(item_rc, opacity) {
+            self.render_and_blend_layer(opacity, item_rc, size)
+        } else {
+            self.apply_opacity(opacity);
+            self.software_renderer.layer_cache.release(item_rc);
+            RenderingResult::ContinueRenderingChildren
+        }
+    }
+
+    fn visit_layer(
+        &mut self,
+        layer_item: Pin<&crate::items::Layer>,
+        item_rc: &ItemRc,
+        size: LogicalSize,
+    ) -> RenderingResult {
+        if layer_item.cache_rendering_hint() {
+            self.render_and_blend_layer(1.0, item_rc, size)
+        } else {
+            self.software_renderer.layer_cache.release(item_rc);
+            RenderingResult::ContinueRenderingChildren
+        }
+    }
+
     fn save_state(&mut self) {
-        self.state_stack.push(self.current_state);
+        self.state_stack.push(self.current_state.clone());
     }
 
     fn restore_state(&mut self) {
@@ -2138,40 +3305,32 @@ fn scale_factor(&self) -> f32 {
 
     fn draw_cached_pixmap(
         &mut self,
-        _item: &ItemRc,
+        item: &ItemRc,
         update_fn: &dyn Fn(&mut dyn FnMut(u32, u32, &[u8])),
     ) {
-        // FIXME: actually cache the pixmap
-        update_fn(&mut |width, height, data| {
-            let img = SharedImageBuffer::RGBA8Premultiplied(SharedPixelBuffer::clone_from_slice(
-                data, width, height,
-            ));
-
-            let physical_clip = self.current_state.clip.cast() * self.scale_factor;
-            let src_rect = euclid::rect(0., 0., width as f32, height as f32);
-
-            if let Some(clipped_src) = src_rect.intersection(&physical_clip) {
-                let offset = self.current_state.offset.to_vector().cast() * self.scale_factor;
-                let geometry = clipped_src.translate(offset).round();
-                let origin = (geometry.origin - offset.round()).cast::<usize>();
-                let actual_x = origin.x - src_rect.origin.x as usize;
-                let actual_y = origin.y - src_rect.origin.y as usize;
-
-                self.processor.process_shared_image_buffer(
-                    geometry.cast().transformed(self.rotation),
-                    SharedBufferCommand {
-                        buffer: SharedBufferData::SharedImage(img),
-                        source_rect: PhysicalRect::new(
-                            PhysicalPoint::new(actual_x as _, actual_y as _),
-                            geometry.size.cast(),
-                        ),
-                        colorize: Default::default(),
-                        alpha: (self.current_state.alpha * 255.) as u8,
-                        rotation: self.rotation.orientation,
-                    },
-                );
-            }
-        });
+        let Some(img) = self.software_renderer.pixmap_cache.get_or_update(
+            item,
+            |_| true,
+            || {
+                let mut img = None;
+                update_fn(&mut |width, height, data| {
+                    img = Some(SharedImageBuffer::RGBA8Premultiplied(
+                        SharedPixelBuffer::clone_from_slice(data, width, height),
+                    ));
+                });
+                img
+            },
+        ) else {
+            return;
+        };
+        let bounds = LogicalRect::from_size(
+            (euclid::size2(img.width() as f32, img.height() as f32) / self.scale_factor).cast(),
+        );
+        if self.needs_indirect_drawing(bounds) {
+            self.draw_indirect(item, bounds, |r| r.draw_pixmap(img));
+            return;
+        }
+        self.draw_pixmap(img);
     }
 
     fn draw_string(&mut self, string: &str, color: Color) {
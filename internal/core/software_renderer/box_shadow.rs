@@ -0,0 +1,132 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! This module contains the rendering of the box shadows into alpha maps.
+//!
+//! The rounded rectangle is rasterized with anti-aliasing, and then blurred with three
+//! successive box blurs, which is a good approximation of a gaussian blur.
+
+use super::PhysicalSize;
+use crate::graphics::boxshadowcache::BoxShadowOptions;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+#[allow(unused)]
+use num_traits::Float;
+
+/// The alpha map of a box shadow, including the blur around the box
+#[derive(Clone)]
+pub(super) struct BoxShadowImage {
+    pub data: Rc<[u8]>,
+    pub size: PhysicalSize,
+}
+
+/// Render the shadow of the box described by the options. The box is placed at a distance
+/// of `options.blur` from the top left corner of the image, and the color is ignored.
+pub(super) fn render_box_shadow(options: &BoxShadowOptions) -> BoxShadowImage {
+    let blur = options.blur.get().max(0.);
+    let (width, height) = (options.width.get(), options.height.get());
+    let size = PhysicalSize::new(
+        (width + 2. * blur).ceil().min(i16::MAX as f32) as i16,
+        (height + 2. * blur).ceil().min(i16::MAX as f32) as i16,
+    );
+    let (image_width, image_height) = (size.width as usize, size.height as usize);
+
+    let coverage = super::draw_functions::RoundedRectCoverage::new(
+        euclid::rect(blur, blur, width, height),
+        options.radius.get(),
+    );
+    let mut data = Vec::with_capacity(image_width * image_height);
+    for y in 0..image_height {
+        data.extend((0..image_width).map(|x| coverage.at(x as f32, y as f32)));
+    }
+
+    // Like the other renderers, the blur radius is twice the standard deviation
+    let sigma = blur / 2.;
+    if sigma > 0. {
+        let mut line = Vec::new();
+        for box_radius in box_blur_radii(sigma) {
+            for row in data.chunks_exact_mut(image_width) {
+                box_blur(row, 1, box_radius, &mut line);
+            }
+            for x in 0..image_width {
+                box_blur(&mut data[x..], image_width, box_radius, &mut line);
+            }
+        }
+    }
+
+    BoxShadowImage { data: data.into(), size }
+}
+
+/// The radii of the three box blurs that approximate a gaussian blur with the given
+/// standard deviation
+fn box_blur_radii(sigma: f32) -> [usize; 3] {
+    const PASSES: f32 = 3.;
+    // See "Fast Almost-Gaussian Filtering" by Peter Kovesi
+    let ideal_width = (12. * sigma * sigma / PASSES + 1.).sqrt();
+    let mut lower_width = ideal_width.floor() as i32;
+    if lower_width % 2 == 0 {
+        lower_width -= 1;
+    }
+    let lower_width = lower_width.max(1);
+    let lower = lower_width as f32;
+    let lower_count =
+        ((12. * sigma * sigma - PASSES * lower * lower - 4. * PASSES * lower - 3. * PASSES)
+            / (-4. * lower - 4.))
+            .round() as i32;
+    core::array::from_fn(|i| {
+        let width = if (i as i32) < lower_count { lower_width } else { lower_width + 2 };
+        (width as usize - 1) / 2
+    })
+}
+
+/// Blur the pixels `data[0]`, `data[stride]`, `data[2 * stride]`, ... with a box of the given
+/// radius. The pixels outside of the line are considered transparent.
+fn box_blur(data: &mut [u8], stride: usize, radius: usize, line: &mut Vec<u8>) {
+    line.clear();
+    line.extend(data.iter().step_by(stride).copied());
+    let len = line.len();
+    let box_width = 2 * radius as u32 + 1;
+    let mut sum: u32 = line.iter().take(radius).map(|a| *a as u32).sum();
+    for (i, pixel) in data.iter_mut().step_by(stride).enumerate() {
+        if i + radius < len {
+            sum += line[i + radius] as u32;
+        }
+        *pixel = ((sum + box_width / 2) / box_width) as u8;
+        if i >= radius {
+            sum -= line[i - radius] as u32;
+        }
+    }
+}
+
+#[test]
+fn box_shadow() {
+    let options = BoxShadowOptions {
+        width: euclid::Length::new(20.),
+        height: euclid::Length::new(10.),
+        blur: euclid::Length::new(4.),
+        radius: euclid::Length::new(2.),
+        ..Default::default()
+    };
+    let image = render_box_shadow(&options);
+    assert_eq!(image.size, PhysicalSize::new(28, 18));
+    let at = |x: usize, y: usize| image.data[y * 28 + x];
+    // Opaque in the middle, transparent in the corners, and symmetric
+    assert_eq!(at(14, 9), 255);
+    assert_eq!(at(0, 0), 0);
+    assert_eq!(at(27, 17), 0);
+    assert_eq!(at(4, 9), at(23, 9));
+    assert_eq!(at(14, 4), at(14, 13));
+    // The blur is a smooth gradient around the edge of the box
+    let edge = (0..14).map(|x| at(x, 9)).collect::<Vec<_>>();
+    assert!(edge.windows(2).all(|w| w[0] <= w[1]), "{edge:?}");
+    assert!(at(4, 9) > 64 && at(4, 9) < 192, "{edge:?}");
+
+    // Without blur, only the edges of the rounded rectangle are anti-aliased
+    let options = BoxShadowOptions { blur: euclid::Length::new(0.), ..options };
+    let image = render_box_shadow(&options);
+    assert_eq!(image.size, PhysicalSize::new(20, 10));
+    assert_eq!(image.data[0], 97);
+    assert_eq!(image.data[5 * 20], 255);
+
+    assert_eq!(box_blur_radii(2.), [1, 1, 2]);
+}
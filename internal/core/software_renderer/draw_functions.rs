@@ -10,8 +10,11 @@
 use crate::graphics::{PixelFormat, Rgb8Pixel};
 use crate::lengths::{PointLengths, RectLengths, SizeLengths};
 use crate::Color;
+use alloc::vec::Vec;
 use derive_more::{Add, Mul, Sub};
 use integer_sqrt::IntegerSquareRoot;
+#[allow(unused)]
+use num_traits::Float;
 
 /// Draw one line of the texture in the line buffer
 pub(super) fn draw_texture_line(
@@ -435,6 +438,156 @@ pub(super) fn draw_gradient_line(
     }
 }
 
+/// Sample the buffer of `size` pixels at the position `(x, y)`, where the pixels are at integer
+/// coordinates, with bilinear interpolation. The pixels outside of the buffer are transparent.
+pub(super) fn sample_bilinear(
+    buffer: &[PremultipliedRgbaColor],
+    size: super::PhysicalSize,
+    x: f32,
+    y: f32,
+) -> PremultipliedRgbaColor {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (((x - x0) * 255.) as u32, ((y - y0) * 255.) as u32);
+    let (x0, y0) = (x0 as i32, y0 as i32);
+    let pixel = |x: i32, y: i32| {
+        if x < 0 || y < 0 || x >= size.width as i32 || y >= size.height as i32 {
+            PremultipliedRgbaColor::default()
+        } else {
+            buffer[y as usize * size.width as usize + x as usize]
+        }
+    };
+    let lerp = |a: PremultipliedRgbaColor, b: PremultipliedRgbaColor, f: u32| {
+        let mix = |a: u8, b: u8| ((a as u32 * (255 - f) + b as u32 * f + 127) / 255) as u8;
+        PremultipliedRgbaColor {
+            red: mix(a.red, b.red),
+            green: mix(a.green, b.green),
+            blue: mix(a.blue, b.blue),
+            alpha: mix(a.alpha, b.alpha),
+        }
+    };
+    let top = lerp(pixel(x0, y0), pixel(x0 + 1, y0), fx);
+    let bottom = lerp(pixel(x0, y0 + 1), pixel(x0 + 1, y0 + 1), fx);
+    lerp(top, bottom, fy)
+}
+
+/// A rounded rectangle, in physical pixels, used to compute the anti-aliased coverage of pixels
+#[derive(Clone, Copy, Debug)]
+pub(super) struct RoundedRectCoverage {
+    center: (f32, f32),
+    /// Half of the size of the rectangle without the rounded corners
+    half_size: (f32, f32),
+    radius: f32,
+}
+
+impl RoundedRectCoverage {
+    pub fn new(rect: euclid::default::Rect<f32>, radius: f32) -> Self {
+        let radius = radius.clamp(0., rect.width().min(rect.height()) / 2.);
+        let center = rect.center();
+        Self {
+            center: (center.x, center.y),
+            half_size: (rect.width() / 2. - radius, rect.height() / 2. - radius),
+            radius,
+        }
+    }
+
+    /// The coverage, between 0 and 255, of the pixel whose top left corner is at `(x, y)`
+    pub fn at(&self, x: f32, y: f32) -> u8 {
+        // Signed distance from the center of the pixel to the rounded rectangle
+        let dx = ((x + 0.5 - self.center.0).abs() - self.half_size.0).max(0.);
+        let dy = ((y + 0.5 - self.center.1).abs() - self.half_size.1).max(0.);
+        let distance = (dx * dx + dy * dy).sqrt() - self.radius;
+        ((0.5 - distance).clamp(0., 1.) * 255. + 0.5) as u8
+    }
+}
+
+/// A linear or radial gradient spanning a rectangle, in physical pixels, used to compute the
+/// color of each pixel
+#[derive(Clone, Debug)]
+pub(super) struct GradientShader {
+    kind: GradientKind,
+    /// The stops, sorted by position
+    stops: Vec<(f32, PremultipliedRgbaColor)>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum GradientKind {
+    /// The position along the gradient is the dot product of `factor` with the vector
+    /// from `start` to the point
+    Linear {
+        start: (f32, f32),
+        factor: (f32, f32),
+    },
+    Radial {
+        center: (f32, f32),
+        radius: f32,
+    },
+}
+
+impl GradientShader {
+    /// Returns None if the brush is not a gradient, or has no stops
+    pub fn new(brush: &crate::Brush, rect: euclid::default::Rect<f32>) -> Option<Self> {
+        let (kind, stops) = match brush {
+            crate::Brush::LinearGradient(g) => {
+                let (start, end) = crate::graphics::line_for_angle(g.angle());
+                let start = (
+                    rect.origin.x + start.x * rect.width(),
+                    rect.origin.y + start.y * rect.height(),
+                );
+                let delta = (
+                    rect.origin.x + end.x * rect.width() - start.0,
+                    rect.origin.y + end.y * rect.height() - start.1,
+                );
+                let length_sq = delta.0 * delta.0 + delta.1 * delta.1;
+                let factor = if length_sq > 0. {
+                    (delta.0 / length_sq, delta.1 / length_sq)
+                } else {
+                    (0., 0.)
+                };
+                (GradientKind::Linear { start, factor }, g.stops().collect::<Vec<_>>())
+            }
+            crate::Brush::RadialGradient(g) => {
+                let center = rect.center();
+                let radius = (rect.width() + rect.height()) / 4.;
+                (GradientKind::Radial { center: (center.x, center.y), radius }, g.stops().collect())
+            }
+            _ => return None,
+        };
+        let mut stops: Vec<_> = stops.iter().map(|s| (s.position, s.color.into())).collect();
+        if stops.is_empty() {
+            return None;
+        }
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Some(Self { kind, stops })
+    }
+
+    /// The color of the pixel whose center is at `(x, y)`
+    pub fn color_at(&self, x: f32, y: f32) -> PremultipliedRgbaColor {
+        let position = match self.kind {
+            GradientKind::Linear { start, factor } => {
+                (x - start.0) * factor.0 + (y - start.1) * factor.1
+            }
+            GradientKind::Radial { center, radius } => {
+                let (dx, dy) = (x - center.0, y - center.1);
+                if radius > 0. {
+                    (dx * dx + dy * dy).sqrt() / radius
+                } else {
+                    1.
+                }
+            }
+        };
+        let next = self.stops.partition_point(|s| s.0 <= position);
+        if next == 0 {
+            return self.stops[0].1;
+        }
+        if next == self.stops.len() {
+            return self.stops[next - 1].1;
+        }
+        let (p1, c1) = self.stops[next - 1];
+        let (p2, c2) = self.stops[next];
+        interpolate_color(((position - p1) / (p2 - p1) * 255. + 0.5) as u32, c1, c2)
+    }
+}
+
 /// A color whose component have been pre-multiplied by alpha
 ///
 /// The renderer operates faster on pre-multiplied color since it
@@ -597,6 +750,83 @@ fn from(p: Rgb565Pixel) -> Self {
     }
 }
 
+/// An 8bit grayscale pixel, where 0 is black and 255 is white
+#[repr(transparent)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Gray8Pixel(pub u8);
+
+impl Gray8Pixel {
+    /// The luma of the color, with the ITU-R BT.601 coefficients
+    fn luma(red: u8, green: u8, blue: u8) -> u8 {
+        ((red as u32 * 77 + green as u32 * 150 + blue as u32 * 29 + 128) >> 8) as u8
+    }
+}
+
+impl TargetPixel for Gray8Pixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let a = (u8::MAX - color.alpha) as u16;
+        // The luma is linear, so it can be computed on the pre-multiplied components
+        let luma = Self::luma(color.red, color.green, color.blue);
+        self.0 = ((self.0 as u16 * a / 255) as u8).saturating_add(luma);
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self(Self::luma(r, g, b))
+    }
+}
+
+impl From<Rgb8Pixel> for Gray8Pixel {
+    fn from(p: Rgb8Pixel) -> Self {
+        Self::from_rgb(p.r, p.g, p.b)
+    }
+}
+
+impl From<Gray8Pixel> for Rgb8Pixel {
+    fn from(p: Gray8Pixel) -> Self {
+        Rgb8Pixel { r: p.0, g: p.0, b: p.0 }
+    }
+}
+
+/// A 32bit pixel with the blue, green, red and alpha components in that order in memory,
+/// as used by many Linux frame buffers.
+///
+/// The color components are pre-multiplied by alpha.
+#[allow(missing_docs)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct Bgra8888Pixel {
+    pub blue: u8,
+    pub green: u8,
+    pub red: u8,
+    pub alpha: u8,
+}
+
+impl TargetPixel for Bgra8888Pixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let a = (u8::MAX - color.alpha) as u16;
+        self.red = (self.red as u16 * a / 255) as u8 + color.red;
+        self.green = (self.green as u16 * a / 255) as u8 + color.green;
+        self.blue = (self.blue as u16 * a / 255) as u8 + color.blue;
+        self.alpha = (self.alpha as u16 * a / 255) as u8 + color.alpha;
+    }
+
+    fn from_rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { red: r, green: g, blue: b, alpha: 255 }
+    }
+}
+
+impl From<Rgb8Pixel> for Bgra8888Pixel {
+    fn from(p: Rgb8Pixel) -> Self {
+        Self::from_rgb(p.r, p.g, p.b)
+    }
+}
+
+impl From<Bgra8888Pixel> for Rgb8Pixel {
+    fn from(p: Bgra8888Pixel) -> Self {
+        Rgb8Pixel { r: p.red, g: p.green, b: p.blue }
+    }
+}
+
 #[test]
 fn rgb565() {
     let pix565 = Rgb565Pixel::from_rgb(0xff, 0x25, 0);
@@ -607,3 +837,60 @@ fn rgb565() {
     let pix888: Rgb8Pixel = pix565.into();
     assert_eq!(pix565, pix888.into());
 }
+
+#[test]
+fn gray8() {
+    assert_eq!(Gray8Pixel::from_rgb(0, 0, 0), Gray8Pixel(0));
+    assert_eq!(Gray8Pixel::from_rgb(0xff, 0xff, 0xff), Gray8Pixel(0xff));
+    // green is brighter than red, which is brighter than blue
+    let (r, g, b) = (
+        Gray8Pixel::from_rgb(0xff, 0, 0),
+        Gray8Pixel::from_rgb(0, 0xff, 0),
+        Gray8Pixel::from_rgb(0, 0, 0xff),
+    );
+    assert!(g.0 > r.0 && r.0 > b.0);
+
+    let mut pix = Gray8Pixel(0xff);
+    pix.blend(Color::from_argb_u8(0x80, 0, 0, 0).into());
+    assert_eq!(pix, Gray8Pixel(0x7f));
+    pix.blend(Color::from_argb_u8(0xff, 0xff, 0xff, 0xff).into());
+    assert_eq!(pix, Gray8Pixel(0xff));
+}
+
+#[test]
+fn bgra8888() {
+    let pix = Bgra8888Pixel::from_rgb(0x12, 0x34, 0x56);
+    assert_eq!(bytemuck::bytes_of(&pix), &[0x56, 0x34, 0x12, 0xff]);
+
+    let mut pix = Bgra8888Pixel::default();
+    pix.blend(Color::from_argb_u8(0x80, 0xff, 0, 0).into());
+    assert_eq!(pix, Bgra8888Pixel { blue: 0, green: 0, red: 0x80, alpha: 0x80 });
+    pix.blend(Color::from_argb_u8(0xff, 0, 0, 0xff).into());
+    assert_eq!(pix, Bgra8888Pixel { blue: 0xff, green: 0, red: 0, alpha: 0xff });
+}
+
+#[test]
+fn gradient_shader() {
+    use crate::graphics::{GradientStop, LinearGradientBrush, RadialGradientBrush};
+    let stops = [
+        GradientStop { color: Color::from_rgb_u8(0xff, 0, 0), position: 0. },
+        GradientStop { color: Color::from_rgb_u8(0, 0, 0xff), position: 1. },
+    ];
+    let rect = euclid::rect(10., 0., 100., 10.);
+    let rgba = |c: PremultipliedRgbaColor| (c.red, c.green, c.blue, c.alpha);
+
+    // From left to right
+    let linear = GradientShader::new(&LinearGradientBrush::new(90., stops).into(), rect).unwrap();
+    assert_eq!(rgba(linear.color_at(0., 5.)), (0xff, 0, 0, 0xff));
+    assert_eq!(rgba(linear.color_at(60., 0.)), (0x7f, 0, 0x80, 0xff));
+    assert_eq!(rgba(linear.color_at(60., 10.)), (0x7f, 0, 0x80, 0xff));
+    assert_eq!(rgba(linear.color_at(120., 5.)), (0, 0, 0xff, 0xff));
+
+    // The radius is the average of the half width and half height
+    let radial = GradientShader::new(&RadialGradientBrush::new_circle(stops).into(), rect).unwrap();
+    assert_eq!(rgba(radial.color_at(60., 5.)), (0xff, 0, 0, 0xff));
+    assert_eq!(rgba(radial.color_at(60. + 27.5, 5.)), (0, 0, 0xff, 0xff));
+    assert_eq!(rgba(radial.color_at(60., 5. + 27.5 / 2.)), (0x7f, 0, 0x80, 0xff));
+
+    assert!(GradientShader::new(&Color::from_rgb_u8(0, 0, 0).into(), rect).is_none());
+}
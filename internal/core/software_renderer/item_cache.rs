@@ -0,0 +1,80 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! This module contains the cache of the pixels rendered for some items, such as the layers,
+//! which are kept until the properties they were rendered from change.
+
+use crate::graphics::CachedGraphicsData;
+use crate::item_tree::{ItemRc, ItemTreeRef};
+use alloc::collections::BTreeMap;
+use core::cell::RefCell;
+
+/// A cache of data computed for items, invalidated when the properties that were read to
+/// compute the data change.
+///
+/// Like [`crate::item_rendering::ItemCache`], the entries are looked up by item tree and by
+/// index of the item, but this cache doesn't need the standard library.
+/// [`ItemCache::component_destroyed`] must be called to clear the cache for that component.
+pub(super) struct ItemCache<T> {
+    /// The pointer is a pointer to a component
+    map: RefCell<BTreeMap<*const vtable::Dyn, BTreeMap<u32, CachedGraphicsData<T>>>>,
+}
+
+impl<T> Default for ItemCache<T> {
+    fn default() -> Self {
+        Self { map: Default::default() }
+    }
+}
+
+impl<T: Clone> ItemCache<T> {
+    /// Returns the data cached for the item if `is_valid` accepts it and the properties it
+    /// depends on didn't change. Otherwise, the data is computed again with `update_fn`.
+    ///
+    /// The dependencies are also registered to the current binding, so that a layer that
+    /// contains this item is rendered again when it changes.
+    pub fn get_or_update(
+        &self,
+        item: &ItemRc,
+        is_valid: impl FnOnce(&T) -> bool,
+        update_fn: impl FnOnce() -> T,
+    ) -> T {
+        let component = &(**item.item_tree()) as *const _;
+        if let Some(entry) =
+            self.map.borrow().get(&component).and_then(|entries| entries.get(&item.index()))
+        {
+            if let Some(tracker) = &entry.dependency_tracker {
+                if !tracker.is_dirty() && is_valid(&entry.data) {
+                    tracker.as_ref().register_as_dependency_to_current_binding();
+                    return entry.data.clone();
+                }
+            }
+        }
+
+        // The cache is not borrowed during the update, which may render other cached items
+        let entry = CachedGraphicsData::new(update_fn);
+        if let Some(tracker) = &entry.dependency_tracker {
+            tracker.as_ref().register_as_dependency_to_current_binding();
+        }
+        let data = entry.data.clone();
+        self.map.borrow_mut().entry(component).or_default().insert(item.index(), entry);
+        data
+    }
+
+    /// Forget the data cached for the item
+    pub fn release(&self, item: &ItemRc) {
+        let component = &(**item.item_tree()) as *const _;
+        if let Some(entries) = self.map.borrow_mut().get_mut(&component) {
+            entries.remove(&item.index());
+        }
+    }
+
+    /// Function that must be called when a component is destroyed.
+    pub fn component_destroyed(&self, component: ItemTreeRef) {
+        let component_ptr: *const _ = ItemTreeRef::as_ptr(component).cast().as_ptr();
+        self.map.borrow_mut().remove(&component_ptr);
+    }
+
+    pub fn clear(&self) {
+        self.map.borrow_mut().clear();
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! This module contains the support for the pixel formats that pack several pixels in a byte,
+//! such as the ones of e-paper and monochrome displays.
+//!
+//! The renderer draws [`Gray8Pixel`]s, which are then quantized, optionally with dithering,
+//! and packed in the target buffer.
+
+use super::draw_functions::Gray8Pixel;
+use super::{LineBufferProvider, PhysicalRegion};
+use alloc::vec::Vec;
+
+/// The format of a buffer whose pixels are packed in bytes.
+///
+/// The first pixel of a line is in the most significant bits of the first byte, and each line
+/// starts on a new byte.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PackedPixelFormat {
+    /// 4 bit grayscale: two pixels per byte, where 0 is black and 15 is white
+    Gray4,
+    /// 1 bit monochrome: eight pixels per byte, where 0 is black and 1 is white
+    Mono1,
+}
+
+impl PackedPixelFormat {
+    /// The number of bits of a pixel
+    pub fn bits_per_pixel(self) -> usize {
+        match self {
+            Self::Gray4 => 4,
+            Self::Mono1 => 1,
+        }
+    }
+
+    /// The number of bytes of a line of `width` pixels
+    pub fn bytes_per_line(self, width: usize) -> usize {
+        (width * self.bits_per_pixel() + 7) / 8
+    }
+
+    /// The highest value of a pixel
+    fn max_value(self) -> u16 {
+        (1 << self.bits_per_pixel()) - 1
+    }
+}
+
+/// The dithering applied when the grayscale pixels are quantized to a [`PackedPixelFormat`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum Dithering {
+    /// Each pixel is rounded to the closest value
+    #[default]
+    None,
+    /// Ordered dithering with a 4x4 Bayer matrix. The result of a pixel only depends on its
+    /// position, so partial rendering gives the same pixels as rendering the whole frame.
+    Ordered,
+    /// Floyd-Steinberg error diffusion. The error is only diffused within the rendered region,
+    /// from one line to the next one.
+    ErrorDiffusion,
+}
+
+const BAYER_MATRIX: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// The error diffused from a line to the next one, for the pixels in `range`
+struct DiffusedError {
+    range: core::ops::Range<usize>,
+    /// The line that receives the error
+    line: usize,
+    /// errors[i + 1] is the error diffused to the pixel `range.start + i`
+    errors: Vec<i16>,
+}
+
+/// Quantizes lines of [`Gray8Pixel`]s and packs them in a buffer of a [`PackedPixelFormat`]
+pub struct GrayscalePacker {
+    format: PackedPixelFormat,
+    dithering: Dithering,
+    /// The errors diffused to the next lines, one per range, as the lines of several
+    /// rectangles can be packed alternately
+    diffused_errors: Vec<DiffusedError>,
+    /// The error diffused to the line after, while the line is packed
+    next_errors: Vec<i16>,
+}
+
+impl GrayscalePacker {
+    /// Create a new packer
+    pub fn new(format: PackedPixelFormat, dithering: Dithering) -> Self {
+        Self { format, dithering, diffused_errors: Vec::new(), next_errors: Vec::new() }
+    }
+
+    /// Quantize the `pixels` of the line `line`, starting at the x coordinate `x`, and pack them
+    /// in `buffer_line`, which is the whole line of the packed buffer.
+    /// The other pixels of the buffer line are not modified.
+    pub fn pack_line(
+        &mut self,
+        line: usize,
+        x: usize,
+        pixels: &[Gray8Pixel],
+        buffer_line: &mut [u8],
+    ) {
+        let max = self.format.max_value();
+        let bpp = self.format.bits_per_pixel();
+        let mut set = |x: usize, value: u16| {
+            let shift = 8 - bpp - (x * bpp) % 8;
+            let byte = &mut buffer_line[x * bpp / 8];
+            *byte = (*byte & !((max as u8) << shift)) | ((value as u8) << shift);
+        };
+        match self.dithering {
+            Dithering::None => {
+                for (i, p) in pixels.iter().enumerate() {
+                    set(x + i, (p.0 as u16 * max + 127) / 255);
+                }
+            }
+            Dithering::Ordered => {
+                let row = &BAYER_MATRIX[line % 4];
+                for (i, p) in pixels.iter().enumerate() {
+                    let threshold = (row[(x + i) % 4] as u16 * 2 + 1) * 255 / 32;
+                    set(x + i, (p.0 as u16 * max + threshold) / 255);
+                }
+            }
+            Dithering::ErrorDiffusion => {
+                let range = x..x + pixels.len();
+                // Forget the errors of the ranges whose lines were not continued
+                self.diffused_errors.retain(|e| e.line >= line);
+                let diffused = match self.diffused_errors.iter_mut().position(|e| e.range == range)
+                {
+                    Some(index) => &mut self.diffused_errors[index],
+                    None => {
+                        self.diffused_errors.push(DiffusedError {
+                            range,
+                            line,
+                            errors: Vec::new(),
+                        });
+                        self.diffused_errors.last_mut().unwrap()
+                    }
+                };
+                if diffused.line != line {
+                    // Not the continuation of the previous line: there is no error to diffuse
+                    diffused.errors.clear();
+                }
+                diffused.errors.resize(pixels.len() + 2, 0);
+                diffused.line = line + 1;
+                let errors = &mut diffused.errors;
+                self.next_errors.clear();
+                self.next_errors.resize(pixels.len() + 2, 0);
+                // errors[i + 1] is the error diffused to the pixel i. The errors diffused out
+                // of the range are lost.
+                let mut right_error = 0;
+                for (i, p) in pixels.iter().enumerate() {
+                    let value = (p.0 as i16 + errors[i + 1] + right_error).clamp(0, 255);
+                    let quantized = (value as u16 * max + 127) / 255;
+                    set(x + i, quantized);
+                    let error = value - (quantized * 255 / max) as i16;
+                    right_error = error * 7 / 16;
+                    self.next_errors[i] += error * 3 / 16;
+                    self.next_errors[i + 1] += error * 5 / 16;
+                    self.next_errors[i + 2] += error / 16;
+                }
+                core::mem::swap(errors, &mut self.next_errors);
+            }
+        }
+    }
+
+    /// Pack the `region` of the `pixels`, which have been rendered for example with
+    /// [`SoftwareRenderer::render()`](super::SoftwareRenderer::render()), in the `buffer`.
+    ///
+    /// `pixel_stride` is the number of pixels between two lines in `pixels`, and
+    /// `byte_stride` the number of bytes between two lines in `buffer`.
+    pub fn pack_region(
+        &mut self,
+        region: &PhysicalRegion,
+        pixels: &[Gray8Pixel],
+        pixel_stride: usize,
+        buffer: &mut [u8],
+        byte_stride: usize,
+    ) {
+        for rect in region.iter_rects() {
+            let x_range = rect.min_x() as usize..rect.max_x() as usize;
+            for line in rect.min_y() as usize..rect.max_y() as usize {
+                self.pack_line(
+                    line,
+                    x_range.start,
+                    &pixels[line * pixel_stride..][x_range.clone()],
+                    &mut buffer[line * byte_stride..][..byte_stride],
+                );
+            }
+        }
+    }
+}
+
+/// A [`LineBufferProvider`] that renders in a buffer of a [`PackedPixelFormat`].
+///
+/// ```rust
+/// # use i_slint_core::software_renderer::*;
+/// # fn xxx(renderer: &SoftwareRenderer, frame_buffer: &mut [u8], display_width: usize) {
+/// let byte_stride = PackedPixelFormat::Mono1.bytes_per_line(display_width);
+/// renderer.render_by_line(PackedLineBuffer::new(
+///     frame_buffer,
+///     byte_stride,
+///     GrayscalePacker::new(PackedPixelFormat::Mono1, Dithering::Ordered),
+/// ));
+/// # }
+/// ```
+pub struct PackedLineBuffer<'a> {
+    buffer: &'a mut [u8],
+    byte_stride: usize,
+    packer: GrayscalePacker,
+    line_buffer: Vec<Gray8Pixel>,
+}
+
+impl<'a> PackedLineBuffer<'a> {
+    /// Create a line buffer provider that packs the lines in `buffer`, which has `byte_stride`
+    /// bytes between two lines.
+    pub fn new(buffer: &'a mut [u8], byte_stride: usize, packer: GrayscalePacker) -> Self {
+        Self { buffer, byte_stride, packer, line_buffer: Vec::new() }
+    }
+}
+
+impl<'a> LineBufferProvider for PackedLineBuffer<'a> {
+    type TargetPixel = Gray8Pixel;
+
+    fn process_line(
+        &mut self,
+        line: usize,
+        range: core::ops::Range<usize>,
+        render_fn: impl FnOnce(&mut [Self::TargetPixel]),
+    ) {
+        self.line_buffer.clear();
+        self.line_buffer.resize(range.len(), Gray8Pixel::default());
+        render_fn(&mut self.line_buffer);
+        self.packer.pack_line(
+            line,
+            range.start,
+            &self.line_buffer,
+            &mut self.buffer[line * self.byte_stride..][..self.byte_stride],
+        );
+    }
+}
+
+#[test]
+fn pack_gray4() {
+    let mut packer = GrayscalePacker::new(PackedPixelFormat::Gray4, Dithering::None);
+    let mut buffer = [0x12, 0x34];
+    packer.pack_line(0, 1, &[Gray8Pixel(0xff), Gray8Pixel(0x88)], &mut buffer);
+    assert_eq!(buffer, [0x1f, 0x84]);
+}
+
+#[test]
+fn pack_mono1() {
+    let mut packer = GrayscalePacker::new(PackedPixelFormat::Mono1, Dithering::None);
+    let mut buffer = [0b1000_0001, 0];
+    let pixels = [0, 0xff, 0x7f, 0x80, 0xff, 0, 0, 0, 0xff].map(Gray8Pixel);
+    packer.pack_line(0, 0, &pixels, &mut buffer);
+    assert_eq!(buffer, [0b0101_1000, 0b1000_0000]);
+    packer.pack_line(0, 10, &[Gray8Pixel(0xff)], &mut buffer);
+    assert_eq!(buffer, [0b0101_1000, 0b1010_0000]);
+}
+
+#[test]
+fn dithering() {
+    // A mid gray should give half of the pixels white, for both kind of dithering
+    for dithering in [Dithering::Ordered, Dithering::ErrorDiffusion] {
+        let mut packer = GrayscalePacker::new(PackedPixelFormat::Mono1, dithering);
+        let mut buffer = [0u8; 4 * 8];
+        for line in 0..8 {
+            packer.pack_line(line, 0, &[Gray8Pixel(0x80); 32], &mut buffer[line * 4..][..4]);
+        }
+        let white = buffer.iter().map(|b| b.count_ones()).sum::<u32>();
+        assert!((120..=136).contains(&white), "{dithering:?}: {white}");
+    }
+
+    // Black and white are not dithered
+    for value in [0, 0xff] {
+        let mut packer = GrayscalePacker::new(PackedPixelFormat::Gray4, Dithering::Ordered);
+        let mut buffer = [0u8; 8];
+        packer.pack_line(0, 0, &[Gray8Pixel(value); 16], &mut buffer);
+        assert_eq!(buffer, [value; 8]);
+    }
+}
+
+#[test]
+fn error_diffusion_of_interleaved_ranges() {
+    // Packing the lines of two rectangles alternately, like `render_by_line` does when they
+    // share lines, must give the same result as packing each rectangle on its own
+    let width = 24;
+    let pixels: Vec<Gray8Pixel> =
+        (0..width * 6).map(|i| Gray8Pixel((i * 37 % 256) as u8)).collect();
+    let ranges = [2..9, 13..22];
+    let pack = |interleaved: bool| {
+        let mut packer = GrayscalePacker::new(PackedPixelFormat::Gray4, Dithering::ErrorDiffusion);
+        let mut buffer = [0u8; 12 * 6];
+        let mut pack_line = |line: usize, range: &core::ops::Range<usize>| {
+            packer.pack_line(
+                line,
+                range.start,
+                &pixels[line * width..][range.clone()],
+                &mut buffer[line * 12..][..12],
+            )
+        };
+        if interleaved {
+            for line in 0..6 {
+                for range in &ranges {
+                    pack_line(line, range);
+                }
+            }
+        } else {
+            for range in &ranges {
+                for line in 0..6 {
+                    pack_line(line, range);
+                }
+            }
+        }
+        buffer
+    };
+    assert_eq!(pack(true), pack(false));
+}
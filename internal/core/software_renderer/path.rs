@@ -0,0 +1,343 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! This module contains the rasterization of the paths into alpha maps.
+//!
+//! The paths are flattened into polylines, which are turned into an [`Outline`]: a set of edges
+//! that is then scanned line by line to compute the anti-aliased coverage of each pixel.
+
+use super::PhysicalRect;
+use crate::items::FillRule;
+use crate::lengths::PhysicalPx;
+use alloc::vec::Vec;
+#[allow(unused)]
+use num_traits::Float;
+
+type Point = euclid::Point2D<f32, PhysicalPx>;
+type Vector = euclid::Vector2D<f32, PhysicalPx>;
+
+/// Number of sub-scanlines sampled for each line of pixels. The horizontal coverage is computed
+/// exactly, so this only affects the anti-aliasing of the edges that are almost horizontal.
+const SUB_SCANLINES: usize = 16;
+
+/// A flattened sub-path, in physical pixels
+#[derive(Default, Debug)]
+pub(super) struct Polyline {
+    pub points: Vec<Point>,
+    /// True if the sub-path ends with a close command
+    pub closed: bool,
+}
+
+/// An edge of the outline, oriented from top to bottom
+#[derive(Clone, Copy, Debug)]
+struct Edge {
+    top: Point,
+    bottom: Point,
+    /// +1 if the edge goes down in the original outline, -1 if it goes up
+    winding: i32,
+}
+
+impl Edge {
+    fn x_at(&self, y: f32) -> f32 {
+        self.top.x + (y - self.top.y) * (self.bottom.x - self.top.x) / (self.bottom.y - self.top.y)
+    }
+}
+
+/// How the segments of a stroke are joined
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum LineJoin {
+    /// The outer edges are extended until they meet. If the length of the miter, relative to
+    /// the width of the line, is more than the limit, the join is beveled instead.
+    Miter { limit: f32 },
+    /// The outer corners of the segments are connected by a straight line
+    #[allow(dead_code)] // The Path element has no property to select the joins yet
+    Bevel,
+    /// The segments are joined by a circular arc
+    Round,
+}
+
+/// The closed outline of a shape, to be rasterized with a fill rule
+#[derive(Default, Debug)]
+pub(super) struct Outline {
+    edges: Vec<Edge>,
+}
+
+impl Outline {
+    /// The outline of the area enclosed by the polylines. Open polylines are implicitly closed.
+    pub fn fill(polylines: &[Polyline]) -> Self {
+        let mut outline = Self::default();
+        for polyline in polylines {
+            outline.add_polygon(&polyline.points);
+        }
+        outline
+    }
+
+    /// The outline of a line of the given width along the polylines, with the given joins and
+    /// butt caps. It must be rasterized with the non-zero fill rule.
+    pub fn stroke(polylines: &[Polyline], width: f32, join: LineJoin) -> Self {
+        let half_width = width / 2.;
+        let mut outline = Self::default();
+        for polyline in polylines {
+            let mut points = polyline.points.clone();
+            points.dedup_by(|a, b| (*a - *b).square_length() < 1e-6);
+            if polyline.closed
+                && points.len() > 1
+                && (points[0] - points[points.len() - 1]).square_length() < 1e-6
+            {
+                points.pop();
+            }
+            let count = points.len();
+            if count < 2 {
+                continue;
+            }
+
+            let segment_count = if polyline.closed { count } else { count - 1 };
+            for i in 0..segment_count {
+                let (from, to) = (points[i], points[(i + 1) % count]);
+                let normal =
+                    (to - from).normalize().yx().component_mul(Vector::new(-1., 1.)) * half_width;
+                outline.add_polygon(&[from + normal, to + normal, to - normal, from - normal]);
+            }
+
+            let joins = if polyline.closed { 0..count } else { 1..count - 1 };
+            for i in joins {
+                let prev = points[(i + count - 1) % count];
+                let (current, next) = (points[i], points[(i + 1) % count]);
+                let (d1, d2) = ((current - prev).normalize(), (next - current).normalize());
+                // Skip the joins where the gap between the two segments is not visible
+                if d1.dot(d2) > 0. && d1.cross(d2).abs() * half_width < 0.05 {
+                    continue;
+                }
+                if join == LineJoin::Round {
+                    outline.add_circle(current, half_width);
+                    continue;
+                }
+                // The corners of the two segments on the outer side of the turn
+                let side = if d1.cross(d2) > 0. { -half_width } else { half_width };
+                let outer1 = current + d1.yx().component_mul(Vector::new(-1., 1.)) * side;
+                let outer2 = current + d2.yx().component_mul(Vector::new(-1., 1.)) * side;
+                // The ratio between the length of the miter and the width of the line
+                let miter_ratio = 1. / ((1. + d1.dot(d2)) / 2.).sqrt();
+                match join {
+                    LineJoin::Miter { limit } if miter_ratio <= limit => {
+                        let tip = current
+                            + ((outer1 - current) + (outer2 - current)).normalize()
+                                * half_width
+                                * miter_ratio;
+                        outline.add_oriented_polygon(&[current, outer1, tip, outer2]);
+                    }
+                    _ => outline.add_oriented_polygon(&[current, outer1, outer2]),
+                }
+            }
+        }
+        outline
+    }
+
+    fn add_line(&mut self, from: Point, to: Point) {
+        // Horizontal edges never cross a scanline
+        if from.y == to.y {
+            return;
+        }
+        self.edges.push(if from.y < to.y {
+            Edge { top: from, bottom: to, winding: 1 }
+        } else {
+            Edge { top: to, bottom: from, winding: -1 }
+        });
+    }
+
+    fn add_polygon(&mut self, points: &[Point]) {
+        for (i, p) in points.iter().enumerate() {
+            self.add_line(*p, points[(i + 1) % points.len()]);
+        }
+    }
+
+    /// Add the polygon with the same orientation as the quads of the stroke, so that the
+    /// non-zero fill rule makes the union of them
+    fn add_oriented_polygon(&mut self, points: &[Point]) {
+        let area: f32 = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| p.to_vector().cross(points[(i + 1) % points.len()].to_vector()))
+            .sum();
+        if area > 0. {
+            self.add_polygon(&points.iter().rev().copied().collect::<Vec<_>>());
+        } else {
+            self.add_polygon(points);
+        }
+    }
+
+    /// Add a circle with the same orientation as the quads of the stroke, so that the non-zero
+    /// fill rule makes the union of them
+    fn add_circle(&mut self, center: Point, radius: f32) {
+        let count = ((radius * 4.) as usize).clamp(8, 64);
+        let points = (0..count)
+            .map(|i| {
+                let angle = -(i as f32) * core::f32::consts::TAU / count as f32;
+                center + Vector::new(angle.cos(), angle.sin()) * radius
+            })
+            .collect::<Vec<_>>();
+        self.add_polygon(&points);
+    }
+
+    /// The bounding box of the outline, or None if the outline is empty
+    pub fn bounding_box(&self) -> Option<euclid::Box2D<f32, PhysicalPx>> {
+        let mut it = self.edges.iter().flat_map(|e| [e.top, e.bottom]);
+        let first = it.next()?;
+        Some(it.fold(euclid::Box2D::new(first, first), |b, p| {
+            euclid::Box2D::new(b.min.min(p), b.max.max(p))
+        }))
+    }
+
+    /// Compute the coverage of each pixel of `rect` by the shape. The result is an alpha map
+    /// of `rect.width() * rect.height()` bytes.
+    pub fn rasterize(&self, rect: PhysicalRect, fill_rule: FillRule) -> Vec<u8> {
+        let width = rect.width().max(0) as usize;
+        let height = rect.height().max(0) as usize;
+        let mut result = alloc::vec![0u8; width * height];
+
+        let origin = rect.origin.cast::<f32>().to_vector();
+        let mut edges = self
+            .edges
+            .iter()
+            .map(|e| Edge { top: e.top - origin, bottom: e.bottom - origin, winding: e.winding })
+            .filter(|e| e.bottom.y > 0. && e.top.y < height as f32)
+            .collect::<Vec<_>>();
+        edges.sort_unstable_by(|a, b| a.top.y.total_cmp(&b.top.y));
+
+        let is_inside = |winding: i32| match fill_rule {
+            FillRule::Nonzero => winding != 0,
+            FillRule::Evenodd => winding % 2 != 0,
+        };
+
+        let weight = 1. / SUB_SCANLINES as f32;
+        let mut next_edge = 0;
+        let mut active_edges = Vec::new();
+        let mut crossings = Vec::new();
+        // The coverage of the pixels at the ends of the spans, and the difference of coverage
+        // with the previous pixel for the pixels within the spans
+        let mut coverage = alloc::vec![0f32; width + 1];
+        let mut deltas = alloc::vec![0f32; width + 1];
+
+        for (y, row) in result.chunks_exact_mut(width.max(1)).enumerate().take(height) {
+            for sub_scanline in 0..SUB_SCANLINES {
+                let sample_y = y as f32 + (sub_scanline as f32 + 0.5) * weight;
+                while edges.get(next_edge).map_or(false, |e| e.top.y <= sample_y) {
+                    active_edges.push(edges[next_edge]);
+                    next_edge += 1;
+                }
+                active_edges.retain(|e| e.bottom.y > sample_y);
+
+                crossings.clear();
+                crossings.extend(
+                    active_edges
+                        .iter()
+                        .map(|e| (e.x_at(sample_y).clamp(0., width as f32), e.winding)),
+                );
+                crossings.sort_unstable_by(|a: &(f32, i32), b| a.0.total_cmp(&b.0));
+
+                let mut winding = 0;
+                let mut span_start = 0.;
+                for (x, w) in crossings.iter().copied() {
+                    let was_inside = is_inside(winding);
+                    winding += w;
+                    match (was_inside, is_inside(winding)) {
+                        (false, true) => span_start = x,
+                        (true, false) => {
+                            add_span(&mut coverage, &mut deltas, span_start, x, weight)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            let mut accumulated = 0.;
+            for (x, pixel) in row.iter_mut().enumerate() {
+                accumulated += deltas[x];
+                *pixel = ((accumulated + coverage[x]).clamp(0., 1.) * 255. + 0.5) as u8;
+            }
+            coverage.fill(0.);
+            deltas.fill(0.);
+        }
+        result
+    }
+}
+
+/// Add the coverage of a span of a sub-scanline between `start` and `end`
+fn add_span(coverage: &mut [f32], deltas: &mut [f32], start: f32, end: f32, weight: f32) {
+    if end <= start {
+        return;
+    }
+    let (first, last) = (start as usize, end as usize);
+    if first == last {
+        coverage[first] += (end - start) * weight;
+    } else {
+        coverage[first] += (first as f32 + 1. - start) * weight;
+        deltas[first + 1] += weight;
+        deltas[last] -= weight;
+        coverage[last] += (end - last as f32) * weight;
+    }
+}
+
+#[cfg(test)]
+fn polyline(points: &[(f32, f32)], closed: bool) -> Polyline {
+    Polyline { points: points.iter().map(|(x, y)| Point::new(*x, *y)).collect(), closed }
+}
+
+#[test]
+fn fill_rules() {
+    // A square with a square hole in the same orientation
+    let outline = Outline::fill(&[
+        polyline(&[(0., 0.), (8., 0.), (8., 8.), (0., 8.)], true),
+        polyline(&[(2., 2.), (6., 2.), (6., 6.), (2., 6.)], true),
+    ]);
+    let rect = PhysicalRect::new(euclid::point2(0, 0), euclid::size2(8, 8));
+    let nonzero = outline.rasterize(rect, FillRule::Nonzero);
+    let evenodd = outline.rasterize(rect, FillRule::Evenodd);
+    assert_eq!(nonzero[0], 255);
+    assert_eq!(nonzero[4 * 8 + 4], 255);
+    assert_eq!(evenodd[0], 255);
+    assert_eq!(evenodd[4 * 8 + 4], 0);
+    assert_eq!(outline.bounding_box().unwrap().to_rect().round_out().cast(), rect);
+
+    // Anti-aliased edges, and the rectangle is offset
+    let outline = Outline::fill(&[polyline(&[(10.5, 10.), (12., 10.), (12., 11.25)], false)]);
+    let rect = PhysicalRect::new(euclid::point2(10, 10), euclid::size2(2, 2));
+    let alpha = outline.rasterize(rect, FillRule::Nonzero);
+    // The expected values are the area of the triangle in each pixel
+    let expected = [27, 203, 0, 10];
+    assert!(alpha.iter().zip(expected).all(|(a, e)| (*a as i32 - e).abs() <= 3), "{alpha:?}");
+}
+
+#[test]
+fn stroke() {
+    let square = || polyline(&[(2., 2.), (10., 2.), (10., 10.), (2., 10.)], true);
+    let outline = Outline::stroke(&[square()], 2., LineJoin::Round);
+    let rect = PhysicalRect::new(euclid::point2(0, 0), euclid::size2(12, 12));
+    let alpha = outline.rasterize(rect, FillRule::Nonzero);
+    let at = |x: usize, y: usize| alpha[y * 12 + x];
+    // On the lines
+    assert_eq!(at(5, 1), 255);
+    assert_eq!(at(5, 2), 255);
+    assert_eq!(at(10, 5), 255);
+    // Inside and outside
+    assert_eq!(at(5, 5), 0);
+    assert_eq!(at(5, 0), 0);
+    // The corners are joined
+    assert_eq!(at(1, 1), at(10, 10));
+    assert!(at(1, 1) > 160 && at(1, 1) < 230, "{}", at(1, 1));
+
+    // The corner pixel is fully covered by a miter join, and half covered by a bevel join
+    let corner =
+        |join| Outline::stroke(&[square()], 2., join).rasterize(rect, FillRule::Nonzero)[12 + 1];
+    assert_eq!(corner(LineJoin::Miter { limit: 4. }), 255);
+    assert!((corner(LineJoin::Bevel) as i32 - 128).abs() <= 3, "{}", corner(LineJoin::Bevel));
+    // The miter of a right angle is sqrt(2) times the width of the line
+    assert_eq!(corner(LineJoin::Miter { limit: 1.3 }), corner(LineJoin::Bevel));
+
+    // An open line has no joins at its ends
+    let line = polyline(&[(2., 6.), (10., 6.)], false);
+    let alpha = Outline::stroke(&[line], 2., LineJoin::Round).rasterize(rect, FillRule::Nonzero);
+    assert_eq!(alpha[5 * 12 + 1], 0);
+    assert_eq!(alpha[5 * 12 + 2], 255);
+    assert_eq!(alpha[5 * 12 + 10], 0);
+}
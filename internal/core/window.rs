@@ -13,7 +13,7 @@
 use crate::graphics::Point;
 use crate::input::{
     key_codes, ClickState, InternalKeyboardModifierState, KeyEvent, KeyEventType, MouseEvent,
-    MouseInputState, TextCursorBlinker,
+    MouseInputState, TextCursorBlinker, TouchEvent, TouchInputState,
 };
 use crate::item_tree::ItemRc;
 use crate::item_tree::{ItemTreeRc, ItemTreeRef, ItemTreeVTable, ItemTreeWeak};
@@ -132,6 +132,18 @@ fn request_redraw(&self) {}
     /// be called again.
     fn update_window_properties(&self, _properties: WindowProperties<'_>) {}
 
+    /// Re-implement this function to connect an input method, such as an on-screen keyboard, with
+    /// the focused text input field.
+    ///
+    /// The text input field requests the input method to be enabled when it receives the focus,
+    /// informs about changes of its text, cursor position and pre-edit text while it has the focus,
+    /// and requests the input method to be disabled when it loses the focus.
+    ///
+    /// The input method composes text using [`WindowEvent::InputMethodPreedit`](crate::platform::WindowEvent::InputMethodPreedit)
+    /// and inserts it using [`WindowEvent::InputMethodCommit`](crate::platform::WindowEvent::InputMethodCommit),
+    /// both dispatched with [`Window::dispatch_event()`].
+    fn input_method_request(&self, _request: InputMethodRequest) {}
+
     #[doc(hidden)]
     fn internal(&self, _: crate::InternalToken) -> Option<&dyn WindowAdapterInternal> {
         None
@@ -170,9 +182,6 @@ fn create_popup(&self, _geometry: LogicalRect) -> Option<Rc<dyn WindowAdapter>>
     // TODO: Make the enum public and make public
     fn set_mouse_cursor(&self, _cursor: MouseCursor) {}
 
-    /// This method allow editable input field to communicate with the platform about input methods
-    fn input_method_request(&self, _: InputMethodRequest) {}
-
     /// Return self as any so the backend can upcast
     // TODO: consider using the as_any crate, or deriving the traint from Any to provide a better default
     fn as_any(&self) -> &dyn core::any::Any {
@@ -189,7 +198,7 @@ fn dark_color_scheme(&self) -> bool {
     }
 }
 
-/// This is the parameter from [`WindowAdapterInternal::input_method_request()`] which lets the editable text input field
+/// This is the parameter from [`WindowAdapter::input_method_request()`] which lets the editable text input field
 /// communicate with the platform about input methods.
 #[non_exhaustive]
 #[derive(Debug, Clone)]
@@ -348,6 +357,7 @@ pub struct WindowInner {
     /// When the window is visible, keep a strong reference
     strong_component_ref: RefCell<Option<ItemTreeRc>>,
     mouse_input_state: Cell<MouseInputState>,
+    touch_input_state: Cell<TouchInputState>,
     pub(crate) modifiers: Cell<InternalKeyboardModifierState>,
 
     /// itemRC will retrieve on wasms
@@ -394,6 +404,7 @@ pub fn new(window_adapter_weak: Weak<dyn WindowAdapter>) -> Self {
             component: Default::default(),
             strong_component_ref: Default::default(),
             mouse_input_state: Default::default(),
+            touch_input_state: Default::default(),
             modifiers: Default::default(),
             pinned_fields: Box::pin(WindowPinnedFields {
                 redraw_tracker,
@@ -419,6 +430,7 @@ pub fn set_component(&self, component: &ItemTreeRc) {
         self.close_popup();
         self.focus_item.replace(Default::default());
         self.mouse_input_state.replace(Default::default());
+        self.touch_input_state.replace(Default::default());
         self.modifiers.replace(Default::default());
         self.component.replace(ItemTreeRc::downgrade(component));
         self.pinned_fields.window_properties_tracker.set_dirty(); // component changed, layout constraints for sure must be re-calculated
@@ -513,6 +525,20 @@ pub fn process_mouse_input(&self, mut event: MouseEvent) {
         }
     }
 
+    /// Receive a touch event for one of the fingers on the surface, and pass it to the items
+    /// of the component, either like a mouse event, or as part of a gesture.
+    pub fn process_touch_input(&self, event: TouchEvent) {
+        crate::animations::update_animations();
+
+        let Some(component) = self.try_component() else { return };
+        self.touch_input_state.set(crate::input::process_touch_input(
+            &component,
+            event,
+            self,
+            self.touch_input_state.take(),
+        ));
+    }
+
     /// Called by the input code's internal timer to send an event that was delayed
     pub(crate) fn process_delayed_event(&self) {
         self.mouse_input_state.set(crate::input::process_delayed_event(
@@ -527,7 +553,26 @@ pub(crate) fn process_delayed_event(&self) {
     /// Arguments:
     /// * `event`: The key event received by the windowing system.
     /// * `component`: The Slint compiled component that provides the tree of items.
-    pub fn process_key_input(&self, mut event: KeyEvent) {
+    pub fn process_key_input(&self, event: KeyEvent) {
+        self.process_key_input_impl(event, None)
+    }
+
+    /// Like [`Self::process_key_input`], but with the `modifiers` that the windowing system
+    /// reported with the event, which are used instead of the ones tracked from the key events
+    /// of the modifier keys.
+    pub fn process_key_input_with_platform_modifiers(
+        &self,
+        event: KeyEvent,
+        modifiers: crate::input::KeyboardModifiers,
+    ) {
+        self.process_key_input_impl(event, Some(modifiers))
+    }
+
+    fn process_key_input_impl(
+        &self,
+        mut event: KeyEvent,
+        platform_modifiers: Option<crate::input::KeyboardModifiers>,
+    ) {
         if let Some(updated_modifier) = self
             .modifiers
             .get()
@@ -537,7 +582,7 @@ pub fn process_key_input(&self, mut event: KeyEvent) {
             self.modifiers.set(updated_modifier);
         }
 
-        event.modifiers = self.modifiers.get().into();
+        event.modifiers = platform_modifiers.unwrap_or_else(|| self.modifiers.get().into());
 
         let mut item = self.focus_item.borrow().clone().upgrade();
         while let Some(focus_item) = item {
@@ -876,7 +921,7 @@ pub fn close_popup(&self) {
 
                 if !popup_region.is_empty() {
                     let window_adapter = self.window_adapter();
-                    window_adapter.renderer().mark_dirty_region(popup_region.to_box2d());
+                    window_adapter.renderer().mark_dirty_region(popup_region.to_box2d().into());
                     window_adapter.request_redraw();
                 }
             }
@@ -1310,6 +1355,58 @@ fn call(&self) -> CloseRequestResponse {
         });
     }
 
+    /// Dispatch a key pressed or release event with the modifiers and physical key reported by
+    /// the platform
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_dispatch_key_event_with_details(
+        handle: *const WindowAdapterRcOpaque,
+        event_type: crate::input::KeyEventType,
+        text: &SharedString,
+        modifiers: crate::input::KeyboardModifiers,
+        physical_key: &SharedString,
+        repeat: bool,
+    ) {
+        let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
+        window_adapter.window().0.process_key_input_with_platform_modifiers(
+            crate::items::KeyEvent {
+                text: text.clone(),
+                physical_key: physical_key.clone(),
+                repeat,
+                event_type,
+                ..Default::default()
+            },
+            modifiers,
+        );
+    }
+
+    /// Dispatch an event of the input method that changes the pre-edit text
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_dispatch_input_method_preedit_event(
+        handle: *const WindowAdapterRcOpaque,
+        text: &SharedString,
+        cursor_start: usize,
+        cursor_end: usize,
+    ) {
+        let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
+        window_adapter.window().dispatch_event(crate::platform::WindowEvent::InputMethodPreedit {
+            text: text.clone(),
+            cursor_start,
+            cursor_end,
+        });
+    }
+
+    /// Dispatch an event of the input method that commits text
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_dispatch_input_method_commit_event(
+        handle: *const WindowAdapterRcOpaque,
+        text: &SharedString,
+    ) {
+        let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
+        window_adapter
+            .window()
+            .dispatch_event(crate::platform::WindowEvent::InputMethodCommit { text: text.clone() });
+    }
+
     /// Dispatch a mouse event
     #[no_mangle]
     pub unsafe extern "C" fn slint_windowrc_dispatch_pointer_event(
@@ -97,6 +97,23 @@ fn main() -> std::io::Result<()> {
                 })
         });
 
+        // An additional region that is rendered line by line, for example to clip the items at
+        // odd positions
+        let needle = "BY_LINE_REGION=";
+        let by_line_region = source.find(needle).map_or(String::new(), |p| {
+            let [x, y, width, height]: [u32; 4] = source[p + needle.len()..]
+                .split_whitespace()
+                .next()
+                .and_then(|region| {
+                    region.split(',').map(|v| v.parse().ok()).collect::<Option<Vec<_>>>()
+                })
+                .and_then(|region| region.try_into().ok())
+                .unwrap_or_else(|| {
+                    panic!("Cannot parse {needle} for {}", testcase.relative_path.display())
+                });
+            format!("testing::assert_with_render_by_line_in_region(screenshot, window.clone(), i_slint_core::graphics::euclid::rect({x}, {y}, {width}, {height}), &options);")
+        });
+
         let mut output = std::fs::File::create(
             Path::new(&std::env::var_os("OUT_DIR").unwrap()).join(format!("{}.rs", module_name)),
         )?;
@@ -121,6 +138,9 @@ fn main() -> std::io::Result<()> {
     testing::assert_with_render(screenshot, window.clone(), &options);
 
     testing::assert_with_render_by_line(screenshot, window.clone(), &options);
+    {by_line_region}
+
+    testing::assert_with_render_with_threads(screenshot, window.clone(), &options);
 
     Ok(())
     }}",
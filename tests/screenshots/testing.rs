@@ -13,6 +13,7 @@
         IntRect, Rgb8Pixel, SharedPixelBuffer,
     },
     item_rendering::DirtyRegion,
+    lengths::LogicalPx,
     platform::PlatformError,
     renderer::RendererSealed,
     software_renderer::{LineBufferProvider, MinimalSoftwareWindow, WindowRotation},
@@ -74,10 +75,9 @@ pub fn screenshot(
     // render to buffer
     window.request_redraw();
     window.draw_if_needed(|renderer| {
-        renderer.mark_dirty_region(Box2D::new(
-            Point2D::new(0., 0.),
-            Point2D::new(width as f32, height as f32),
-        ));
+        renderer.mark_dirty_region(
+            Box2D::new(Point2D::new(0., 0.), Point2D::new(width as f32, height as f32)).into(),
+        );
         renderer.set_window_rotation(rotated);
         renderer.render(buffer.make_mut_slice(), width as usize);
         renderer.set_window_rotation(WindowRotation::NoRotation);
@@ -273,7 +273,31 @@ pub fn assert_with_render_by_line(
     }
 
     // Try to render a clipped version (to simulate partial rendering) and it should be exactly the same
-    let region = euclid::rect(s.width / 4, s.height / 4, s.width / 2, s.height / 2).cast::<usize>();
+    let region = euclid::rect(s.width / 4, s.height / 4, s.width / 2, s.height / 2);
+    assert_partial_render_by_line(path, window, region, &mut rendering, options);
+}
+
+/// Like [`assert_with_render_by_line`], but the partial rendering is done in the given `region`
+pub fn assert_with_render_by_line_in_region(
+    path: &str,
+    window: Rc<MinimalSoftwareWindow>,
+    region: euclid::default::Rect<u32>,
+    options: &TestCaseOptions,
+) {
+    let mut rendering = screenshot(window.clone(), WindowRotation::NoRotation);
+    assert_partial_render_by_line(path, window, region, &mut rendering, options);
+}
+
+/// Render only the `region` line by line, over the previous `rendering` of the window, and
+/// check that the result is still the same.
+fn assert_partial_render_by_line(
+    path: &str,
+    window: Rc<MinimalSoftwareWindow>,
+    region: euclid::default::Rect<u32>,
+    rendering: &mut SharedPixelBuffer<Rgb8Pixel>,
+    options: &TestCaseOptions,
+) {
+    let region = region.cast::<usize>();
     for y in region.y_range() {
         let stride = rendering.width() as usize;
         // fill with garbage
@@ -283,9 +307,30 @@ pub fn assert_with_render_by_line(
             255,
         ));
     }
-    screenshot_render_by_line(window, Some(region.cast()), &mut rendering);
+    screenshot_render_by_line(window, Some(region.cast()), rendering);
+    if let Err(reason) = compare_images(path, rendering, WindowRotation::NoRotation, options) {
+        panic!("Partial rendering image comparison failure for line-by-line rendering for {path} in {region:?}: {reason}");
+    }
+}
+
+pub fn assert_with_render_with_threads(
+    path: &str,
+    window: Rc<MinimalSoftwareWindow>,
+    options: &TestCaseOptions,
+) {
+    let s = window.size();
+    let mut rendering = SharedPixelBuffer::<Rgb8Pixel>::new(s.width, s.height);
+
+    // An odd number of threads, so that the bands don't all have the same height
+    window.request_redraw();
+    window.draw_if_needed(|renderer| {
+        renderer.mark_dirty_region(
+            Box2D::new(Point2D::new(0., 0.), Point2D::new(s.width as f32, s.height as f32)).into(),
+        );
+        renderer.render_with_threads(rendering.make_mut_slice(), s.width as usize, 3);
+    });
     if let Err(reason) = compare_images(path, &rendering, WindowRotation::NoRotation, options) {
-        panic!("Partial rendering image comparison failure for line-by-line rendering for {path}: {reason}");
+        panic!("Image comparison failure for rendering with threads for {path}: {reason}");
     }
 }
 
@@ -299,13 +344,16 @@ pub fn screenshot_render_by_line(
 
     window.draw_if_needed(|renderer| {
         match region {
-            None => renderer.mark_dirty_region(Box2D::new(
-                euclid::point2(0., 0.),
-                euclid::point2(buffer.width() as f32, buffer.height() as f32),
-            )),
-            Some(r) => renderer.mark_dirty_region(
-                DirtyRegion::from_untyped(&r.to_box2d().cast()) / window.scale_factor(),
+            None => renderer.mark_dirty_region(
+                Box2D::new(
+                    euclid::point2(0., 0.),
+                    euclid::point2(buffer.width() as f32, buffer.height() as f32),
+                )
+                .into(),
             ),
+            Some(r) => renderer.mark_dirty_region(DirtyRegion::from(
+                Box2D::<f32, LogicalPx>::from_untyped(&r.to_box2d().cast()) / window.scale_factor(),
+            )),
         }
         renderer.render_by_line(TestingLineBuffer {
             stride: buffer.width() as usize,
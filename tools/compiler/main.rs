@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
 
 use clap::{Parser, ValueEnum};
-use i_slint_compiler::diagnostics::BuildDiagnostics;
+use i_slint_compiler::diagnostics::{BuildDiagnostics, DiagnosticFormat};
 use i_slint_compiler::*;
 use itertools::Itertools;
 use std::io::Write;
@@ -62,6 +62,10 @@ struct Cli {
     /// Translation domain
     #[arg(long = "translation-domain", action)]
     translation_domain: Option<String>,
+
+    /// Set the format of the diagnostics printed on stderr ('human' or 'json')
+    #[arg(long = "diagnostic-format", default_value = "human", action)]
+    diagnostic_format: DiagnosticFormat,
 }
 
 fn main() -> std::io::Result<()> {
@@ -71,7 +75,7 @@ fn main() -> std::io::Result<()> {
     let syntax_node = parser::parse_file(&args.path, &mut diag);
     //println!("{:#?}", syntax_node);
     if diag.has_error() {
-        diag.print();
+        diag.print_with_format(args.diagnostic_format);
         std::process::exit(-1);
     }
     let mut compiler_config = CompilerConfiguration::new(args.format);
@@ -99,7 +103,7 @@ fn main() -> std::io::Result<()> {
     let syntax_node = syntax_node.expect("diags contained no compilation errors");
     let (doc, diag) = spin_on::spin_on(compile_syntax_node(syntax_node, diag, compiler_config));
 
-    let diag = diag.check_and_exit_on_error();
+    let diag = diag.check_and_exit_on_error_with_format(args.diagnostic_format);
 
     if args.output == std::path::Path::new("-") {
         generator::generate(args.format, &mut std::io::stdout(), &doc)?;
@@ -125,6 +129,6 @@ fn main() -> std::io::Result<()> {
 
         writeln!(f)?;
     }
-    diag.print_warnings_and_exit_on_error();
+    diag.print_warnings_and_exit_on_error_with_format(args.diagnostic_format);
     Ok(())
 }
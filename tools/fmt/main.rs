@@ -11,8 +11,8 @@
     Some code in this main.rs file is duplicated with the slint-updater, i guess it could
     be refactored in a separate utility crate or module or something.
 
-    The [`writer::TokenWriter`] trait is meant to be able to support the LSP later as the
-    LSP wants just the edits, not the full file
+    The formatter itself lives in `i_slint_compiler::fmt` and is shared with the LSP, which
+    implements the [`writer::TokenWriter`] trait to only produce the edits, not the full file
 */
 
 use i_slint_compiler::diagnostics::BuildDiagnostics;
@@ -22,8 +22,7 @@
 
 use clap::Parser;
 
-mod fmt;
-mod writer;
+use i_slint_compiler::fmt::{self, writer};
 
 #[derive(clap::Parser)]
 #[command(author, version, about, long_about = None)]
@@ -0,0 +1,115 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+/*!
+    Check .slint files with the lint rules of the compiler.
+    ```sh
+        cargo run --bin slint-lint -- -W magic-color -A unused-import some_file.slint
+    ```
+
+    The same rules are used by the language server.
+*/
+
+use clap::Parser;
+use i_slint_compiler::diagnostics::BuildDiagnostics;
+use i_slint_compiler::lint::{LintConfig, LintLevel, RULES};
+use i_slint_compiler::typeloader::TypeLoader;
+use i_slint_compiler::typeregister::TypeRegister;
+use i_slint_compiler::CompilerConfiguration;
+
+#[derive(clap::Parser)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[arg(name = "path to .slint file(s)", action)]
+    paths: Vec<std::path::PathBuf>,
+
+    /// Include path for other .slint files
+    #[arg(short = 'I', name = "include path", number_of_values = 1, action)]
+    include_paths: Vec<std::path::PathBuf>,
+
+    /// The argument should be in the format `<library>=<path>` specifying the
+    /// name of the library and the path to the library directory or a .slint
+    /// entry-point file.
+    #[arg(short = 'L', name = "library path", number_of_values = 1, action)]
+    library_paths: Vec<String>,
+
+    /// The style name ('native' or 'fluent')
+    #[arg(long, name = "style name", action)]
+    style: Option<String>,
+
+    /// Do not check the rule
+    #[arg(short = 'A', long = "allow", name = "rule to allow", number_of_values = 1, action)]
+    allow: Vec<String>,
+
+    /// Report violations of the rule as warnings
+    #[arg(short = 'W', long = "warn", name = "rule to warn", number_of_values = 1, action)]
+    warn: Vec<String>,
+
+    /// Report violations of the rule as errors
+    #[arg(short = 'D', long = "deny", name = "rule to deny", number_of_values = 1, action)]
+    deny: Vec<String>,
+
+    /// List the rules and their default level
+    #[arg(long, action)]
+    list: bool,
+}
+
+fn main() {
+    let args = Cli::parse();
+
+    if args.list {
+        for rule in RULES {
+            let level = match rule.default_level {
+                LintLevel::Allow => "allow",
+                LintLevel::Warn => "warn",
+                LintLevel::Deny => "deny",
+            };
+            println!("{:<26} {:<6} {}", rule.name, level, rule.description);
+        }
+        return;
+    }
+
+    let mut lint_config = LintConfig::default();
+    for (rules, level) in [
+        (&args.allow, LintLevel::Allow),
+        (&args.warn, LintLevel::Warn),
+        (&args.deny, LintLevel::Deny),
+    ] {
+        for rule in rules {
+            if let Err(e) = lint_config.set_level(rule, level) {
+                eprintln!("{e}");
+                std::process::exit(-1);
+            }
+        }
+    }
+
+    let mut compiler_config =
+        CompilerConfiguration::new(i_slint_compiler::generator::OutputFormat::Interpreter);
+    compiler_config.include_paths = args.include_paths;
+    compiler_config.library_paths = args
+        .library_paths
+        .iter()
+        .filter_map(|entry| entry.split_once('=').map(|(k, v)| (k.into(), v.into())))
+        .collect();
+    if let Some(style) = args.style {
+        compiler_config.style = Some(style);
+    }
+
+    let mut diag = BuildDiagnostics::default();
+    let mut loader = TypeLoader::new(TypeRegister::builtin(), compiler_config, &mut diag);
+    for path in &args.paths {
+        let path = i_slint_compiler::pathutils::clean_path(path);
+        let source = match std::fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("Could not read {}: {e}", path.display());
+                std::process::exit(-1);
+            }
+        };
+        spin_on::spin_on(loader.load_file(&path, &path, source, false, &mut diag));
+        if let Some(doc) = loader.get_document(&path) {
+            i_slint_compiler::lint::lint_document(doc, &lint_config, &mut diag);
+        }
+    }
+    diag.print_warnings_and_exit_on_error();
+}
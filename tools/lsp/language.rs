@@ -3,16 +3,26 @@
 
 // cSpell: ignore descr rfind unindented
 
+mod call_hierarchy;
 mod completion;
+mod diagnostics;
+mod document_links;
+mod folding;
+mod formatting;
 mod goto;
+mod hover;
+mod inlay_hints;
 mod properties;
+mod references;
 mod semantic_tokens;
+mod signature_help;
 #[cfg(test)]
 mod test;
+mod type_hierarchy;
 
 use crate::common::{PreviewApi, Result};
 use crate::language::properties::find_element_indent;
-use crate::util::{map_node, map_range, map_token, to_lsp_diag};
+use crate::util::{map_node, map_node_and_url, map_range, map_token, to_lsp_diag};
 
 #[cfg(target_arch = "wasm32")]
 use crate::wasm_prelude::*;
@@ -24,17 +34,27 @@
 use i_slint_compiler::{diagnostics::BuildDiagnostics, langtype::Type};
 use i_slint_compiler::{typeloader::TypeLoader, typeregister::TypeRegister};
 use lsp_types::request::{
+    CallHierarchyIncomingCalls, CallHierarchyOutgoingCalls, CallHierarchyPrepare,
     CodeActionRequest, CodeLensRequest, ColorPresentationRequest, Completion, DocumentColor,
-    DocumentHighlightRequest, DocumentSymbolRequest, ExecuteCommand, GotoDefinition, HoverRequest,
-    PrepareRenameRequest, Rename, SemanticTokensFullRequest,
+    DocumentDiagnosticRequest, DocumentHighlightRequest, DocumentLinkRequest,
+    DocumentSymbolRequest, ExecuteCommand, FoldingRangeRequest, Formatting, GotoDefinition,
+    HoverRequest, InlayHintRequest, OnTypeFormatting, PrepareRenameRequest, RangeFormatting,
+    References, Rename, SelectionRangeRequest, SemanticTokensFullRequest, SignatureHelpRequest,
+    TypeHierarchyPrepare, TypeHierarchySubtypes, TypeHierarchySupertypes,
+    WorkspaceDiagnosticRequest, WorkspaceSymbolRequest,
 };
 use lsp_types::{
-    ClientCapabilities, CodeActionOrCommand, CodeActionProviderCapability, CodeLens,
-    CodeLensOptions, Color, ColorInformation, ColorPresentation, Command, CompletionOptions,
-    DocumentSymbol, DocumentSymbolResponse, Hover, InitializeParams, InitializeResult, OneOf,
-    Position, PrepareRenameResponse, PublishDiagnosticsParams, RenameOptions,
+    CallHierarchyServerCapability, ClientCapabilities, CodeActionOrCommand,
+    CodeActionProviderCapability, CodeLens, CodeLensOptions, Color, ColorInformation,
+    ColorPresentation, Command, CompletionOptions, DocumentDiagnosticReport,
+    DocumentDiagnosticReportResult, DocumentLinkOptions, DocumentOnTypeFormattingOptions,
+    DocumentSymbol, DocumentSymbolResponse, FoldingRangeProviderCapability, InitializeParams,
+    InitializeResult, OneOf, Position, PrepareRenameResponse, PublishDiagnosticsParams,
+    RelatedFullDocumentDiagnosticReport, RenameOptions, SelectionRangeProviderCapability,
     SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions, ServerCapabilities,
-    ServerInfo, TextDocumentSyncCapability, TextEdit, Url, WorkDoneProgressOptions, WorkspaceEdit,
+    ServerInfo, SignatureHelpOptions, TextDocumentSyncCapability, TextEdit, Url,
+    WorkDoneProgressOptions, WorkspaceDiagnosticReportResult, WorkspaceEdit, WorkspaceSymbol,
+    WorkspaceSymbolResponse,
 };
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -105,13 +125,25 @@ pub fn request_state(ctx: &std::rc::Rc<Context>) {
 pub struct DocumentCache {
     pub(crate) documents: TypeLoader,
     versions: HashMap<Url, i32>,
+    /// The diagnostics of each file from the last time it was loaded
+    diagnostics: HashMap<Url, Vec<lsp_types::Diagnostic>>,
+    /// The configuration of the lints, or None if the lints are disabled
+    pub(crate) lint_config: Option<i_slint_compiler::lint::LintConfig>,
+    /// The results of the previous workspace diagnostics requests
+    workspace_diagnostics: diagnostics::WorkspaceDiagnostics,
 }
 
 impl DocumentCache {
     pub fn new(config: CompilerConfiguration) -> Self {
         let documents =
             TypeLoader::new(TypeRegister::builtin(), config, &mut BuildDiagnostics::default());
-        Self { documents, versions: Default::default() }
+        Self {
+            documents,
+            versions: Default::default(),
+            diagnostics: Default::default(),
+            lint_config: None,
+            workspace_diagnostics: Default::default(),
+        }
     }
 
     pub fn document_version(&self, target_uri: &lsp_types::Url) -> Option<i32> {
@@ -173,6 +205,12 @@ pub fn server_initialize_result(client_cap: &ClientCapabilities) -> InitializeRe
                 completion_item: None,
             }),
             definition_provider: Some(OneOf::Left(true)),
+            hover_provider: Some(true.into()),
+            signature_help_provider: Some(SignatureHelpOptions {
+                trigger_characters: Some(vec!["(".to_owned(), ",".to_owned()]),
+                retrigger_characters: None,
+                work_done_progress_options: WorkDoneProgressOptions::default(),
+            }),
             text_document_sync: Some(TextDocumentSyncCapability::Kind(
                 lsp_types::TextDocumentSyncKind::FULL,
             )),
@@ -184,6 +222,12 @@ pub fn server_initialize_result(client_cap: &ClientCapabilities) -> InitializeRe
             document_symbol_provider: Some(OneOf::Left(true)),
             color_provider: Some(true.into()),
             code_lens_provider: Some(CodeLensOptions { resolve_provider: Some(true) }),
+            document_formatting_provider: Some(OneOf::Left(true)),
+            document_range_formatting_provider: Some(OneOf::Left(true)),
+            document_on_type_formatting_provider: Some(DocumentOnTypeFormattingOptions {
+                first_trigger_character: "}".into(),
+                more_trigger_character: Some(vec![";".into()]),
+            }),
             semantic_tokens_provider: Some(
                 SemanticTokensOptions {
                     legend: SemanticTokensLegend {
@@ -196,6 +240,17 @@ pub fn server_initialize_result(client_cap: &ClientCapabilities) -> InitializeRe
                 .into(),
             ),
             document_highlight_provider: Some(OneOf::Left(true)),
+            references_provider: Some(OneOf::Left(true)),
+            call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            diagnostic_provider: Some(diagnostics::diagnostic_options()),
+            folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+            selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+            document_link_provider: Some(DocumentLinkOptions {
+                resolve_provider: None,
+                work_done_progress_options: WorkDoneProgressOptions::default(),
+            }),
+            workspace_symbol_provider: Some(OneOf::Left(true)),
             rename_provider: Some(
                 if client_cap
                     .text_document
@@ -256,18 +311,15 @@ pub fn register_request_handlers(rh: &mut RequestHandler) {
         });
         Ok(result)
     });
-    rh.register::<HoverRequest, _>(|_params, _ctx| async move {
-        /*let result =
-            token_descr(document_cache, params.text_document_position_params).map(|x| Hover {
-                contents: lsp_types::HoverContents::Scalar(MarkedString::from_language_code(
-                    "text".into(),
-                    format!("{:?}", x.token),
-                )),
-                range: None,
-            });
-        let resp = Response::new_ok(id, result);
-        connection.sender.send(Message::Response(resp))?;*/
-        Ok(None::<Hover>)
+    rh.register::<HoverRequest, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result = token_descr(
+            document_cache,
+            &params.text_document_position_params.text_document.uri,
+            &params.text_document_position_params.position,
+        )
+        .and_then(|(token, _)| hover::get_tooltip(document_cache, token));
+        Ok(result)
     });
     rh.register::<CodeActionRequest, _>(|params, ctx| async move {
         let document_cache = &mut ctx.document_cache.borrow_mut();
@@ -390,6 +442,154 @@ pub fn register_request_handlers(rh: &mut RequestHandler) {
         };
         Err("This symbol cannot be renamed. (Only element id can be renamed at the moment)".into())
     });
+    rh.register::<References, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result = token_descr(
+            document_cache,
+            &params.text_document_position.text_document.uri,
+            &params.text_document_position.position,
+        )
+        .and_then(|(token, _)| {
+            references::find_references(document_cache, token, params.context.include_declaration)
+        });
+        Ok(result)
+    });
+    rh.register::<CallHierarchyPrepare, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result = token_descr(
+            document_cache,
+            &params.text_document_position_params.text_document.uri,
+            &params.text_document_position_params.position,
+        )
+        .and_then(|(token, _)| call_hierarchy::prepare_call_hierarchy(document_cache, token));
+        Ok(result)
+    });
+    rh.register::<CallHierarchyIncomingCalls, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result =
+            token_descr(document_cache, &params.item.uri, &params.item.selection_range.start)
+                .and_then(|(token, _)| call_hierarchy::incoming_calls(document_cache, token));
+        Ok(result)
+    });
+    rh.register::<CallHierarchyOutgoingCalls, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result =
+            token_descr(document_cache, &params.item.uri, &params.item.selection_range.start)
+                .and_then(|(token, _)| call_hierarchy::outgoing_calls(document_cache, token));
+        Ok(result)
+    });
+    rh.register::<TypeHierarchyPrepare, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result = token_descr(
+            document_cache,
+            &params.text_document_position_params.text_document.uri,
+            &params.text_document_position_params.position,
+        )
+        .and_then(|(token, _)| type_hierarchy::prepare_type_hierarchy(document_cache, token));
+        Ok(result)
+    });
+    rh.register::<TypeHierarchySupertypes, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result =
+            token_descr(document_cache, &params.item.uri, &params.item.selection_range.start)
+                .and_then(|(token, _)| type_hierarchy::supertypes(document_cache, token));
+        Ok(result)
+    });
+    rh.register::<TypeHierarchySubtypes, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result =
+            token_descr(document_cache, &params.item.uri, &params.item.selection_range.start)
+                .and_then(|(token, _)| type_hierarchy::subtypes(document_cache, token));
+        Ok(result)
+    });
+    rh.register::<WorkspaceSymbolRequest, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        Ok(get_workspace_symbols(document_cache, &params.query))
+    });
+    rh.register::<SignatureHelpRequest, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let result = token_descr(
+            document_cache,
+            &params.text_document_position_params.text_document.uri,
+            &params.text_document_position_params.position,
+        )
+        .and_then(|(token, offset)| {
+            signature_help::get_signature_help(document_cache, token, offset)
+        });
+        Ok(result)
+    });
+    rh.register::<InlayHintRequest, _>(|params, ctx| async move {
+        let document_cache = ctx.document_cache.borrow();
+        Ok(inlay_hints::get_inlay_hints(&document_cache, &params.text_document.uri, &params.range))
+    });
+    rh.register::<FoldingRangeRequest, _>(|params, ctx| async move {
+        let document_cache = ctx.document_cache.borrow();
+        let result = uri_to_file(&params.text_document.uri)
+            .and_then(|path| document_cache.documents.get_document(&path)?.node.as_ref())
+            .map(folding::get_folding_ranges);
+        Ok(result)
+    });
+    rh.register::<SelectionRangeRequest, _>(|params, ctx| async move {
+        let document_cache = ctx.document_cache.borrow();
+        let result = uri_to_file(&params.text_document.uri)
+            .and_then(|path| document_cache.documents.get_document(&path)?.node.as_ref())
+            .map(|doc| {
+                let offsets = params.positions.iter().map(|p| {
+                    doc.source_file.offset(p.line as usize + 1, p.character as usize + 1) as u32
+                });
+                folding::get_selection_ranges(doc, offsets)
+            });
+        Ok(result)
+    });
+    rh.register::<DocumentLinkRequest, _>(|params, ctx| async move {
+        let document_cache = ctx.document_cache.borrow();
+        let result = uri_to_file(&params.text_document.uri)
+            .and_then(|path| document_cache.documents.get_document(&path)?.node.as_ref())
+            .map(|doc| document_links::get_document_links(&document_cache, doc));
+        Ok(result)
+    });
+    rh.register::<DocumentDiagnosticRequest, _>(|params, ctx| async move {
+        let document_cache = &mut ctx.document_cache.borrow_mut();
+        let report =
+            diagnostics::document_diagnostics(document_cache, &params.text_document.uri).await;
+        Ok(DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(
+            RelatedFullDocumentDiagnosticReport {
+                related_documents: None,
+                full_document_diagnostic_report: report,
+            },
+        )))
+    });
+    rh.register::<WorkspaceDiagnosticRequest, _>(|params, ctx| async move {
+        let folders = diagnostics::workspace_folders(&ctx.init_param);
+        let report = diagnostics::workspace_diagnostics(
+            &ctx.document_cache,
+            &folders,
+            &params.previous_result_ids,
+        )
+        .await;
+        Ok(WorkspaceDiagnosticReportResult::Report(report))
+    });
+    rh.register::<Formatting, _>(|params, ctx| async move {
+        let document_cache = ctx.document_cache.borrow();
+        Ok(formatting::format_document(&document_cache, &params.text_document.uri, None))
+    });
+    rh.register::<RangeFormatting, _>(|params, ctx| async move {
+        let document_cache = ctx.document_cache.borrow();
+        Ok(formatting::format_document(
+            &document_cache,
+            &params.text_document.uri,
+            Some(params.range),
+        ))
+    });
+    rh.register::<OnTypeFormatting, _>(|params, ctx| async move {
+        let document_cache = ctx.document_cache.borrow();
+        Ok(formatting::format_on_type(
+            &document_cache,
+            &params.text_document_position.text_document.uri,
+            params.text_document_position.position,
+            &params.ch,
+        ))
+    });
     rh.register::<PrepareRenameRequest, _>(|params, ctx| async move {
         let mut document_cache = ctx.document_cache.borrow_mut();
         let uri = params.text_document.uri;
@@ -656,12 +856,34 @@ pub(crate) async fn reload_document_impl(
     if let Some(ctx) = ctx {
         ctx.preview.set_contents(&path, &content);
     }
+    load_file(&path, content, document_cache).await
+}
+
+/// Load (or reload) the file in the document cache and return the diagnostics for
+/// the file and all the files loaded because of it
+pub(crate) async fn load_file(
+    path: &std::path::Path,
+    content: String,
+    document_cache: &mut DocumentCache,
+) -> HashMap<Url, Vec<lsp_types::Diagnostic>> {
     let mut diag = BuildDiagnostics::default();
-    document_cache.documents.load_file(&path, &path, content, false, &mut diag).await;
+    document_cache.documents.load_file(path, path, content, false, &mut diag).await;
+
+    if let Some(lint_config) = &document_cache.lint_config {
+        let loaded_files = diag.all_loaded_files.clone();
+        for path in core::iter::once(path).chain(loaded_files.iter().map(|p| p.as_path())) {
+            if path.starts_with("builtin:/") {
+                continue;
+            }
+            if let Some(doc) = document_cache.documents.get_document(path) {
+                i_slint_compiler::lint::lint_document(doc, lint_config, &mut diag);
+            }
+        }
+    }
 
     // Always provide diagnostics for all files. Empty diagnostics clear any previous ones.
-    let mut lsp_diags: HashMap<Url, Vec<lsp_types::Diagnostic>> = core::iter::once(&path)
-        .chain(diag.all_loaded_files.iter())
+    let mut lsp_diags: HashMap<Url, Vec<lsp_types::Diagnostic>> = core::iter::once(path)
+        .chain(diag.all_loaded_files.iter().map(|p| p.as_path()))
         .map(|path| {
             let uri = Url::from_file_path(path).unwrap();
             (uri, Default::default())
@@ -677,6 +899,7 @@ pub(crate) async fn reload_document_impl(
         lsp_diags.entry(uri).or_default().push(to_lsp_diag(&d));
     }
 
+    document_cache.diagnostics.extend(lsp_diags.iter().map(|(k, v)| (k.clone(), v.clone())));
     lsp_diags
 }
 
@@ -689,6 +912,10 @@ pub async fn reload_document(
 ) -> Result<()> {
     let lsp_diags = reload_document_impl(Some(ctx), content, uri, version, document_cache).await;
 
+    if diagnostics::client_pulls_diagnostics(&ctx.init_param.capabilities) {
+        // The client will ask for the diagnostics itself
+        return Ok(());
+    }
     for (uri, diagnostics) in lsp_diags {
         ctx.server_notifier.send_notification(
             "textDocument/publishDiagnostics".into(),
@@ -1107,6 +1334,74 @@ fn gen_children(elem: &ElementRc, ds: &DocumentSymbol) -> Option<Vec<DocumentSym
     Some(r.into())
 }
 
+/// Return true if all the characters of the query appear in the name, in order and ignoring the case
+fn matches_symbol_query(name: &str, query: &str) -> bool {
+    let mut name = name.chars().flat_map(char::to_lowercase);
+    query.chars().flat_map(char::to_lowercase).all(|q| name.any(|c| c == q))
+}
+
+fn get_workspace_symbols(
+    document_cache: &mut DocumentCache,
+    query: &str,
+) -> Option<WorkspaceSymbolResponse> {
+    let mut r = vec![];
+    let mut push = |name: &str, kind, container_name: Option<&str>, node: Option<&SyntaxNode>| {
+        if !matches_symbol_query(name, query) {
+            return;
+        }
+        if let Some((uri, range)) = node.and_then(map_node_and_url) {
+            r.push(WorkspaceSymbol {
+                name: name.into(),
+                kind,
+                tags: None,
+                container_name: container_name.map(Into::into),
+                location: OneOf::Left(lsp_types::Location { uri, range }),
+                data: None,
+            });
+        }
+    };
+
+    for (path, doc) in document_cache.documents.all_file_documents() {
+        if path.starts_with("builtin:/") {
+            continue;
+        }
+        for c in &doc.inner_components {
+            let root_element = c.root_element.borrow();
+            let Some(component_node) =
+                root_element.node.as_ref().and_then(|n| syntax_nodes::Component::new(n.parent()?))
+            else {
+                continue;
+            };
+            let kind = if c.is_global() {
+                lsp_types::SymbolKind::OBJECT
+            } else {
+                lsp_types::SymbolKind::CLASS
+            };
+            push(&c.id, kind, None, Some(&component_node.DeclaredIdentifier()));
+            for (name, decl) in &root_element.property_declarations {
+                let kind = match decl.property_type {
+                    Type::Callback { .. } => lsp_types::SymbolKind::EVENT,
+                    Type::Function { .. } => lsp_types::SymbolKind::METHOD,
+                    _ => lsp_types::SymbolKind::PROPERTY,
+                };
+                push(name, kind, Some(&c.id), decl.node.as_ref());
+            }
+        }
+        for ty in &doc.inner_types {
+            match ty {
+                Type::Struct { name: Some(name), node: Some(node), .. } => {
+                    push(name, lsp_types::SymbolKind::STRUCT, None, node.parent().as_ref())
+                }
+                Type::Enumeration(e) => {
+                    push(&e.name, lsp_types::SymbolKind::ENUM, None, e.node.as_deref())
+                }
+                _ => (),
+            }
+        }
+    }
+    Some(WorkspaceSymbolResponse::Nested(r))
+}
+
 fn get_code_lenses(
     document_cache: &mut DocumentCache,
     text_document: &lsp_types::TextDocumentIdentifier,
@@ -1200,6 +1495,35 @@ fn recurse(
     None
 }
 
+/// Register the capabilities that can't be announced in the result of the `initialize` request
+/// because `lsp_types::ServerCapabilities` has no field for them.
+pub async fn register_dynamic_capabilities(ctx: &Context) -> Result<()> {
+    if !ctx
+        .init_param
+        .capabilities
+        .text_document
+        .as_ref()
+        .and_then(|td| td.type_hierarchy.as_ref())
+        .and_then(|th| th.dynamic_registration)
+        .unwrap_or(false)
+    {
+        return Ok(());
+    }
+
+    ctx.server_notifier
+        .send_request::<lsp_types::request::RegisterCapability>(lsp_types::RegistrationParams {
+            registrations: vec![lsp_types::Registration {
+                id: "slint-type-hierarchy".into(),
+                method: <TypeHierarchyPrepare as lsp_types::request::Request>::METHOD.into(),
+                register_options: Some(serde_json::to_value(
+                    lsp_types::TextDocumentRegistrationOptions::default(),
+                )?),
+            }],
+        })?
+        .await?;
+    Ok(())
+}
+
 pub async fn load_configuration(ctx: &Context) -> Result<()> {
     if !ctx
         .init_param
@@ -1248,6 +1572,9 @@ pub async fn load_configuration(ctx: &Context) -> Result<()> {
                     document_cache.documents.compiler_config.style = Some(style.into());
                 }
             }
+            if let Some(lint) = o.get("lint").and_then(|v| v.as_object()) {
+                document_cache.lint_config = lint_config(lint);
+            }
         }
     }
 
@@ -1266,6 +1593,22 @@ pub async fn load_configuration(ctx: &Context) -> Result<()> {
     Ok(())
 }
 
+/// Parse the `lint` section of the configuration: `{ "enabled": bool, "rules": { rule: level } }`
+fn lint_config(
+    config: &serde_json::Map<String, serde_json::Value>,
+) -> Option<i_slint_compiler::lint::LintConfig> {
+    if !config.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return None;
+    }
+    let mut lint_config = i_slint_compiler::lint::LintConfig::default();
+    for (rule, level) in config.get("rules").and_then(|v| v.as_object()).into_iter().flatten() {
+        let Some(level) = level.as_str().and_then(|l| l.parse().ok()) else { continue };
+        // Unknown rules may come from a newer version of the configuration
+        lint_config.set_level(rule, level).ok();
+    }
+    Some(lint_config)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1293,6 +1636,31 @@ fn test_reload_document_valid_contents() {
         assert!(diagnostics.is_empty());
     }
 
+    #[test]
+    fn test_reload_document_lint() {
+        let source = "export component Main { property <int> unused; property <color> c: #f00; }";
+        let mut dc = test::empty_document_cache();
+        let config = serde_json::json!({ "enabled": true, "rules": { "magic-color": "deny" } });
+        dc.lint_config = lint_config(config.as_object().unwrap());
+        let url = Url::from_file_path(if cfg!(target_family = "windows") {
+            "c://foo/bar.slint"
+        } else {
+            "/foo/bar.slint"
+        })
+        .unwrap();
+        let diag =
+            spin_on::spin_on(reload_document_impl(None, source.into(), url.clone(), 1, &mut dc));
+        let diagnostics = diag.get(&url).unwrap();
+        assert_eq!(diagnostics.len(), 3);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.severity == Some(lsp_types::DiagnosticSeverity::ERROR)
+                && d.message.ends_with("[magic-color]")));
+
+        // Lints are disabled by default
+        assert!(lint_config(serde_json::json!({ "rules": {} }).as_object().unwrap()).is_none());
+    }
+
     #[test]
     fn test_text_document_color_no_color_set() {
         let (mut dc, url, _) = loaded_document_cache(
@@ -1464,6 +1832,42 @@ fn test_document_symbols_hello_world() {
         }
     }
 
+    #[test]
+    fn test_workspace_symbols() {
+        let (mut dc, uri, _) = loaded_document_cache(
+            r#"struct Point { x: int, y: int }
+global Settings { in-out property <Point> origin; }
+export component MainWindow {
+    callback clicked();
+    property <int> counter;
+}
+            "#
+            .into(),
+        );
+
+        let mut names = |query: &str| match get_workspace_symbols(&mut dc, query) {
+            Some(WorkspaceSymbolResponse::Nested(result)) => {
+                let mut names = result
+                    .iter()
+                    .map(|s| {
+                        assert!(matches!(&s.location, OneOf::Left(l) if l.uri == uri));
+                        s.name.clone()
+                    })
+                    .collect::<Vec<_>>();
+                names.sort();
+                names
+            }
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            names(""),
+            vec!["MainWindow", "Point", "Settings", "clicked", "counter", "origin"]
+        );
+        assert_eq!(names("mw"), vec!["MainWindow"]);
+        assert_eq!(names("CoUn"), vec!["counter"]);
+        assert!(names("nothing").is_empty());
+    }
+
     #[test]
     fn test_code_actions() {
         let (mut dc, url, _) = loaded_document_cache(
@@ -0,0 +1,331 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use super::goto::find_definition;
+use super::references::{declaration_at, is_same_node, reference_tokens};
+use super::DocumentCache;
+use crate::util::{map_node, map_token};
+
+#[cfg(target_arch = "wasm32")]
+use crate::wasm_prelude::*;
+
+use i_slint_compiler::parser::{normalize_identifier, SyntaxKind, SyntaxNode, SyntaxToken};
+use lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyItem, CallHierarchyOutgoingCall, Range, SymbolKind, Url,
+};
+
+/// Return the item for the callback or function under the token, or for the callback handler
+/// whose name is the token.
+pub fn prepare_call_hierarchy(
+    document_cache: &DocumentCache,
+    token: SyntaxToken,
+) -> Option<Vec<CallHierarchyItem>> {
+    if token.kind() != SyntaxKind::Identifier {
+        return None;
+    }
+    let node =
+        named_node(&token).or_else(|| find_definition(document_cache, token)).filter(|n| {
+            matches!(
+                n.kind(),
+                SyntaxKind::CallbackDeclaration
+                    | SyntaxKind::Function
+                    | SyntaxKind::CallbackConnection
+            )
+        })?;
+    Some(vec![make_item(&node)?])
+}
+
+/// Return the functions, callback handlers, bindings and callbacks forwarding with `<=>` that
+/// call the callback or function of the item (`token` is the name of the item)
+pub fn incoming_calls(
+    document_cache: &DocumentCache,
+    token: SyntaxToken,
+) -> Option<Vec<CallHierarchyIncomingCall>> {
+    let definition = callable_definition(document_cache, &token)?;
+
+    let mut result: Vec<(SyntaxNode, Vec<Range>)> = vec![];
+    for t in reference_tokens(document_cache, &token, &definition, false) {
+        if !is_in_expression(&t) {
+            continue;
+        }
+        let (Some(caller), Some(range)) = (caller_node(&t), map_token(&t)) else { continue };
+        match result.iter_mut().find(|(n, _)| is_same_node(n, &caller)) {
+            Some((_, ranges)) => ranges.push(range),
+            None => result.push((caller, vec![range])),
+        }
+    }
+    Some(
+        result
+            .into_iter()
+            .filter_map(|(caller, from_ranges)| {
+                Some(CallHierarchyIncomingCall { from: make_item(&caller)?, from_ranges })
+            })
+            .collect(),
+    )
+}
+
+/// Return the callbacks and functions called by the item (`token` is the name of the item).
+/// For a callback, these are the calls done by its handlers, or the callback it forwards to
+/// with `<=>`.
+pub fn outgoing_calls(
+    document_cache: &DocumentCache,
+    token: SyntaxToken,
+) -> Option<Vec<CallHierarchyOutgoingCall>> {
+    let node = named_node(&token)?;
+    let mut bodies = vec![node.clone()];
+    if node.kind() == SyntaxKind::CallbackDeclaration {
+        bodies.extend(callback_handlers(document_cache, &node));
+    }
+
+    let mut result: Vec<(SyntaxNode, Vec<Range>)> = vec![];
+    for body in bodies {
+        let end = body.text_range().end();
+        let mut current = body.first_token();
+        while let Some(t) = current.take().filter(|t| t.text_range().start() < end) {
+            current = t.next_token();
+            if t.kind() != SyntaxKind::Identifier || !is_in_expression(&t) {
+                continue;
+            }
+            let Some(callee) = find_definition(document_cache, t.clone()).filter(|n| {
+                matches!(n.kind(), SyntaxKind::CallbackDeclaration | SyntaxKind::Function)
+            }) else {
+                continue;
+            };
+            let Some(range) = map_token(&t) else { continue };
+            match result.iter_mut().find(|(n, _)| is_same_node(n, &callee)) {
+                Some((_, ranges)) => ranges.push(range),
+                None => result.push((callee, vec![range])),
+            }
+        }
+    }
+    Some(
+        result
+            .into_iter()
+            .filter_map(|(callee, from_ranges)| {
+                Some(CallHierarchyOutgoingCall { to: make_item(&callee)?, from_ranges })
+            })
+            .collect(),
+    )
+}
+
+/// The declaration of the callback or function of an item. The item of a callback handler
+/// stands for the callback it handles.
+fn callable_definition(document_cache: &DocumentCache, token: &SyntaxToken) -> Option<SyntaxNode> {
+    let node = named_node(token)?;
+    match node.kind() {
+        SyntaxKind::CallbackDeclaration | SyntaxKind::Function => Some(node),
+        SyntaxKind::CallbackConnection => find_definition(document_cache, token.clone()),
+        _ => None,
+    }
+}
+
+/// The handlers (`name => { ... }`) of the callback declared by `declaration`
+fn callback_handlers(document_cache: &DocumentCache, declaration: &SyntaxNode) -> Vec<SyntaxNode> {
+    let Some(name) = declaration
+        .child_node(SyntaxKind::DeclaredIdentifier)
+        .and_then(|n| n.child_text(SyntaxKind::Identifier))
+        .map(|n| normalize_identifier(&n))
+    else {
+        return vec![];
+    };
+    let mut result = vec![];
+    for (path, doc) in document_cache.documents.all_file_documents() {
+        if path.starts_with("builtin:/") {
+            continue;
+        }
+        let Some(doc_node) = &doc.node else { continue };
+        for n in doc_node.descendants() {
+            let n = SyntaxNode { node: n, source_file: doc_node.source_file.clone() };
+            if n.kind() != SyntaxKind::CallbackConnection {
+                continue;
+            }
+            let Some(t) = n.child_token(SyntaxKind::Identifier) else { continue };
+            if normalize_identifier(t.text()) == name
+                && find_definition(document_cache, t)
+                    .map_or(false, |d| is_same_node(&d, declaration))
+            {
+                result.push(n);
+            }
+        }
+    }
+    result
+}
+
+/// If the token is the name of something that can appear in the call hierarchy, return its node
+fn named_node(token: &SyntaxToken) -> Option<SyntaxNode> {
+    let node = token.parent();
+    match node.kind() {
+        SyntaxKind::DeclaredIdentifier => declaration_at(token).filter(|n| {
+            matches!(
+                n.kind(),
+                SyntaxKind::CallbackDeclaration
+                    | SyntaxKind::Function
+                    | SyntaxKind::PropertyDeclaration
+            )
+        }),
+        SyntaxKind::CallbackConnection | SyntaxKind::Binding => Some(node),
+        _ => None,
+    }
+}
+
+/// True if the token is part of a name used in an expression (and not, for example, the name of
+/// an element type)
+fn is_in_expression(token: &SyntaxToken) -> bool {
+    let qn = token.parent();
+    qn.kind() == SyntaxKind::QualifiedName
+        && qn.parent().map_or(false, |p| p.kind() == SyntaxKind::Expression)
+}
+
+/// The function, callback handler, binding or callback declaration containing the token
+fn caller_node(token: &SyntaxToken) -> Option<SyntaxNode> {
+    let mut node = token.parent();
+    loop {
+        match node.kind() {
+            SyntaxKind::Function
+            | SyntaxKind::CallbackConnection
+            | SyntaxKind::Binding
+            | SyntaxKind::CallbackDeclaration
+            | SyntaxKind::PropertyDeclaration => return Some(node),
+            SyntaxKind::Element | SyntaxKind::Component | SyntaxKind::Document => return None,
+            _ => node = node.parent()?,
+        }
+    }
+}
+
+fn make_item(node: &SyntaxNode) -> Option<CallHierarchyItem> {
+    let name_token = match node.kind() {
+        SyntaxKind::CallbackConnection | SyntaxKind::Binding => {
+            node.child_token(SyntaxKind::Identifier)?
+        }
+        _ => {
+            node.child_node(SyntaxKind::DeclaredIdentifier)?.child_token(SyntaxKind::Identifier)?
+        }
+    };
+    let kind = match node.kind() {
+        SyntaxKind::Function => SymbolKind::METHOD,
+        SyntaxKind::CallbackDeclaration | SyntaxKind::CallbackConnection => SymbolKind::EVENT,
+        _ => SymbolKind::PROPERTY,
+    };
+    Some(CallHierarchyItem {
+        name: name_token.text().to_string(),
+        kind,
+        tags: None,
+        detail: component_name(node),
+        uri: Url::from_file_path(node.source_file.path()).ok()?,
+        range: map_node(node)?,
+        selection_range: map_token(&name_token)?,
+        data: None,
+    })
+}
+
+/// The name of the component or global containing the node
+fn component_name(node: &SyntaxNode) -> Option<String> {
+    let mut node = node.parent()?;
+    while node.kind() != SyntaxKind::Component {
+        node = node.parent()?;
+    }
+    node.child_node(SyntaxKind::DeclaredIdentifier)?.child_text(SyntaxKind::Identifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::test::loaded_document_cache;
+    use crate::language::uri_to_file;
+
+    const SOURCE: &str = r#"component Button {
+    callback clicked;
+    public function press() { clicked(); }
+    TouchArea { clicked => { press(); } }
+}
+export component Main {
+    callback activated <=> b.clicked;
+    function log(x: int) -> int { return x; }
+    property <int> count: log(1) + log(2);
+    b := Button {
+        clicked => { log(3); }
+    }
+    TouchArea { clicked => { b.press(); } }
+}"#;
+
+    fn token_at(dc: &DocumentCache, uri: &Url, offset: usize) -> SyntaxToken {
+        let doc = dc.documents.get_document(&uri_to_file(uri).unwrap()).unwrap();
+        crate::language::token_at_offset(doc.node.as_ref().unwrap(), offset as u32).unwrap()
+    }
+
+    #[test]
+    fn test_prepare_call_hierarchy() {
+        let (dc, uri, _) = loaded_document_cache(SOURCE.into());
+        let items =
+            prepare_call_hierarchy(&dc, token_at(&dc, &uri, SOURCE.find("b.press").unwrap() + 2))
+                .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "press");
+        assert_eq!(items[0].kind, SymbolKind::METHOD);
+        assert_eq!(items[0].detail.as_deref(), Some("Button"));
+        assert_eq!(items[0].selection_range.start, lsp_types::Position::new(2, 20));
+
+        let items =
+            prepare_call_hierarchy(&dc, token_at(&dc, &uri, SOURCE.find("log(1)").unwrap()))
+                .unwrap();
+        assert_eq!(items[0].name, "log");
+        assert_eq!(items[0].detail.as_deref(), Some("Main"));
+
+        let offset = SOURCE.find("count").unwrap();
+        assert!(prepare_call_hierarchy(&dc, token_at(&dc, &uri, offset)).is_none());
+    }
+
+    #[test]
+    fn test_incoming_calls() {
+        let (dc, uri, _) = loaded_document_cache(SOURCE.into());
+        let offset = SOURCE.find("callback clicked").unwrap() + 9;
+        let mut calls = incoming_calls(&dc, token_at(&dc, &uri, offset))
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.from.name, c.from.detail.unwrap(), c.from_ranges.len()))
+            .collect::<Vec<_>>();
+        calls.sort();
+        assert_eq!(
+            calls,
+            vec![("activated".into(), "Main".into(), 1), ("press".into(), "Button".into(), 1)]
+        );
+
+        let offset = SOURCE.find("function log").unwrap() + 9;
+        let mut calls = incoming_calls(&dc, token_at(&dc, &uri, offset))
+            .unwrap()
+            .into_iter()
+            .map(|c| (c.from.name, c.from.kind, c.from_ranges.len()))
+            .collect::<Vec<_>>();
+        calls.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            calls,
+            vec![
+                ("clicked".into(), SymbolKind::EVENT, 1),
+                ("count".into(), SymbolKind::PROPERTY, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outgoing_calls() {
+        let (dc, uri, _) = loaded_document_cache(SOURCE.into());
+        let outgoing = |offset: usize| {
+            let mut calls = outgoing_calls(&dc, token_at(&dc, &uri, offset))
+                .unwrap()
+                .into_iter()
+                .map(|c| (c.to.name, c.from_ranges.len()))
+                .collect::<Vec<_>>();
+            calls.sort();
+            calls
+        };
+        assert_eq!(
+            outgoing(SOURCE.find("function press").unwrap() + 9),
+            vec![("clicked".into(), 1)]
+        );
+        assert_eq!(outgoing(SOURCE.find("activated").unwrap()), vec![("clicked".into(), 1)]);
+        assert_eq!(outgoing(SOURCE.find("count").unwrap()), vec![("log".into(), 2)]);
+        // The handlers of a callback are part of its outgoing calls
+        let offset = SOURCE.find("callback clicked").unwrap() + 9;
+        assert_eq!(outgoing(offset), vec![("log".into(), 1)]);
+    }
+}
@@ -0,0 +1,375 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+//! Support for the diagnostics that are pulled by the client (`textDocument/diagnostic` and
+//! `workspace/diagnostic`)
+
+use super::{load_file, uri_to_file, DocumentCache};
+
+#[cfg(target_arch = "wasm32")]
+use crate::wasm_prelude::*;
+
+use lsp_types::{
+    ClientCapabilities, Diagnostic, FullDocumentDiagnosticReport, InitializeParams,
+    PreviousResultId, UnchangedDocumentDiagnosticReport, Url, WorkspaceDiagnosticReport,
+    WorkspaceDocumentDiagnosticReport, WorkspaceFullDocumentDiagnosticReport,
+    WorkspaceUnchangedDocumentDiagnosticReport,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Returns true if the client supports pulling the diagnostics, in which case we do not
+/// need to send them
+pub fn client_pulls_diagnostics(capabilities: &ClientCapabilities) -> bool {
+    capabilities.text_document.as_ref().map_or(false, |td| td.diagnostic.is_some())
+}
+
+pub fn diagnostic_options() -> lsp_types::DiagnosticServerCapabilities {
+    lsp_types::DiagnosticServerCapabilities::Options(lsp_types::DiagnosticOptions {
+        identifier: Some("slint".into()),
+        inter_file_dependencies: true,
+        workspace_diagnostics: true,
+        work_done_progress_options: Default::default(),
+    })
+}
+
+/// The folders to check for the workspace diagnostics
+pub fn workspace_folders(init_param: &InitializeParams) -> Vec<PathBuf> {
+    match &init_param.workspace_folders {
+        Some(folders) => folders.iter().filter_map(|f| uri_to_file(&f.uri)).collect(),
+        #[allow(deprecated)]
+        None => init_param.root_uri.iter().filter_map(uri_to_file).collect(),
+    }
+}
+
+/// Return the diagnostics of the document, loading it from disk if it is not loaded yet
+pub async fn document_diagnostics(
+    document_cache: &mut DocumentCache,
+    uri: &Url,
+) -> FullDocumentDiagnosticReport {
+    if !document_cache.diagnostics.contains_key(uri) {
+        if let Some(path) = uri_to_file(uri) {
+            load_from_disk(&path, document_cache).await;
+        }
+    }
+    FullDocumentDiagnosticReport {
+        result_id: None,
+        items: document_cache.diagnostics.get(uri).cloned().unwrap_or_default(),
+    }
+}
+
+/// What the diagnostics of a file depend on: the version of the document opened in the editor,
+/// or else the modification time of the file on disk
+#[derive(Clone, Debug, PartialEq)]
+enum FileStamp {
+    Version(i32),
+    Modified(Option<std::time::SystemTime>),
+}
+
+/// The diagnostics of a file that was checked for the workspace diagnostics
+struct CheckedFile {
+    /// The stamps of the file and of all the files it depends on, at the time it was checked
+    stamps: Vec<(PathBuf, FileStamp)>,
+    result_id: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// The results of the previous `workspace/diagnostic` requests, so that only the files that
+/// changed, or that depend on a file that changed, are checked again
+#[derive(Default)]
+pub struct WorkspaceDiagnostics {
+    checked: HashMap<PathBuf, CheckedFile>,
+    last_result_id: u64,
+}
+
+/// Check the .slint files in the workspace folders and return their diagnostics.
+///
+/// Files are only checked again if they, or one of the files they depend on, changed since the
+/// previous request. Files whose diagnostics have the result id that the client already has
+/// are reported as unchanged. The document cache is only borrowed while checking one file.
+pub async fn workspace_diagnostics(
+    document_cache: &RefCell<DocumentCache>,
+    workspace_folders: &[PathBuf],
+    previous_result_ids: &[PreviousResultId],
+) -> WorkspaceDiagnosticReport {
+    let mut files = Vec::new();
+    let mut visited_dirs = HashSet::new();
+    for folder in workspace_folders {
+        find_slint_files(folder, &mut files, &mut visited_dirs);
+    }
+
+    let loaded_before =
+        document_cache.borrow().documents.all_files().cloned().collect::<HashSet<_>>();
+
+    let mut items = Vec::new();
+    for path in files {
+        let Ok(uri) = Url::from_file_path(&path) else { continue };
+        let mut document_cache = document_cache.borrow_mut();
+        let document_cache = &mut *document_cache;
+
+        let up_to_date =
+            document_cache.workspace_diagnostics.checked.get(&path).map_or(false, |checked| {
+                checked.stamps.iter().all(|(p, stamp)| file_stamp(document_cache, p) == *stamp)
+            });
+        if !up_to_date {
+            // Files opened in the editor may have unsaved changes
+            let open_content = document_cache
+                .versions
+                .contains_key(&uri)
+                .then(|| document_cache.documents.get_document(&path))
+                .flatten()
+                .and_then(|doc| doc.node.as_ref())
+                .map(|node| node.text().to_string());
+            let mut diagnostics = match open_content {
+                Some(content) => load_file(&path, content, document_cache).await,
+                None => load_from_disk(&path, document_cache).await,
+            };
+            let diagnostics = diagnostics.remove(&uri).unwrap_or_default();
+            let stamps = dependency_closure(document_cache, &path)
+                .into_iter()
+                .map(|p| {
+                    let stamp = file_stamp(document_cache, &p);
+                    (p, stamp)
+                })
+                .collect();
+
+            let state = &mut document_cache.workspace_diagnostics;
+            let result_id = match state.checked.get(&path) {
+                Some(previous) if previous.diagnostics == diagnostics => previous.result_id.clone(),
+                _ => {
+                    state.last_result_id += 1;
+                    state.last_result_id.to_string()
+                }
+            };
+            state.checked.insert(path.clone(), CheckedFile { stamps, result_id, diagnostics });
+        }
+
+        let checked = &document_cache.workspace_diagnostics.checked[&path];
+        let version = document_cache.document_version(&uri).map(i64::from);
+        let unchanged =
+            previous_result_ids.iter().any(|p| p.uri == uri && p.value == checked.result_id);
+        items.push(if unchanged {
+            WorkspaceDocumentDiagnosticReport::Unchanged(
+                WorkspaceUnchangedDocumentDiagnosticReport {
+                    uri,
+                    version,
+                    unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport {
+                        result_id: checked.result_id.clone(),
+                    },
+                },
+            )
+        } else {
+            WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+                uri,
+                version,
+                full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                    result_id: Some(checked.result_id.clone()),
+                    items: checked.diagnostics.clone(),
+                },
+            })
+        });
+    }
+
+    // The files that were only loaded to be checked don't need to stay in memory
+    let mut document_cache = document_cache.borrow_mut();
+    let loaded_for_check = document_cache
+        .documents
+        .all_files()
+        .filter(|p| !loaded_before.contains(*p) && !p.starts_with("builtin:/"))
+        .cloned()
+        .collect::<Vec<_>>();
+    for path in loaded_for_check {
+        document_cache.documents.drop_document(&path);
+        if let Ok(uri) = Url::from_file_path(&path) {
+            document_cache.diagnostics.remove(&uri);
+        }
+    }
+
+    WorkspaceDiagnosticReport { items }
+}
+
+fn file_stamp(document_cache: &DocumentCache, path: &Path) -> FileStamp {
+    match Url::from_file_path(path).ok().and_then(|uri| document_cache.document_version(&uri)) {
+        Some(version) => FileStamp::Version(version),
+        None => FileStamp::Modified(file_modified(path)),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn file_modified(path: &Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn file_modified(_: &Path) -> Option<std::time::SystemTime> {
+    None
+}
+
+/// The file and all the files it imports, directly or indirectly
+fn dependency_closure(document_cache: &DocumentCache, path: &Path) -> Vec<PathBuf> {
+    let mut result = vec![path.to_owned()];
+    let mut seen = HashSet::from([path.to_owned()]);
+    let mut i = 0;
+    while i < result.len() {
+        for dependency in document_cache.documents.document_dependencies(&result[i]) {
+            if !dependency.starts_with("builtin:/") && seen.insert(dependency.clone()) {
+                result.push(dependency);
+            }
+        }
+        i += 1;
+    }
+    result
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn load_from_disk(
+    path: &Path,
+    document_cache: &mut DocumentCache,
+) -> HashMap<Url, Vec<Diagnostic>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) if path.extension().map_or(false, |e| e == "slint") => {
+            load_file(path, content, document_cache).await
+        }
+        _ => Default::default(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn load_from_disk(_: &Path, _: &mut DocumentCache) -> HashMap<Url, Vec<Diagnostic>> {
+    // Files can only be loaded through the editor
+    Default::default()
+}
+
+/// Recursively find the .slint files in the directory, skipping hidden directories and the
+/// directories containing build artifacts or dependencies. Symbolic links are followed, but
+/// each directory is only visited once.
+#[cfg(not(target_arch = "wasm32"))]
+fn find_slint_files(dir: &Path, result: &mut Vec<PathBuf>, visited_dirs: &mut HashSet<PathBuf>) {
+    let Ok(canonical) = std::fs::canonicalize(dir) else { return };
+    if !visited_dirs.insert(canonical) {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    let mut entries = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" && name != "node_modules" {
+                find_slint_files(&path, result, visited_dirs);
+            }
+        } else if path.extension().map_or(false, |e| e == "slint") {
+            result.push(i_slint_compiler::pathutils::clean_path(&path));
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn find_slint_files(_: &Path, _: &mut Vec<PathBuf>, _: &mut HashSet<PathBuf>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::test::empty_document_cache;
+
+    #[test]
+    fn test_workspace_diagnostics() {
+        let dir =
+            std::env::temp_dir().join(format!("slint-lsp-diagnostics-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("ui/.hidden")).unwrap();
+        std::fs::write(
+            dir.join("ui/settings.slint"),
+            "export global Settings { in-out property <int> value; }",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("ui/app.slint"),
+            "import { Settings } from \"settings.slint\";\nexport component App { property <int> v: Settings.valu; }",
+        )
+        .unwrap();
+        std::fs::write(dir.join("ui/.hidden/broken.slint"), "export component {").unwrap();
+
+        // A symbolic link to a parent directory must not make the search loop forever
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&dir, dir.join("ui/loop")).unwrap();
+
+        let dc = RefCell::new(empty_document_cache());
+        let pull = |previous_result_ids: &[PreviousResultId]| {
+            let report = spin_on::spin_on(workspace_diagnostics(
+                &dc,
+                std::slice::from_ref(&dir),
+                previous_result_ids,
+            ));
+            let mut items = report
+                .items
+                .into_iter()
+                .map(|item| match item {
+                    WorkspaceDocumentDiagnosticReport::Full(item) => {
+                        let result_id = item.full_document_diagnostic_report.result_id.unwrap();
+                        (item.uri, result_id, Some(item.full_document_diagnostic_report.items))
+                    }
+                    WorkspaceDocumentDiagnosticReport::Unchanged(item) => {
+                        (item.uri, item.unchanged_document_diagnostic_report.result_id, None)
+                    }
+                })
+                .collect::<Vec<_>>();
+            items.sort_by(|a, b| a.0.cmp(&b.0));
+            items
+        };
+        let file_name = |uri: &Url| {
+            uri_to_file(uri).unwrap().file_name().unwrap().to_str().unwrap().to_string()
+        };
+
+        let items = pull(&[]);
+        assert_eq!(items.len(), 2);
+        assert_eq!(file_name(&items[0].0), "app.slint");
+        let app_diagnostics = items[0].2.as_ref().unwrap();
+        assert_eq!(app_diagnostics.len(), 1);
+        assert_eq!(app_diagnostics[0].range.start, lsp_types::Position::new(1, 50));
+        assert_eq!(file_name(&items[1].0), "settings.slint");
+        assert!(items[1].2.as_ref().unwrap().is_empty());
+
+        // The files that were only loaded for the check are not kept
+        assert_eq!(
+            dc.borrow().documents.all_files().filter(|p| !p.starts_with("builtin:/")).count(),
+            0
+        );
+
+        // Nothing changed: the client already has the current diagnostics
+        let previous_result_ids = items
+            .iter()
+            .map(|(uri, value, _)| PreviousResultId { uri: uri.clone(), value: value.clone() })
+            .collect::<Vec<_>>();
+        let unchanged = pull(&previous_result_ids);
+        assert!(unchanged.iter().all(|item| item.2.is_none()));
+        assert_eq!(
+            unchanged.iter().map(|item| &item.1).collect::<Vec<_>>(),
+            items.iter().map(|item| &item.1).collect::<Vec<_>>()
+        );
+
+        // Opening the imported file in the editor fixes the diagnostics of the importing file
+        let settings = items[1].0.clone();
+        spin_on::spin_on(crate::language::reload_document_impl(
+            None,
+            "export global Settings { in-out property <int> valu; }".into(),
+            settings.clone(),
+            1,
+            &mut dc.borrow_mut(),
+        ));
+        let changed = pull(&previous_result_ids);
+        assert_eq!(changed[0].2.as_ref().map(Vec::len), Some(0));
+        assert_ne!(changed[0].1, items[0].1);
+        // The diagnostics of the opened file itself didn't change
+        assert_eq!(changed[1].1, items[1].1);
+        assert!(changed[1].2.is_none());
+        assert!(dc.borrow().documents.get_document(&uri_to_file(&settings).unwrap()).is_some());
+
+        // The pulled diagnostics of a document are loaded again if needed
+        let app = Url::from_file_path(dir.join("ui/app.slint")).unwrap();
+        let report = spin_on::spin_on(document_diagnostics(&mut dc.borrow_mut(), &app));
+        assert_eq!(report.items.len(), 0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
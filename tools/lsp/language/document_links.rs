@@ -0,0 +1,96 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use super::DocumentCache;
+use crate::util::map_token;
+
+#[cfg(target_arch = "wasm32")]
+use crate::wasm_prelude::*;
+
+use i_slint_compiler::parser::{syntax_nodes, NodeOrToken, SyntaxKind, SyntaxToken};
+use lsp_types::{DocumentLink, Url};
+use std::path::PathBuf;
+
+/// Return links for the files of the `import` statements and of the `@image-url`
+pub fn get_document_links(
+    document_cache: &DocumentCache,
+    document: &syntax_nodes::Document,
+) -> Vec<DocumentLink> {
+    let mut result = Vec::new();
+    for node in document.descendants() {
+        let kind = node.kind();
+        if !matches!(kind, SyntaxKind::ImportSpecifier | SyntaxKind::AtImageUrl) {
+            continue;
+        }
+        let Some(token) = node
+            .children_with_tokens()
+            .filter_map(|t| t.into_token())
+            .find(|t| t.kind() == SyntaxKind::StringLiteral)
+        else {
+            continue;
+        };
+        let token = SyntaxToken { token, source_file: document.source_file.clone() };
+        let Some(path) = resolve_path(document_cache, &token, kind == SyntaxKind::AtImageUrl)
+        else {
+            continue;
+        };
+        let (Some(range), Ok(target)) = (map_token(&token), Url::from_file_path(&path)) else {
+            continue;
+        };
+        result.push(DocumentLink { range, target: Some(target), tooltip: None, data: None });
+    }
+    result
+}
+
+/// Resolve the path in the string literal the same way the compiler does, for imports or images
+fn resolve_path(
+    document_cache: &DocumentCache,
+    token: &SyntaxToken,
+    is_image: bool,
+) -> Option<PathBuf> {
+    let path = i_slint_compiler::literals::unescape_string(token.text())?;
+    if path.is_empty() {
+        return None;
+    }
+    if is_image && i_slint_compiler::pathutils::is_absolute(std::path::Path::new(&path)) {
+        return Some(PathBuf::from(path));
+    }
+    match document_cache
+        .documents
+        .resolve_import_path(Some(&NodeOrToken::Token(token.clone())), &path)
+    {
+        // Files that are built into the compiler can't be opened
+        Some((_, Some(_))) => None,
+        Some((path, None)) => Some(path),
+        None if is_image => i_slint_compiler::pathutils::join(
+            &i_slint_compiler::pathutils::dirname(token.source_file.path()),
+            std::path::Path::new(&path),
+        ),
+        None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::test::loaded_document_cache;
+    use crate::language::uri_to_file;
+
+    #[test]
+    fn test_document_links() {
+        let source = r#"import { Button } from "std-widgets.slint";
+import { Foo } from "../foo.slint";
+export component Main {
+    Image { source: @image-url("images/logo.png"); }
+    Image { source: @image-url(""); }
+}"#;
+        let (dc, uri, _) = loaded_document_cache(source.into());
+        let doc = dc.documents.get_document(&uri_to_file(&uri).unwrap()).unwrap();
+        let links = get_document_links(&dc, doc.node.as_ref().unwrap());
+        // The std-widgets are builtin, and foo.slint doesn't exist
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].range.start, lsp_types::Position::new(3, 31));
+        let target = uri_to_file(links[0].target.as_ref().unwrap()).unwrap();
+        assert!(target.ends_with("images/logo.png"));
+    }
+}
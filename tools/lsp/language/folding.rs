@@ -0,0 +1,218 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use crate::util::{map_position, map_range};
+
+use i_slint_compiler::diagnostics::SourceFile;
+use i_slint_compiler::parser::{syntax_nodes, NodeOrToken, SyntaxKind, SyntaxNode, TextSize};
+use lsp_types::{FoldingRange, FoldingRangeKind, SelectionRange};
+
+/// Return the folding ranges for the elements, states, transitions, code blocks and
+/// other bracketed constructs of the document, as well as for comments and imports.
+pub fn get_folding_ranges(document: &syntax_nodes::Document) -> Vec<FoldingRange> {
+    let sf = &document.source_file;
+    let line = |offset: TextSize| map_position(sf, offset).line;
+    let mut result = Vec::new();
+    let mut push = |start: u32, end: u32, kind: Option<FoldingRangeKind>| {
+        if end > start {
+            result.push(FoldingRange {
+                start_line: start,
+                start_character: None,
+                end_line: end,
+                end_character: None,
+                kind,
+                collapsed_text: None,
+            });
+        }
+    };
+
+    // The current run of consecutive `//` comments or of imports, as start and end offsets
+    let mut comment_run: Option<(TextSize, TextSize)> = None;
+    let mut import_run: Option<(TextSize, TextSize)> = None;
+
+    for n in document.descendants_with_tokens() {
+        match n {
+            rowan::NodeOrToken::Token(t) => match t.kind() {
+                SyntaxKind::Comment if t.text().starts_with("/*") => {
+                    push(
+                        line(t.text_range().start()),
+                        line(t.text_range().end()),
+                        Some(FoldingRangeKind::Comment),
+                    );
+                }
+                SyntaxKind::Comment => {
+                    let r = t.text_range();
+                    comment_run = match comment_run {
+                        Some((start, end)) if line(end) + 1 == line(r.start()) => {
+                            Some((start, r.end()))
+                        }
+                        run => {
+                            if let Some((start, end)) = run {
+                                push(line(start), line(end), Some(FoldingRangeKind::Comment));
+                            }
+                            Some((r.start(), r.end()))
+                        }
+                    };
+                }
+                _ => {}
+            },
+            rowan::NodeOrToken::Node(n) => match n.kind() {
+                SyntaxKind::ImportSpecifier => {
+                    let r = n.text_range();
+                    import_run = match import_run {
+                        Some((start, end)) if line(end) + 1 >= line(r.start()) => {
+                            Some((start, r.end()))
+                        }
+                        run => {
+                            if let Some((start, end)) = run {
+                                push(line(start), line(end), Some(FoldingRangeKind::Imports));
+                            }
+                            Some((r.start(), r.end()))
+                        }
+                    };
+                }
+                SyntaxKind::Element
+                | SyntaxKind::States
+                | SyntaxKind::State
+                | SyntaxKind::Transitions
+                | SyntaxKind::Transition
+                | SyntaxKind::PropertyAnimation
+                | SyntaxKind::CodeBlock
+                | SyntaxKind::ObjectLiteral
+                | SyntaxKind::ObjectType
+                | SyntaxKind::Array
+                | SyntaxKind::EnumDeclaration => {
+                    let node = SyntaxNode { node: n, source_file: sf.clone() };
+                    if let Some((start, end)) = bracket_lines(&node, sf) {
+                        push(start, end, None);
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+    if let Some((start, end)) = comment_run {
+        push(line(start), line(end), Some(FoldingRangeKind::Comment));
+    }
+    if let Some((start, end)) = import_run {
+        push(line(start), line(end), Some(FoldingRangeKind::Imports));
+    }
+    result
+}
+
+/// The lines to fold for a node with brackets: from the line of the opening bracket to the
+/// line before the closing one, so that the closing bracket stays visible.
+fn bracket_lines(node: &SyntaxNode, sf: &SourceFile) -> Option<(u32, u32)> {
+    let tokens = || node.children_with_tokens().filter_map(NodeOrToken::into_token);
+    let open = tokens().find(|t| matches!(t.kind(), SyntaxKind::LBrace | SyntaxKind::LBracket))?;
+    let close =
+        tokens().filter(|t| matches!(t.kind(), SyntaxKind::RBrace | SyntaxKind::RBracket)).last();
+    let start = map_position(sf, open.text_range().start()).line;
+    let end = match close {
+        Some(close) => map_position(sf, close.text_range().start()).line.saturating_sub(1),
+        None => map_position(sf, node.text_range().end()).line,
+    };
+    Some((start, end))
+}
+
+/// Return the selection ranges for the offsets, from the token at the offset to the whole document.
+pub fn get_selection_ranges(
+    document: &syntax_nodes::Document,
+    offsets: impl IntoIterator<Item = u32>,
+) -> Vec<SelectionRange> {
+    let sf = &document.source_file;
+    offsets
+        .into_iter()
+        .map(|offset| {
+            let mut ranges = Vec::new();
+            if let Some(token) = crate::language::token_at_offset(document, offset) {
+                ranges.push(token.text_range());
+                let mut node = Some(token.parent());
+                while let Some(n) = node {
+                    if ranges.last() != Some(&n.text_range()) {
+                        ranges.push(n.text_range());
+                    }
+                    node = n.parent();
+                }
+            }
+            ranges.into_iter().rev().fold(None, |parent, range| {
+                Some(SelectionRange { range: map_range(sf, range), parent: parent.map(Box::new) })
+            })
+        })
+        .map(|r| {
+            r.unwrap_or_else(|| {
+                let pos = map_position(sf, 0.into());
+                SelectionRange { range: lsp_types::Range::new(pos, pos), parent: None }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::test::loaded_document_cache;
+    use crate::language::uri_to_file;
+
+    #[test]
+    fn test_folding_ranges() {
+        let source = r#"import { Button } from "std-widgets.slint";
+import { LineEdit } from "std-widgets.slint";
+// A comment
+// on two lines
+export component Main {
+    callback clicked;
+    clicked => {
+        debug("hello");
+    }
+    Rectangle { }
+    states [
+        pressed when true: {
+            background: red;
+        }
+    ]
+}"#;
+        let (dc, uri, _) = loaded_document_cache(source.into());
+        let doc = dc.documents.get_document(&uri_to_file(&uri).unwrap()).unwrap();
+        let mut ranges = get_folding_ranges(doc.node.as_ref().unwrap())
+            .into_iter()
+            .map(|r| (r.start_line, r.end_line, r.kind))
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|r| (r.0, r.1));
+        assert_eq!(
+            ranges,
+            vec![
+                (0, 1, Some(FoldingRangeKind::Imports)),
+                (2, 3, Some(FoldingRangeKind::Comment)),
+                (4, 14, None),
+                (6, 7, None),
+                (10, 13, None),
+                (11, 12, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_selection_ranges() {
+        let source = "component Main {\n    Text { text: \"hello\" + 42; }\n}";
+        let (dc, uri, _) = loaded_document_cache(source.into());
+        let doc = dc.documents.get_document(&uri_to_file(&uri).unwrap()).unwrap();
+        let offset = source.find("42").unwrap() as u32;
+        let ranges = get_selection_ranges(doc.node.as_ref().unwrap(), [offset]);
+        let sf = &doc.node.as_ref().unwrap().source_file;
+        assert_eq!(ranges.len(), 1);
+        let mut texts = Vec::new();
+        let mut r = Some(&ranges[0]);
+        while let Some(range) = r {
+            let offset =
+                |p: lsp_types::Position| sf.offset(p.line as usize + 1, p.character as usize + 1);
+            let (start, end) = (offset(range.range.start), offset(range.range.end));
+            texts.push(source[start..end].to_string());
+            r = range.parent.as_deref();
+        }
+        assert_eq!(texts.first().map(String::as_str), Some("42"));
+        assert!(texts.contains(&"\"hello\" + 42".to_string()));
+        assert!(texts.contains(&"Text { text: \"hello\" + 42; }".to_string()));
+        assert_eq!(texts.last().map(String::as_str), Some(source));
+    }
+}
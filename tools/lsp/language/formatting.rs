@@ -0,0 +1,190 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use super::{uri_to_file, DocumentCache};
+use crate::util::map_range;
+use i_slint_compiler::fmt::{self, writer::TokenWriter};
+
+use i_slint_compiler::diagnostics::BuildDiagnostics;
+use i_slint_compiler::parser::{syntax_nodes, SyntaxKind, SyntaxToken, TextRange, TextSize};
+use lsp_types::{Position, Range, TextEdit, Url};
+
+/// A TokenWriter that records the changes instead of writing the whole file
+#[derive(Default)]
+struct TextEditWriter {
+    edits: Vec<(TextRange, String)>,
+}
+
+impl TextEditWriter {
+    fn add_edit(&mut self, range: TextRange, contents: &str) {
+        // merge with the previous edit if they touch each other, to produce fewer edits
+        if let Some((last_range, last_contents)) = self.edits.last_mut() {
+            if last_range.end() == range.start() {
+                *last_range = last_range.cover(range);
+                *last_contents += contents;
+                return;
+            }
+        }
+        self.edits.push((range, contents.into()));
+    }
+}
+
+impl TokenWriter for TextEditWriter {
+    fn no_change(&mut self, _token: SyntaxToken) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn with_new_content(&mut self, token: SyntaxToken, contents: &str) -> std::io::Result<()> {
+        if token.text() != contents {
+            self.add_edit(token.text_range(), contents);
+        }
+        Ok(())
+    }
+
+    fn insert_before(&mut self, token: SyntaxToken, contents: &str) -> std::io::Result<()> {
+        if !contents.is_empty() {
+            self.add_edit(TextRange::empty(token.text_range().start()), contents);
+        }
+        Ok(())
+    }
+}
+
+/// Format the document and return the edits.
+///
+/// If `range` is set, only the edits that are fully within that range are returned.
+/// Documents with syntax errors are not formatted.
+pub fn format_document(
+    document_cache: &DocumentCache,
+    uri: &Url,
+    range: Option<Range>,
+) -> Option<Vec<TextEdit>> {
+    let path = uri_to_file(uri)?;
+    let doc = document_cache.documents.get_document(&path)?;
+    let source = doc.node.as_ref()?.text().to_string();
+
+    // Parse again, as the formatter must not be run on a document that has errors
+    let mut diag = BuildDiagnostics::default();
+    let node = i_slint_compiler::parser::parse(source, Some(&path), &mut diag);
+    if diag.has_error() {
+        return None;
+    }
+    let sf = node.source_file.clone();
+    let doc = syntax_nodes::Document::new(node)?;
+
+    let mut writer = TextEditWriter::default();
+    fmt::format_document(doc, &mut writer).ok()?;
+
+    let to_offset = |pos: Position| -> TextSize {
+        (sf.offset(pos.line as usize + 1, pos.character as usize + 1) as u32).into()
+    };
+    let range = range.map(|r| TextRange::new(to_offset(r.start), to_offset(r.end)));
+
+    Some(
+        writer
+            .edits
+            .into_iter()
+            .filter(|(r, _)| {
+                // insertions at the end of the range belong to the token after the range
+                range.map_or(true, |range| range.contains_range(*r) && r.start() < range.end())
+            })
+            .map(|(r, new_text)| TextEdit { range: map_range(&sf, r), new_text })
+            .collect(),
+    )
+}
+
+/// Format the node that was terminated by the character `ch` typed at `position`.
+///
+/// Typing a `}` formats the block it closes, and typing a `;` formats the statement it ends.
+pub fn format_on_type(
+    document_cache: &DocumentCache,
+    uri: &Url,
+    position: Position,
+    ch: &str,
+) -> Option<Vec<TextEdit>> {
+    let path = uri_to_file(uri)?;
+    let doc = document_cache.documents.get_document(&path)?;
+    let node = doc.node.as_ref()?;
+    let offset =
+        node.source_file.offset(position.line as usize + 1, position.character as usize + 1);
+    // The position is after the character that was just typed
+    let token = node.token_at_offset((offset.checked_sub(1)? as u32).into()).right_biased()?;
+    let expected = match ch {
+        "}" => SyntaxKind::RBrace,
+        ";" => SyntaxKind::Semicolon,
+        _ => return None,
+    };
+    if token.kind() != expected {
+        return None;
+    }
+    let range = map_range(&node.source_file, token.parent()?.text_range());
+    format_document(document_cache, uri, Some(range))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::test::loaded_document_cache;
+
+    /// Apply the edits to the source, assuming that they are sorted and do not overlap
+    fn apply_edits(source: &str, edits: Vec<TextEdit>) -> String {
+        let lines: Vec<usize> =
+            std::iter::once(0).chain(source.match_indices('\n').map(|(i, _)| i + 1)).collect();
+        let offset = |p: Position| lines[p.line as usize] + p.character as usize;
+        let mut result = String::new();
+        let mut last = 0;
+        for edit in edits {
+            let start = offset(edit.range.start);
+            assert!(start >= last);
+            result += &source[last..start];
+            result += &edit.new_text;
+            last = offset(edit.range.end);
+        }
+        result += &source[last..];
+        result
+    }
+
+    #[test]
+    fn test_format_document() {
+        let source = "component Foo {\nproperty<int> bar:  42;\n   Text {text:\"hello\";}\n}\n";
+        let (dc, uri, _) = loaded_document_cache(source.into());
+        let edits = format_document(&dc, &uri, None).unwrap();
+        assert_eq!(
+            apply_edits(source, edits),
+            "component Foo {\n    property <int> bar:  42;\n    Text {\n        text: \"hello\";\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_format_range() {
+        let source = "component Foo {\nproperty<int> bar:  42;\nproperty<int> baz:  42;\n}\n";
+        let (dc, uri, _) = loaded_document_cache(source.into());
+        let range = Range::new(Position::new(2, 0), Position::new(2, 23));
+        let edits = format_document(&dc, &uri, Some(range)).unwrap();
+        assert!(edits.iter().all(|e| e.range.start.line == 2 && e.range.end.line == 2));
+        assert_eq!(
+            apply_edits(source, edits),
+            "component Foo {\nproperty<int> bar:  42;\nproperty <int> baz:  42;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_format_on_type() {
+        let source = "component Foo {\n    Text {text:\"hello\";}\n    Rectangle {   }\n}\n";
+        let (dc, uri, _) = loaded_document_cache(source.into());
+        // after the `;`
+        let edits = format_on_type(&dc, &uri, Position::new(1, 23), ";").unwrap();
+        assert_eq!(
+            apply_edits(source, edits),
+            "component Foo {\n    Text {text: \"hello\";}\n    Rectangle {   }\n}\n"
+        );
+        // Not after a `}`
+        assert!(format_on_type(&dc, &uri, Position::new(1, 23), "}").is_none());
+    }
+
+    #[test]
+    fn test_no_format_with_errors() {
+        let source = "component Foo {\nproperty<int> bar:  42\n}\n";
+        let (dc, uri, _) = loaded_document_cache(source.into());
+        assert!(format_document(&dc, &uri, None).is_none());
+    }
+}
@@ -18,6 +18,11 @@ pub fn goto_definition(
     document_cache: &mut DocumentCache,
     token: SyntaxToken,
 ) -> Option<GotoDefinitionResponse> {
+    goto_node(&find_definition(document_cache, token)?)
+}
+
+/// Return the node declaring the symbol under the token
+pub fn find_definition(document_cache: &DocumentCache, token: SyntaxToken) -> Option<SyntaxNode> {
     let mut node = token.parent();
     loop {
         if let Some(n) = syntax_nodes::QualifiedName::new(node.clone()) {
@@ -27,8 +32,8 @@ pub fn goto_definition(
                     let qual = i_slint_compiler::object_tree::QualifiedTypeName::from_node(n);
                     let doc = document_cache.documents.get_document(node.source_file.path())?;
                     match doc.local_registry.lookup_qualified(&qual.members) {
-                        Type::Struct { node: Some(node), .. } => goto_node(node.parent().as_ref()?),
-                        Type::Enumeration(e) => goto_node(e.node.as_ref()?),
+                        Type::Struct { node: Some(node), .. } => node.parent(),
+                        Type::Enumeration(e) => e.node.clone().map(Into::into),
                         _ => None,
                     }
                 }
@@ -37,7 +42,7 @@ pub fn goto_definition(
                     let doc = document_cache.documents.get_document(node.source_file.path())?;
                     match doc.local_registry.lookup_element(&qual.to_string()) {
                         Ok(ElementType::Component(c)) => {
-                            goto_node(c.root_element.borrow().node.as_ref()?)
+                            c.root_element.borrow().node.clone().map(Into::into)
                         }
                         _ => None,
                     }
@@ -46,25 +51,8 @@ pub fn goto_definition(
                     if token.kind() != SyntaxKind::Identifier {
                         return None;
                     }
-                    let lr = with_lookup_ctx(document_cache, node, |ctx| {
-                        let mut it = n
-                            .children_with_tokens()
-                            .filter_map(|t| t.into_token())
-                            .filter(|t| t.kind() == SyntaxKind::Identifier);
-                        let mut cur_tok = it.next()?;
-                        let first_str =
-                            i_slint_compiler::parser::normalize_identifier(cur_tok.text());
-                        let global = i_slint_compiler::lookup::global_lookup();
-                        let mut expr_it = global.lookup(ctx, &first_str)?;
-                        while cur_tok.token != token.token {
-                            cur_tok = it.next()?;
-                            let str =
-                                i_slint_compiler::parser::normalize_identifier(cur_tok.text());
-                            expr_it = expr_it.lookup(ctx, &str)?;
-                        }
-                        Some(expr_it)
-                    })?;
-                    let gn = match lr? {
+                    let lr = lookup_expression(document_cache, &token, &n)?;
+                    let gn = match lr {
                         LookupResult::Expression {
                             expression: Expression::ElementReference(e),
                             ..
@@ -99,7 +87,7 @@ pub fn goto_definition(
                         LookupResult::Enumeration(e) => e.node.clone()?.into(),
                         _ => return None,
                     };
-                    goto_node(&gn)
+                    Some(gn)
                 }
                 _ => None,
             };
@@ -107,9 +95,24 @@ pub fn goto_definition(
             let doc = document_cache.documents.get_document(node.source_file.path())?;
             let imp_name = i_slint_compiler::typeloader::ImportedName::from_node(n);
             return match doc.local_registry.lookup_element(&imp_name.internal_name) {
-                Ok(ElementType::Component(c)) => goto_node(c.root_element.borrow().node.as_ref()?),
+                Ok(ElementType::Component(c)) => {
+                    c.root_element.borrow().node.clone().map(Into::into)
+                }
                 _ => None,
             };
+        } else if let Some(n) = syntax_nodes::ExportSpecifier::new(node.clone()) {
+            let doc = document_cache.documents.get_document(node.source_file.path())?;
+            let name = i_slint_compiler::parser::identifier_text(&n.ExportIdentifier())?;
+            return match doc.local_registry.lookup_element(&name) {
+                Ok(ElementType::Component(c)) => {
+                    c.root_element.borrow().node.clone().map(Into::into)
+                }
+                _ => match doc.local_registry.lookup(&name) {
+                    Type::Struct { node: Some(node), .. } => node.parent(),
+                    Type::Enumeration(e) => e.node.clone().map(Into::into),
+                    _ => None,
+                },
+            };
         } else if let Some(n) = syntax_nodes::ImportSpecifier::new(node.clone()) {
             let import_file = node
                 .source_file
@@ -120,7 +123,7 @@ pub fn goto_definition(
             let import_file = clean_path(&import_file);
             let doc = document_cache.documents.get_document(&import_file)?;
             let doc_node = doc.node.clone()?;
-            return goto_node(&doc_node);
+            return Some(doc_node.into());
         } else if syntax_nodes::BindingExpression::new(node.clone()).is_some() {
             // don't fallback to the Binding
             return None;
@@ -134,10 +137,10 @@ pub fn goto_definition(
                 (i_slint_compiler::parser::identifier_text(&p.DeclaredIdentifier())? == prop_name)
                     .then_some(p)
             }) {
-                return goto_node(&p);
+                return Some(p.into());
             }
             let n = find_property_declaration_in_base(document_cache, element, prop_name)?;
-            return goto_node(&n);
+            return Some(n);
         } else if let Some(n) = syntax_nodes::TwoWayBinding::new(node.clone()) {
             if token.kind() != SyntaxKind::Identifier {
                 return None;
@@ -151,10 +154,10 @@ pub fn goto_definition(
                 (i_slint_compiler::parser::identifier_text(&p.DeclaredIdentifier())? == prop_name)
                     .then_some(p)
             }) {
-                return goto_node(&p);
+                return Some(p.into());
             }
             let n = find_property_declaration_in_base(document_cache, element, prop_name)?;
-            return goto_node(&n);
+            return Some(n);
         } else if let Some(n) = syntax_nodes::CallbackConnection::new(node.clone()) {
             if token.kind() != SyntaxKind::Identifier {
                 return None;
@@ -168,17 +171,41 @@ pub fn goto_definition(
                 (i_slint_compiler::parser::identifier_text(&p.DeclaredIdentifier())? == prop_name)
                     .then_some(p)
             }) {
-                return goto_node(&p);
+                return Some(p.into());
             }
             let n = find_property_declaration_in_base(document_cache, element, prop_name)?;
-            return goto_node(&n);
+            return Some(n);
         }
         node = node.parent()?;
     }
 }
 
+/// Lookup the expression in the qualified name `n` up to the `token`
+pub(super) fn lookup_expression(
+    document_cache: &DocumentCache,
+    token: &SyntaxToken,
+    n: &syntax_nodes::QualifiedName,
+) -> Option<LookupResult> {
+    with_lookup_ctx(document_cache, (**n).clone(), |ctx| {
+        let mut it = n
+            .children_with_tokens()
+            .filter_map(|t| t.into_token())
+            .filter(|t| t.kind() == SyntaxKind::Identifier);
+        let mut cur_tok = it.next()?;
+        let first_str = i_slint_compiler::parser::normalize_identifier(cur_tok.text());
+        let global = i_slint_compiler::lookup::global_lookup();
+        let mut expr_it = global.lookup(ctx, &first_str)?;
+        while cur_tok.token != token.token {
+            cur_tok = it.next()?;
+            let str = i_slint_compiler::parser::normalize_identifier(cur_tok.text());
+            expr_it = expr_it.lookup(ctx, &str)?;
+        }
+        Some(expr_it)
+    })?
+}
+
 /// Try to lookup the property `prop_name` in the base of the given Element
-fn find_property_declaration_in_base(
+pub(super) fn find_property_declaration_in_base(
     document_cache: &DocumentCache,
     element: syntax_nodes::Element,
     prop_name: &str,
@@ -0,0 +1,387 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use super::goto::lookup_expression;
+use super::DocumentCache;
+use crate::util::map_token;
+
+use i_slint_compiler::expression_tree::{Expression, Unit};
+use i_slint_compiler::langtype::{ElementType, Type};
+use i_slint_compiler::lookup::LookupResult;
+use i_slint_compiler::object_tree::{ElementRc, PropertyVisibility};
+use i_slint_compiler::parser::{doc_comment, syntax_nodes, SyntaxKind, SyntaxNode, SyntaxToken};
+use i_slint_compiler::passes::const_propagation::simplified_constant;
+
+use lsp_types::{Hover, HoverContents, MarkupContent, MarkupKind};
+
+pub fn get_tooltip(document_cache: &mut DocumentCache, token: SyntaxToken) -> Option<Hover> {
+    if token.kind() != SyntaxKind::Identifier {
+        return None;
+    }
+    let node = token.parent();
+    let content = match node.kind() {
+        SyntaxKind::QualifiedName => {
+            let n = syntax_nodes::QualifiedName::new(node.clone())?;
+            match n.parent()?.kind() {
+                SyntaxKind::Type => {
+                    let qual = i_slint_compiler::object_tree::QualifiedTypeName::from_node(n);
+                    let doc = document_cache.documents.get_document(node.source_file.path())?;
+                    type_tooltip(&doc.local_registry.lookup_qualified(&qual.members))?
+                }
+                SyntaxKind::Element => {
+                    let qual = i_slint_compiler::object_tree::QualifiedTypeName::from_node(n);
+                    let doc = document_cache.documents.get_document(node.source_file.path())?;
+                    element_type_tooltip(
+                        &doc.local_registry.lookup_element(&qual.to_string()).ok()?,
+                    )?
+                }
+                SyntaxKind::Expression => match lookup_expression(document_cache, &token, &n)? {
+                    LookupResult::Expression { expression, .. } => {
+                        expression_tooltip(&expression, &token)?
+                    }
+                    LookupResult::Enumeration(e) => type_tooltip(&Type::Enumeration(e))?,
+                    LookupResult::Namespace(_) => return None,
+                },
+                _ => return None,
+            }
+        }
+        SyntaxKind::DeclaredIdentifier => {
+            let parent = node.parent()?;
+            match parent.kind() {
+                SyntaxKind::Component => {
+                    let doc = document_cache.documents.get_document(node.source_file.path())?;
+                    element_type_tooltip(
+                        &doc.local_registry.lookup_element(&normalized_text(&token)).ok()?,
+                    )?
+                }
+                SyntaxKind::PropertyDeclaration
+                | SyntaxKind::CallbackDeclaration
+                | SyntaxKind::Function => {
+                    let element = syntax_nodes::Element::new(parent.parent()?)?;
+                    let element = find_element(document_cache, &element)?;
+                    property_tooltip(&element, &normalized_text(&token))?
+                }
+                _ => return None,
+            }
+        }
+        SyntaxKind::Binding | SyntaxKind::TwoWayBinding | SyntaxKind::CallbackConnection => {
+            if node.child_token(SyntaxKind::Identifier)?.text_range() != token.text_range() {
+                return None;
+            }
+            let element = syntax_nodes::Element::new(node.parent()?)?;
+            let element = find_element(document_cache, &element)?;
+            property_tooltip(&element, &normalized_text(&token))?
+        }
+        _ => return None,
+    };
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: content,
+        }),
+        range: map_token(&token),
+    })
+}
+
+fn normalized_text(token: &SyntaxToken) -> String {
+    i_slint_compiler::parser::normalize_identifier(token.text())
+}
+
+/// Find the ElementRc for the given syntax node in the loaded documents
+fn find_element(
+    document_cache: &DocumentCache,
+    element_node: &syntax_nodes::Element,
+) -> Option<ElementRc> {
+    fn recurse(element: &ElementRc, range: rowan::TextRange) -> Option<ElementRc> {
+        if element.borrow().node.as_ref().map_or(false, |n| n.text_range() == range) {
+            return Some(element.clone());
+        }
+        element.borrow().children.iter().find_map(|c| recurse(c, range))
+    }
+
+    let doc = document_cache.documents.get_document(element_node.source_file.path())?;
+    doc.inner_components.iter().find_map(|c| recurse(&c.root_element, element_node.text_range()))
+}
+
+fn code_block(code: &str) -> String {
+    format!("```slint\n{code}\n```")
+}
+
+fn with_documentation(mut content: String, node: Option<&SyntaxNode>) -> String {
+    if let Some(doc) = node.and_then(doc_comment) {
+        content += "\n\n";
+        content += &doc;
+    }
+    content
+}
+
+fn type_tooltip(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Struct { name: Some(name), fields, node: Some(node), .. } => {
+            let fields = fields.iter().map(|(k, v)| format!("    {k}: {v},\n")).collect::<String>();
+            Some(with_documentation(
+                code_block(&format!("struct {name} {{\n{fields}}}")),
+                node.parent().as_ref(),
+            ))
+        }
+        Type::Enumeration(e) => Some(with_documentation(
+            code_block(&format!("enum {} {{ {} }}", e.name, e.values.join(", "))),
+            e.node.as_deref(),
+        )),
+        _ => None,
+    }
+}
+
+fn element_type_tooltip(ty: &ElementType) -> Option<String> {
+    match ty {
+        ElementType::Component(c) => {
+            let root = c.root_element.borrow();
+            let signature = if c.is_global() {
+                format!("global {}", c.id)
+            } else {
+                match &root.base_type {
+                    ElementType::Component(b) => format!("component {} inherits {}", c.id, b.id),
+                    ElementType::Builtin(b) => format!("component {} inherits {}", c.id, b.name),
+                    _ => format!("component {}", c.id),
+                }
+            };
+            Some(with_documentation(
+                code_block(&signature),
+                root.node.as_ref().and_then(|n| n.parent()).as_ref(),
+            ))
+        }
+        ElementType::Builtin(b) => {
+            let mut content = code_block(&b.name);
+            if let Some(doc) = &b.documentation {
+                content += "\n\n";
+                content += doc;
+            }
+            Some(content)
+        }
+        _ => None,
+    }
+}
+
+fn expression_tooltip(expression: &Expression, token: &SyntaxToken) -> Option<String> {
+    match expression {
+        Expression::PropertyReference(nr)
+        | Expression::CallbackReference(nr, _)
+        | Expression::FunctionReference(nr, _) => property_tooltip(&nr.element(), nr.name()),
+        Expression::ElementReference(e) => {
+            let e = e.upgrade()?;
+            let e = e.borrow();
+            let content = code_block(&format!("{} := {}", e.id, e.base_type));
+            Some(with_documentation(content, e.node.as_deref()))
+        }
+        Expression::BuiltinFunctionReference(f, _) => {
+            Some(code_block(&function_signature(token.text(), &f.ty())))
+        }
+        Expression::EnumerationValue(v) => Some(code_block(&v.to_string())),
+        e => Some(code_block(&format!("{}: {}", token.text(), e.ty()))),
+    }
+}
+
+/// Format callback and function types as they would be declared
+fn function_signature(name: &str, ty: &Type) -> String {
+    match ty {
+        Type::Callback { args, return_type } => {
+            let args = args.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", ");
+            match return_type {
+                Some(r) => format!("callback {name}({args}) -> {r}"),
+                None => format!("callback {name}({args})"),
+            }
+        }
+        Type::Function { args, return_type } => {
+            let args = args.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", ");
+            if matches!(**return_type, Type::Void) {
+                format!("function {name}({args})")
+            } else {
+                format!("function {name}({args}) -> {return_type}")
+            }
+        }
+        ty => format!("{name}: {ty}"),
+    }
+}
+
+fn property_tooltip(element: &ElementRc, name: &str) -> Option<String> {
+    let lookup = element.borrow().lookup_property(name);
+    if lookup.property_type == Type::Invalid {
+        return None;
+    }
+
+    let declaration_node = find_declaration_node(element, name);
+
+    let signature = match &lookup.property_type {
+        ty @ (Type::Callback { .. } | Type::Function { .. }) => {
+            let sig = function_signature(&lookup.resolved_name, ty);
+            match lookup.declared_pure {
+                Some(true) => format!("pure {sig}"),
+                _ => sig,
+            }
+        }
+        ty => {
+            let visibility = match lookup.property_visibility {
+                PropertyVisibility::Input => "in ",
+                PropertyVisibility::Output => "out ",
+                PropertyVisibility::InOut => "in-out ",
+                _ => "",
+            };
+            format!("{visibility}property <{ty}> {}", lookup.resolved_name)
+        }
+    };
+    let mut content = with_documentation(code_block(&signature), declaration_node.as_ref());
+
+    if let Some(value) = constant_value(element, &lookup.resolved_name) {
+        content += &format!("\n\nValue: `{value}`");
+    }
+    Some(content)
+}
+
+/// Find the node of the declaration of a property, callback or function, which can be in
+/// a base component
+pub(super) fn find_declaration_node(element: &ElementRc, name: &str) -> Option<SyntaxNode> {
+    let mut e = element.clone();
+    loop {
+        if let Some(decl) = e.borrow().property_declarations.get(name) {
+            return decl.node.clone();
+        }
+        let base = match &e.borrow().base_type {
+            ElementType::Component(c) => c.root_element.clone(),
+            _ => return None,
+        };
+        e = base;
+    }
+}
+
+/// Return the value of the binding of the property if it can be computed at compile time
+fn constant_value(element: &ElementRc, name: &str) -> Option<String> {
+    let mut e = element.clone();
+    let expression = loop {
+        if let Some(b) = e.borrow().bindings.get(name) {
+            let b = b.borrow();
+            if !b.two_way_bindings.is_empty() {
+                return None;
+            }
+            break b.expression.clone();
+        }
+        let base = match &e.borrow().base_type {
+            ElementType::Component(c) => c.root_element.clone(),
+            _ => return None,
+        };
+        e = base;
+    };
+    format_constant(&simplified_constant(&expression)?)
+}
+
+fn format_constant(expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::StringLiteral(s) => Some(format!("{s:?}")),
+        Expression::NumberLiteral(n, Unit::None) => Some(n.to_string()),
+        Expression::NumberLiteral(n, unit) => Some(format!("{n}{unit}")),
+        Expression::BoolLiteral(b) => Some(b.to_string()),
+        Expression::EnumerationValue(v) => Some(v.to_string()),
+        Expression::Cast { from, to: Type::Color | Type::Brush } => match &**from {
+            Expression::NumberLiteral(n, Unit::None) => Some(format!("#{:08x}", *n as u32)),
+            Expression::Cast { .. } => format_constant(from),
+            _ => None,
+        },
+        Expression::Cast { from, .. } => format_constant(from),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::uri_to_file;
+
+    /// The cursor is at the position of the `🔺` emoji
+    fn get_hover(file: &str) -> Option<String> {
+        const CURSOR_EMOJI: char = '🔺';
+        let offset = file.find(CURSOR_EMOJI).unwrap() as u32;
+        let source = file.replace(CURSOR_EMOJI, "");
+        let (mut dc, uri, _) = crate::language::test::loaded_document_cache(source);
+
+        let doc = dc.documents.get_document(&uri_to_file(&uri).unwrap()).unwrap();
+        let token = crate::language::token_at_offset(doc.node.as_ref().unwrap(), offset)?;
+        match get_tooltip(&mut dc, token)?.contents {
+            HoverContents::Markup(m) => Some(m.value),
+            _ => panic!("unexpected hover content"),
+        }
+    }
+
+    #[test]
+    fn property() {
+        let source = r#"
+            component Foo {
+                // The answer
+                in property <int> answer: 6 * 7;
+                property <string> s: 🔺answer + "";
+            }
+        "#;
+        let res = get_hover(source).unwrap();
+        assert!(res.contains("in property <int> answer"), "{res}");
+        assert!(res.contains("The answer"), "{res}");
+        assert!(res.contains("Value: `42`"), "{res}");
+    }
+
+    #[test]
+    fn binding_of_builtin_property() {
+        let source = r#"
+            component Foo {
+                Rectangle { 🔺background: #ff0000; }
+            }
+        "#;
+        let res = get_hover(source).unwrap();
+        assert!(res.contains("in property <brush> background"), "{res}");
+        assert!(res.contains("Value: `#ffff0000`"), "{res}");
+    }
+
+    #[test]
+    fn callback_and_function() {
+        let source = r#"
+            component Foo {
+                callback clicked(int, string) -> bool;
+                pure function compute(a: int) -> int { a * 2 }
+                property <int> p: 🔺compute(1);
+            }
+        "#;
+        let res = get_hover(source).unwrap();
+        assert!(res.contains("pure function compute(int) -> int"), "{res}");
+        let res = get_hover(&source.replace("🔺", "").replace("clicked(", "🔺clicked(")).unwrap();
+        assert!(res.contains("callback clicked(int, string) -> bool"), "{res}");
+    }
+
+    #[test]
+    fn components_and_types() {
+        let source = r#"
+            /// A nice button
+            component MyButton inherits Rectangle { }
+
+            // not the doc
+
+            /// The pair
+            struct Pair { a: int, b: string }
+            export component Foo {
+                property <Pair> pair;
+                🔺MyButton {}
+            }
+        "#;
+        let res = get_hover(source).unwrap();
+        assert!(res.contains("component MyButton inherits Rectangle"), "{res}");
+        assert!(res.contains("A nice button"), "{res}");
+
+        let res = get_hover(&source.replace("🔺", "").replace("<Pair>", "<🔺Pair>")).unwrap();
+        assert!(res.contains("struct Pair {"), "{res}");
+        assert!(res.contains("The pair"), "{res}");
+        assert!(!res.contains("not the doc"), "{res}");
+
+        let res = get_hover(&source.replace("🔺", "").replace("inherits Rect", "inherits 🔺Rect"))
+            .unwrap();
+        assert!(res.contains("Draws a rectangle"), "{res}");
+
+        let res = get_hover(&source.replace("🔺MyButton", "🔺Text")).unwrap();
+        assert!(res.contains("Displays a piece of text"), "{res}");
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use super::signature_help::lookup_callee;
+use super::{uri_to_file, DocumentCache};
+use crate::util::map_position;
+
+use i_slint_compiler::expression_tree::Expression;
+use i_slint_compiler::langtype::Type;
+use i_slint_compiler::object_tree::ElementRc;
+use i_slint_compiler::parser::{
+    normalize_identifier, syntax_nodes, SyntaxKind, SyntaxNode, TextRange, TextSize,
+};
+use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, Range, Url};
+
+/// Return the inlay hints within the range of the document:
+///  - The type of the properties and callbacks whose type is inferred from a two way binding
+///  - The unit of literal zeros in bindings of properties with an unit
+///  - The type of the struct literals passed as argument to callbacks and functions
+pub fn get_inlay_hints(
+    document_cache: &DocumentCache,
+    uri: &Url,
+    range: &Range,
+) -> Option<Vec<InlayHint>> {
+    let path = uri_to_file(uri)?;
+    let doc = document_cache.documents.get_document(&path)?;
+    let doc_node = doc.node.as_ref()?;
+    let sf = &doc_node.source_file;
+    let to_offset = |pos: lsp_types::Position| -> TextSize {
+        (sf.offset(pos.line as usize + 1, pos.character as usize + 1) as u32).into()
+    };
+    let range = TextRange::new(to_offset(range.start), to_offset(range.end));
+
+    let mut hints = Vec::new();
+    for component in &doc.inner_components {
+        element_hints(&component.root_element, &mut hints);
+    }
+    for node in doc_node.descendants() {
+        let node = SyntaxNode { node, source_file: sf.clone() };
+        if let Some(call) = syntax_nodes::FunctionCallExpression::new(node) {
+            struct_argument_hints(document_cache, &call, &mut hints);
+        }
+    }
+
+    Some(
+        hints
+            .into_iter()
+            .filter(|(offset, _)| range.contains_inclusive(*offset))
+            .map(|(offset, hint)| InlayHint { position: map_position(sf, offset), ..hint })
+            .collect(),
+    )
+}
+
+fn make_hint(label: String, kind: InlayHintKind) -> InlayHint {
+    InlayHint {
+        position: Default::default(),
+        label: InlayHintLabel::String(label),
+        kind: Some(kind),
+        text_edits: None,
+        tooltip: None,
+        padding_left: None,
+        padding_right: None,
+        data: None,
+    }
+}
+
+fn element_hints(element: &ElementRc, hints: &mut Vec<(TextSize, InlayHint)>) {
+    let e = element.borrow();
+    for decl in e.property_declarations.values() {
+        let Some(node) = &decl.node else { continue };
+        match (node.kind(), &decl.property_type) {
+            (_, Type::Invalid | Type::InferredProperty | Type::InferredCallback) => {}
+            (SyntaxKind::PropertyDeclaration, ty) => {
+                let Some(decl) = syntax_nodes::PropertyDeclaration::new(node.clone()) else {
+                    continue;
+                };
+                if decl.Type().is_none() && decl.TwoWayBinding().is_some() {
+                    let mut hint = make_hint(format!("<{ty}>"), InlayHintKind::TYPE);
+                    hint.padding_right = Some(true);
+                    hints.push((decl.DeclaredIdentifier().text_range().start(), hint));
+                }
+            }
+            (SyntaxKind::CallbackDeclaration, Type::Callback { args, return_type }) => {
+                let Some(decl) = syntax_nodes::CallbackDeclaration::new(node.clone()) else {
+                    continue;
+                };
+                if decl.Type().next().is_none()
+                    && decl.ReturnType().is_none()
+                    && decl.TwoWayBinding().is_some()
+                {
+                    let args = args.iter().map(|a| a.to_string()).collect::<Vec<_>>().join(", ");
+                    let label = match return_type {
+                        Some(r) => format!("({args}) -> {r}"),
+                        None => format!("({args})"),
+                    };
+                    let end = last_non_whitespace_offset(&decl.DeclaredIdentifier());
+                    hints.push((end, make_hint(label, InlayHintKind::TYPE)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(node) = &e.node {
+        for binding in node.Binding() {
+            if let Some(hint) = unit_hint(&binding_property_type(element, &binding), &binding) {
+                hints.push(hint);
+            }
+        }
+    }
+
+    for c in &e.children {
+        element_hints(c, hints);
+    }
+}
+
+/// A literal `0` can be used for any unit: show the unit that is used
+fn unit_hint(ty: &Type, binding: &syntax_nodes::Binding) -> Option<(TextSize, InlayHint)> {
+    let unit = ty.default_unit()?;
+    let expression = binding.BindingExpression().Expression()?;
+    let token = expression.child_token(SyntaxKind::NumberLiteral)?;
+    if token.text().parse::<f64>().ok()? != 0. {
+        return None;
+    }
+    Some((token.text_range().end(), make_hint(unit.to_string(), InlayHintKind::TYPE)))
+}
+
+fn struct_argument_hints(
+    document_cache: &DocumentCache,
+    call: &syntax_nodes::FunctionCallExpression,
+    hints: &mut Vec<(TextSize, InlayHint)>,
+) {
+    // Avoid the lookup for calls without struct literal
+    if !call.Expression().skip(1).any(|a| a.ObjectLiteral().is_some()) {
+        return;
+    }
+    let arg_types = match lookup_callee(document_cache, call) {
+        Some((_, Expression::CallbackReference(nr, _) | Expression::FunctionReference(nr, _))) => {
+            match nr.ty() {
+                Type::Callback { args, .. } | Type::Function { args, .. } => args,
+                _ => return,
+            }
+        }
+        _ => return,
+    };
+    for (argument, ty) in call.Expression().skip(1).zip(arg_types) {
+        if argument.ObjectLiteral().is_some() && matches!(ty, Type::Struct { .. }) {
+            let mut hint = make_hint(ty.to_string(), InlayHintKind::TYPE);
+            hint.padding_right = Some(true);
+            hints.push((argument.text_range().start(), hint));
+        }
+    }
+}
+
+fn last_non_whitespace_offset(node: &SyntaxNode) -> TextSize {
+    let mut token = node.last_token();
+    while let Some(t) = token.as_ref().filter(|t| t.kind() == SyntaxKind::Whitespace) {
+        token = t.prev_token();
+    }
+    token.map_or(node.text_range().end(), |t| t.text_range().end())
+}
+
+fn binding_property_type(element: &ElementRc, binding: &syntax_nodes::Binding) -> Type {
+    binding.child_text(SyntaxKind::Identifier).map_or(Type::Invalid, |name| {
+        element.borrow().lookup_property(&normalize_identifier(&name)).property_type
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::test::loaded_document_cache;
+
+    #[test]
+    fn test_inlay_hints() {
+        let source = r#"struct Point { x: int, y: int }
+export component Main {
+    callback clicked(Point);
+    property text <=> ti.text;
+    callback moved <=> ti.cursor-position-changed;
+    ti := TextInput {
+        x: 0;
+        width: 10px;
+        edited => { root.clicked({ x: 1, y: 2 }); }
+    }
+}"#;
+        let (dc, uri, _) = loaded_document_cache(source.into());
+        let full = Range::new(Default::default(), lsp_types::Position::new(11, 0));
+        let mut hints = get_inlay_hints(&dc, &uri, &full)
+            .unwrap()
+            .into_iter()
+            .map(|h| {
+                let InlayHintLabel::String(label) = h.label else { panic!() };
+                (h.position.line, h.position.character, label)
+            })
+            .collect::<Vec<_>>();
+        hints.sort();
+        assert_eq!(
+            hints,
+            vec![
+                (3, 13, "<string>".to_string()),
+                (4, 18, "(slint::LogicalPosition)".to_string()),
+                (6, 12, "px".to_string()),
+                (8, 33, "Point".to_string()),
+            ]
+        );
+
+        let range = Range::new(lsp_types::Position::new(6, 0), lsp_types::Position::new(7, 0));
+        let hints = get_inlay_hints(&dc, &uri, &range).unwrap();
+        assert_eq!(hints.len(), 1);
+        assert!(matches!(&hints[0].label, InlayHintLabel::String(l) if l == "px"));
+    }
+}
@@ -0,0 +1,295 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use super::goto::find_definition;
+use super::DocumentCache;
+use crate::util::{map_range, map_token};
+
+#[cfg(target_arch = "wasm32")]
+use crate::wasm_prelude::*;
+
+use i_slint_compiler::parser::{
+    identifier_text, normalize_identifier, SyntaxKind, SyntaxNode, SyntaxToken,
+};
+use lsp_types::{Location, Url};
+use std::collections::HashSet;
+
+/// If the token is the name in a declaration, return the node that `find_definition` would
+/// return for the uses of that name.
+pub(super) fn declaration_at(token: &SyntaxToken) -> Option<SyntaxNode> {
+    let node = token.parent();
+    match node.kind() {
+        SyntaxKind::DeclaredIdentifier => {
+            let parent = node.parent()?;
+            match parent.kind() {
+                // Uses of components resolve to their root element
+                SyntaxKind::Component => parent.child_node(SyntaxKind::Element),
+                SyntaxKind::PropertyDeclaration
+                | SyntaxKind::CallbackDeclaration
+                | SyntaxKind::Function
+                | SyntaxKind::StructDeclaration
+                | SyntaxKind::EnumDeclaration => Some(parent),
+                _ => None,
+            }
+        }
+        // The id of an element
+        SyntaxKind::SubElement => node.child_node(SyntaxKind::Element),
+        _ => None,
+    }
+}
+
+pub(super) fn is_same_node(a: &SyntaxNode, b: &SyntaxNode) -> bool {
+    a.text_range() == b.text_range() && a.source_file.path() == b.source_file.path()
+}
+
+/// Find all the uses of the symbol under the token in all the loaded documents
+pub fn find_references(
+    document_cache: &DocumentCache,
+    token: SyntaxToken,
+    include_declaration: bool,
+) -> Option<Vec<Location>> {
+    if token.kind() != SyntaxKind::Identifier {
+        return None;
+    }
+
+    // Element ids are only visible within their component, where the rename already finds them
+    if let Some(ranges) = super::find_element_id_for_highlight(&token, &token.parent()) {
+        let source_file = &token.source_file;
+        let uri = Url::from_file_path(source_file.path()).ok()?;
+        let declarations = if include_declaration {
+            HashSet::new()
+        } else {
+            let mut node = token.parent();
+            while node.kind() != SyntaxKind::Component {
+                node = node.parent()?;
+            }
+            node.descendants()
+                .filter(|n| n.kind() == SyntaxKind::SubElement)
+                .filter_map(|n| {
+                    n.children_with_tokens()
+                        .filter_map(|c| c.into_token())
+                        .find(|t| t.kind() == SyntaxKind::Identifier)
+                })
+                .map(|t| t.text_range())
+                .collect::<HashSet<_>>()
+        };
+        return Some(
+            ranges
+                .into_iter()
+                .filter(|r| !declarations.contains(r))
+                .map(|r| Location { uri: uri.clone(), range: map_range(source_file, r) })
+                .collect(),
+        );
+    }
+
+    let definition =
+        declaration_at(&token).or_else(|| find_definition(document_cache, token.clone()))?;
+    Some(
+        reference_tokens(document_cache, &token, &definition, include_declaration)
+            .into_iter()
+            .filter_map(|t| {
+                let uri = Url::from_file_path(t.source_file.path()).ok()?;
+                Some(Location { uri, range: map_token(&t)? })
+            })
+            .collect(),
+    )
+}
+
+/// Return the identifiers, in all the loaded documents, that refer to `definition`. `token` is
+/// one of its uses or its declaration.
+pub(super) fn reference_tokens(
+    document_cache: &DocumentCache,
+    token: &SyntaxToken,
+    definition: &SyntaxNode,
+    include_declaration: bool,
+) -> Vec<SyntaxToken> {
+    let names = names_with_aliases(document_cache, normalize_identifier(token.text()));
+
+    let mut result = vec![];
+    for (path, doc) in document_cache.documents.all_file_documents() {
+        if path.starts_with("builtin:/") {
+            continue;
+        }
+        let Some(doc_node) = &doc.node else { continue };
+        let mut current = doc_node.first_token();
+        while let Some(t) = current {
+            current = t.next_token();
+            if t.kind() == SyntaxKind::Identifier && names.contains(&normalize_identifier(t.text()))
+            {
+                let declaration = declaration_at(&t);
+                if include_declaration || declaration.is_none() {
+                    let def = declaration.or_else(|| find_definition(document_cache, t.clone()));
+                    if def.map_or(false, |d| is_same_node(&d, definition)) {
+                        result.push(t);
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Return the name, together with the names it is imported or exported as, through
+/// `import { Foo as Bar }` and `export { Foo as Bar }`, in any of the loaded documents.
+///
+/// This may contain names that refer to another symbol in some documents, so the uses still
+/// need to be resolved.
+fn names_with_aliases(document_cache: &DocumentCache, name: String) -> HashSet<String> {
+    let mut aliases = vec![];
+    for (_, doc) in document_cache.documents.all_file_documents() {
+        let Some(doc_node) = &doc.node else { continue };
+        for import in doc_node.ImportSpecifier() {
+            for id in import.ImportIdentifierList().iter().flat_map(|l| l.ImportIdentifier()) {
+                if let (Some(external), Some(internal)) = (
+                    identifier_text(&id.ExternalName()),
+                    id.InternalName().and_then(|n| identifier_text(&n)),
+                ) {
+                    aliases.push((external, internal));
+                }
+            }
+        }
+        for export in doc_node.ExportsList().flat_map(|l| l.ExportSpecifier()) {
+            if let (Some(internal), Some(external)) = (
+                identifier_text(&export.ExportIdentifier()),
+                export.ExportName().and_then(|n| identifier_text(&n)),
+            ) {
+                aliases.push((internal, external));
+            }
+        }
+    }
+
+    let mut names = HashSet::from([name]);
+    loop {
+        let count = names.len();
+        for (from, to) in &aliases {
+            if names.contains(from) {
+                names.insert(to.clone());
+            }
+        }
+        if names.len() == count {
+            return names;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::test::loaded_document_cache;
+    use crate::language::uri_to_file;
+
+    /// Return the references of the symbol at the `🔺` emoji, as (line, column) of the start
+    fn get_references(file: &str, include_declaration: bool) -> Vec<(u32, u32)> {
+        const CURSOR_EMOJI: char = '🔺';
+        let offset = file.find(CURSOR_EMOJI).unwrap() as u32;
+        let source = file.replace(CURSOR_EMOJI, "");
+        let (dc, uri, _) = loaded_document_cache(source);
+
+        let doc = dc.documents.get_document(&uri_to_file(&uri).unwrap()).unwrap();
+        let token = crate::language::token_at_offset(doc.node.as_ref().unwrap(), offset).unwrap();
+        let mut result = find_references(&dc, token, include_declaration)
+            .unwrap()
+            .into_iter()
+            .map(|l| {
+                assert_eq!(l.uri, uri);
+                (l.range.start.line, l.range.start.character)
+            })
+            .collect::<Vec<_>>();
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn property_references() {
+        let source = r#"component Base {
+    in-out property <int> value;
+    out property <int> double: value * 2;
+}
+export component Main {
+    property <int> value: 12;
+    b := Base {
+        value: root.🔺value;
+    }
+    Text { text: b.value; }
+}"#;
+        assert_eq!(get_references(source, true), vec![(5, 19), (7, 20)]);
+        let base = source.replace("🔺", "").replace("b.value", "b.🔺value");
+        assert_eq!(get_references(&base, true), vec![(1, 26), (2, 31), (7, 8), (9, 19)]);
+        assert_eq!(get_references(&base, false), vec![(2, 31), (7, 8), (9, 19)]);
+    }
+
+    #[test]
+    fn type_references() {
+        let source = r#"struct Point { x: int, y: int }
+global Settings {
+    in-out property <Point> origin;
+}
+component Marker inherits Rectangle {
+    in property <Point> p: Settings.origin;
+}
+export component 🔺Main {
+    Marker { p: Settings.origin; }
+    Marker { }
+}"#;
+        assert_eq!(get_references(source, true), vec![(7, 17)]);
+        let marker = source.replace("🔺", "").replace("component Marker", "component 🔺Marker");
+        assert_eq!(get_references(&marker, true), vec![(4, 10), (8, 4), (9, 4)]);
+        let point = source.replace("🔺", "").replace("<Point> origin", "<🔺Point> origin");
+        assert_eq!(get_references(&point, true), vec![(0, 7), (2, 21), (5, 17)]);
+        let global = source.replace("🔺", "").replace("global Settings", "global 🔺Settings");
+        assert_eq!(get_references(&global, true), vec![(1, 7), (5, 27), (8, 16)]);
+    }
+
+    #[test]
+    fn element_id_references() {
+        let source = r#"export component Main {
+    property <length> w: 🔺r.width;
+    r := Rectangle { }
+    Text { text: r.x / 1px; }
+}"#;
+        assert_eq!(get_references(source, true), vec![(1, 25), (2, 4), (3, 17)]);
+        assert_eq!(get_references(source, false), vec![(1, 25), (3, 17)]);
+    }
+
+    #[test]
+    fn aliased_references() {
+        let mut dc = crate::language::test::empty_document_cache();
+        let base = if cfg!(target_family = "windows") { "c://foo/" } else { "/foo/" };
+        let lib_url = Url::from_file_path(format!("{base}lib.slint")).unwrap();
+        let lib_source = r#"component Foo { }
+export { Foo as Bar }"#;
+        spin_on::spin_on(crate::language::reload_document_impl(
+            None,
+            lib_source.into(),
+            lib_url.clone(),
+            1,
+            &mut dc,
+        ));
+        let main_url = Url::from_file_path(format!("{base}main.slint")).unwrap();
+        let main_source = r#"import { Bar as Baz } from "lib.slint";
+export component Main {
+    Baz { }
+}"#;
+        spin_on::spin_on(crate::language::reload_document_impl(
+            None,
+            main_source.into(),
+            main_url.clone(),
+            1,
+            &mut dc,
+        ));
+
+        let doc = dc.documents.get_document(&uri_to_file(&lib_url).unwrap()).unwrap();
+        let token = crate::language::token_at_offset(doc.node.as_ref().unwrap(), 11).unwrap();
+        assert_eq!(token.text(), "Foo");
+        let mut result = find_references(&dc, token, false)
+            .unwrap()
+            .into_iter()
+            .map(|l| (l.uri == main_url, l.range.start.line, l.range.start.character))
+            .collect::<Vec<_>>();
+        result.sort();
+        assert_eq!(
+            result,
+            vec![(false, 1, 9), (false, 1, 16), (true, 0, 9), (true, 0, 16), (true, 2, 4)]
+        );
+    }
+}
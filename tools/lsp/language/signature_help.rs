@@ -0,0 +1,270 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use super::goto::lookup_expression;
+use super::hover::find_declaration_node;
+use super::DocumentCache;
+
+use i_slint_compiler::expression_tree::{BuiltinMacroFunction, Expression};
+use i_slint_compiler::langtype::Type;
+use i_slint_compiler::lookup::LookupResult;
+use i_slint_compiler::parser::{
+    doc_comment, syntax_nodes, NodeOrToken, SyntaxKind, SyntaxNode, SyntaxToken, TextSize,
+};
+use lsp_types::{
+    Documentation, ParameterInformation, ParameterLabel, SignatureHelp, SignatureInformation,
+};
+
+/// The signature of the function being called, as shown to the user
+struct Signature {
+    name: String,
+    parameters: Vec<String>,
+    return_type: Option<String>,
+    /// The last parameter can be repeated
+    variadic: bool,
+    documentation: Option<String>,
+}
+
+/// Return the signature of the function call whose arguments contain the `offset`
+pub fn get_signature_help(
+    document_cache: &DocumentCache,
+    token: SyntaxToken,
+    offset: u32,
+) -> Option<SignatureHelp> {
+    let offset = TextSize::from(offset);
+    let mut node = token.parent();
+    loop {
+        if matches!(node.kind(), SyntaxKind::FunctionCallExpression | SyntaxKind::AtTr) {
+            if let Some(active_parameter) = active_parameter(&node, offset) {
+                let signature = if node.kind() == SyntaxKind::AtTr {
+                    Some(tr_signature())
+                } else {
+                    call_signature(document_cache, &node)
+                };
+                if let Some(signature) = signature {
+                    return Some(make_signature_help(signature, active_parameter));
+                }
+            }
+        }
+        node = node.parent()?;
+    }
+}
+
+/// If the offset is within the parentheses of the call, return the index of the argument
+/// the offset is in.
+fn active_parameter(node: &SyntaxNode, offset: TextSize) -> Option<u32> {
+    let mut in_arguments = false;
+    let mut index = 0;
+    for child in node.children_with_tokens() {
+        let NodeOrToken::Token(t) = child else { continue };
+        match t.kind() {
+            SyntaxKind::LParent if !in_arguments => {
+                if offset <= t.text_range().start() {
+                    return None;
+                }
+                in_arguments = true;
+            }
+            SyntaxKind::Comma if in_arguments && t.text_range().start() < offset => index += 1,
+            SyntaxKind::RParent if in_arguments => {
+                return (offset <= t.text_range().start()).then_some(index);
+            }
+            _ => {}
+        }
+    }
+    // The closing parenthesis is not typed yet
+    in_arguments.then_some(index)
+}
+
+/// Lookup the function called by a call expression, and return it with the token of its name
+pub(super) fn lookup_callee(
+    document_cache: &DocumentCache,
+    call: &syntax_nodes::FunctionCallExpression,
+) -> Option<(SyntaxToken, Expression)> {
+    let callee = call.Expression().next()?;
+    let qn = callee.QualifiedName()?;
+    let token = qn
+        .children_with_tokens()
+        .filter_map(|t| t.into_token())
+        .filter(|t| t.kind() == SyntaxKind::Identifier)
+        .last()?;
+    match lookup_expression(document_cache, &token, &qn)? {
+        LookupResult::Expression { expression, .. } => Some((token, expression)),
+        _ => None,
+    }
+}
+
+fn call_signature(document_cache: &DocumentCache, node: &SyntaxNode) -> Option<Signature> {
+    let call = syntax_nodes::FunctionCallExpression::new(node.clone())?;
+    let (token, expression) = lookup_callee(document_cache, &call)?;
+    let name = token.text().to_string();
+    match expression {
+        Expression::CallbackReference(nr, _) | Expression::FunctionReference(nr, _) => {
+            let declaration = find_declaration_node(&nr.element(), nr.name());
+            let mut signature = signature_from_type(name, &nr.ty())?;
+            if let Some(function) =
+                declaration.as_ref().and_then(|d| syntax_nodes::Function::new(d.clone()))
+            {
+                // Functions have named arguments
+                signature.parameters = function
+                    .ArgumentDeclaration()
+                    .map(|a| {
+                        let name = a.DeclaredIdentifier().text().to_string();
+                        format!("{}: {}", name.trim(), a.Type().text().to_string().trim())
+                    })
+                    .collect();
+            }
+            signature.documentation = declaration.as_ref().and_then(doc_comment);
+            Some(signature)
+        }
+        Expression::BuiltinFunctionReference(f, _) => signature_from_type(name, &f.ty()),
+        Expression::MemberFunction { member, .. } => match *member {
+            Expression::BuiltinFunctionReference(f, _) => {
+                // The first argument is the object the function is called on
+                let mut signature = signature_from_type(name, &f.ty())?;
+                if !signature.parameters.is_empty() {
+                    signature.parameters.remove(0);
+                }
+                Some(signature)
+            }
+            _ => None,
+        },
+        Expression::BuiltinMacroReference(m, _) => Some(macro_signature(name, m)),
+        _ => None,
+    }
+}
+
+fn signature_from_type(name: String, ty: &Type) -> Option<Signature> {
+    let (args, return_type) = match ty {
+        Type::Callback { args, return_type } => (args, return_type.as_deref()),
+        Type::Function { args, return_type } => (args, Some(&**return_type)),
+        _ => return None,
+    };
+    Some(Signature {
+        name,
+        parameters: args.iter().map(|a| a.to_string()).collect(),
+        return_type: return_type.filter(|r| **r != Type::Void).map(|r| r.to_string()),
+        variadic: false,
+        documentation: None,
+    })
+}
+
+/// The macros don't have a type, so describe them by hand
+fn macro_signature(name: String, m: BuiltinMacroFunction) -> Signature {
+    let (parameters, return_type, variadic): (&[&str], _, _) = match m {
+        BuiltinMacroFunction::Min | BuiltinMacroFunction::Max => (&["a", "b"], None, true),
+        BuiltinMacroFunction::Mod => (&["a", "b"], None, false),
+        BuiltinMacroFunction::CubicBezier => {
+            (&["x1: float", "y1: float", "x2: float", "y2: float"], Some("easing"), false)
+        }
+        BuiltinMacroFunction::Rgb => {
+            (&["red: int", "green: int", "blue: int", "alpha: float"], Some("color"), false)
+        }
+        BuiltinMacroFunction::Debug => (&["value"], None, true),
+    };
+    Signature {
+        name,
+        parameters: parameters.iter().map(|p| p.to_string()).collect(),
+        return_type: return_type.map(|r| r.to_string()),
+        variadic,
+        documentation: None,
+    }
+}
+
+fn tr_signature() -> Signature {
+    Signature {
+        name: "@tr".into(),
+        parameters: vec!["text: string".into(), "argument".into()],
+        return_type: Some("string".into()),
+        variadic: true,
+        documentation: Some(
+            "Translates the text. `{}` or `{0}`, `{1}`, ... in the text are replaced by the arguments."
+                .into(),
+        ),
+    }
+}
+
+fn make_signature_help(signature: Signature, active_parameter: u32) -> SignatureHelp {
+    let mut parameters = signature.parameters;
+    if signature.variadic {
+        if let Some(last) = parameters.last_mut() {
+            *last += "...";
+        }
+    }
+    let mut label = format!("{}({})", signature.name, parameters.join(", "));
+    if let Some(return_type) = &signature.return_type {
+        label += &format!(" -> {return_type}");
+    }
+    let active_parameter = if signature.variadic {
+        active_parameter.min(parameters.len().saturating_sub(1) as u32)
+    } else {
+        active_parameter
+    };
+    SignatureHelp {
+        signatures: vec![SignatureInformation {
+            label,
+            documentation: signature.documentation.map(Documentation::String),
+            parameters: Some(
+                parameters
+                    .into_iter()
+                    .map(|p| ParameterInformation {
+                        label: ParameterLabel::Simple(p),
+                        documentation: None,
+                    })
+                    .collect(),
+            ),
+            active_parameter: None,
+        }],
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::test::loaded_document_cache;
+    use crate::language::uri_to_file;
+
+    /// Return the label and active parameter for the cursor at the position of the `🔺` emoji
+    fn get_signature(file: &str) -> Option<(String, u32)> {
+        const CURSOR_EMOJI: char = '🔺';
+        let offset = file.find(CURSOR_EMOJI).unwrap() as u32;
+        let source = file.replace(CURSOR_EMOJI, "");
+        let (dc, uri, _) = loaded_document_cache(source);
+
+        let doc = dc.documents.get_document(&uri_to_file(&uri).unwrap()).unwrap();
+        let token = crate::language::token_at_offset(doc.node.as_ref().unwrap(), offset).unwrap();
+        let help = get_signature_help(&dc, token, offset)?;
+        assert_eq!(help.signatures.len(), 1);
+        Some((help.signatures[0].label.clone(), help.active_parameter.unwrap()))
+    }
+
+    #[test]
+    fn test_signature_help() {
+        let source = r#"
+export component Main {
+    callback clicked(int, string) -> bool;
+    /// Adds things
+    pure function add(a: int, b: int) -> int { return a + b; }
+    property <color> c: #ff0000;
+    property <int> v: add(1, 🔺2);
+}"#;
+        assert_eq!(get_signature(source), Some(("add(a: int, b: int) -> int".into(), 1)));
+        let s = source.replace("add(1, 🔺2)", "add(🔺)");
+        assert_eq!(get_signature(&s), Some(("add(a: int, b: int) -> int".into(), 0)));
+        let s = source.replace("add(1, 🔺2)", "add(1, 2)🔺");
+        assert_eq!(get_signature(&s), None);
+        let s = source.replace("add(1, 🔺2)", "root.clicked(1, 🔺\"x\") ? 1 : 0");
+        assert_eq!(get_signature(&s), Some(("clicked(int, string) -> bool".into(), 1)));
+        let s = source.replace("add(1, 🔺2)", "Math.abs(add(1, 2) + 🔺3)");
+        assert_eq!(get_signature(&s), Some(("abs(float) -> float".into(), 0)));
+        let s = source.replace("add(1, 🔺2)", "max(1, 2, 🔺3)");
+        assert_eq!(get_signature(&s), Some(("max(a, b...)".into(), 1)));
+        let s = source
+            .replace("property <int> v: add(1, 🔺2)", "property <color> v: c.mix(#000, 🔺0.5)");
+        assert_eq!(get_signature(&s), Some(("mix(color, float) -> color".into(), 1)));
+        let s = source
+            .replace("property <int> v: add(1, 🔺2)", "property <string> v: @tr(\"{}\", 🔺42)");
+        assert_eq!(get_signature(&s), Some(("@tr(text: string, argument...) -> string".into(), 1)));
+    }
+}
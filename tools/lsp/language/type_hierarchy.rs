@@ -0,0 +1,171 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use super::goto::find_definition;
+use super::references::{declaration_at, is_same_node};
+use super::DocumentCache;
+use crate::util::{map_node, map_token};
+
+#[cfg(target_arch = "wasm32")]
+use crate::wasm_prelude::*;
+
+use i_slint_compiler::langtype::ElementType;
+use i_slint_compiler::object_tree::{Component, ElementRc};
+use i_slint_compiler::parser::{SyntaxKind, SyntaxToken};
+use lsp_types::{SymbolKind, TypeHierarchyItem, Url};
+use std::rc::Rc;
+
+/// Return the item for the component under the token
+pub fn prepare_type_hierarchy(
+    document_cache: &DocumentCache,
+    token: SyntaxToken,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let component = component_at(document_cache, token)?;
+    Some(vec![make_item(&component, None)?])
+}
+
+/// Return the component the component of the item inherits from (`token` is the name of the item)
+pub fn supertypes(
+    document_cache: &DocumentCache,
+    token: SyntaxToken,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let component = component_at(document_cache, token)?;
+    let base = component.root_element.borrow().base_type.clone();
+    Some(match base {
+        ElementType::Component(base) => make_item(&base, None).into_iter().collect(),
+        _ => vec![],
+    })
+}
+
+/// Return the components that inherit from, or that contain an instance of, the component of
+/// the item (`token` is the name of the item)
+pub fn subtypes(
+    document_cache: &DocumentCache,
+    token: SyntaxToken,
+) -> Option<Vec<TypeHierarchyItem>> {
+    let component = component_at(document_cache, token)?;
+    let node = component.node.clone()?;
+    let is_target = |element: &ElementRc| {
+        matches!(&element.borrow().base_type, ElementType::Component(c)
+            if c.node.as_ref().map_or(false, |n| is_same_node(n, &node)))
+    };
+
+    let mut result = vec![];
+    for (path, doc) in document_cache.documents.all_file_documents() {
+        if path.starts_with("builtin:/") {
+            continue;
+        }
+        for c in &doc.inner_components {
+            let detail = if is_target(&c.root_element) {
+                format!("inherits {}", component.id)
+            } else if c.root_element.borrow().children.iter().any(|e| contains(e, &is_target)) {
+                format!("uses {}", component.id)
+            } else {
+                continue;
+            };
+            result.extend(make_item(c, Some(detail)));
+        }
+    }
+    Some(result)
+}
+
+/// True if the element or one of its children matches the predicate
+fn contains(element: &ElementRc, predicate: &impl Fn(&ElementRc) -> bool) -> bool {
+    predicate(element) || element.borrow().children.iter().any(|c| contains(c, predicate))
+}
+
+/// The component whose name, or whose use as an element, is under the token
+fn component_at(document_cache: &DocumentCache, token: SyntaxToken) -> Option<Rc<Component>> {
+    if token.kind() != SyntaxKind::Identifier {
+        return None;
+    }
+    let root_element = declaration_at(&token).or_else(|| find_definition(document_cache, token))?;
+    let node = root_element.parent().filter(|n| n.kind() == SyntaxKind::Component)?;
+    let doc = document_cache.documents.get_document(node.source_file.path())?;
+    doc.inner_components
+        .iter()
+        .find(|c| c.node.as_ref().map_or(false, |n| is_same_node(n, &node)))
+        .cloned()
+}
+
+fn make_item(component: &Component, detail: Option<String>) -> Option<TypeHierarchyItem> {
+    let node = component.node.as_ref()?;
+    let name =
+        node.child_node(SyntaxKind::DeclaredIdentifier)?.child_token(SyntaxKind::Identifier)?;
+    Some(TypeHierarchyItem {
+        name: component.id.clone(),
+        kind: if component.is_global() { SymbolKind::OBJECT } else { SymbolKind::CLASS },
+        tags: None,
+        detail,
+        uri: Url::from_file_path(node.source_file.path()).ok()?,
+        range: map_node(node)?,
+        selection_range: map_token(&name)?,
+        data: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::test::loaded_document_cache;
+    use crate::language::uri_to_file;
+
+    const SOURCE: &str = r#"component Base inherits Rectangle { }
+component Derived inherits Base { }
+component User {
+    VerticalLayout {
+        if true: Base { }
+    }
+}
+export component Main inherits Derived {
+    User { }
+}"#;
+
+    fn token_at(dc: &DocumentCache, uri: &Url, offset: usize) -> SyntaxToken {
+        let doc = dc.documents.get_document(&uri_to_file(uri).unwrap()).unwrap();
+        crate::language::token_at_offset(doc.node.as_ref().unwrap(), offset as u32).unwrap()
+    }
+
+    fn names(items: Option<Vec<TypeHierarchyItem>>) -> Vec<(String, Option<String>)> {
+        let mut names = items.unwrap().into_iter().map(|i| (i.name, i.detail)).collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_type_hierarchy() {
+        let (dc, uri, _) = loaded_document_cache(SOURCE.into());
+        let base = token_at(&dc, &uri, SOURCE.find("Base").unwrap());
+        let items = prepare_type_hierarchy(&dc, base.clone()).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "Base");
+        assert_eq!(items[0].kind, SymbolKind::CLASS);
+        assert_eq!(items[0].selection_range.start, lsp_types::Position::new(0, 10));
+
+        // The builtin Rectangle is not in the hierarchy
+        assert_eq!(names(supertypes(&dc, base.clone())), vec![]);
+        assert_eq!(
+            names(subtypes(&dc, base)),
+            vec![
+                ("Derived".into(), Some("inherits Base".into())),
+                ("User".into(), Some("uses Base".into()))
+            ]
+        );
+
+        // From the use of a component in the `inherits`
+        let derived = token_at(&dc, &uri, SOURCE.find("inherits Derived").unwrap() + 9);
+        assert_eq!(
+            names(prepare_type_hierarchy(&dc, derived.clone())),
+            vec![("Derived".into(), None)]
+        );
+        assert_eq!(names(supertypes(&dc, derived.clone())), vec![("Base".into(), None)]);
+        assert_eq!(
+            names(subtypes(&dc, derived)),
+            vec![("Main".into(), Some("inherits Derived".into()))]
+        );
+
+        // From the use of a component as an element
+        let user = token_at(&dc, &uri, SOURCE.find("User { }").unwrap());
+        assert_eq!(names(subtypes(&dc, user)), vec![("Main".into(), Some("uses User".into()))]);
+    }
+}
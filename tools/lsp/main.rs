@@ -364,7 +364,13 @@ fn main_loop(connection: Connection, init_param: InitializeParams) -> Result<()>
     });
 
     let mut futures = Vec::<Pin<Box<dyn Future<Output = Result<()>>>>>::new();
-    let mut first_future = Box::pin(load_configuration(&ctx));
+    let mut first_future = Box::pin(async {
+        // Not being able to register a capability must not prevent the configuration from loading
+        if let Err(e) = register_dynamic_capabilities(&ctx).await {
+            eprintln!("Error registering the dynamic capabilities: {e}");
+        }
+        load_configuration(&ctx).await
+    });
 
     // We are waiting in this loop for two kind of futures:
     //  - The compiler future should always be ready immediately because we do not set a callback to load files
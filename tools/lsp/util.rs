@@ -210,6 +210,19 @@ fn lookup_expression_context(mut n: SyntaxNode) -> Option<ExpressionContextInfo>
             break (element, prop_name, false);
         }
         match n.kind() {
+            SyntaxKind::CallbackDeclaration => {
+                // The `<=>` of a callback alias
+                let prop_name = i_slint_compiler::parser::identifier_text(
+                    &n.child_node(SyntaxKind::DeclaredIdentifier)?,
+                )?;
+                let element = syntax_nodes::Element::new(n.parent()?)?;
+                break (element, prop_name, false);
+            }
+            SyntaxKind::TwoWayBinding
+                if n.parent().map_or(false, |p| p.kind() == SyntaxKind::CallbackDeclaration) =>
+            {
+                n = n.parent()?
+            }
             SyntaxKind::Binding | SyntaxKind::TwoWayBinding | SyntaxKind::CallbackConnection => {
                 let parent = n.parent()?;
                 if parent.kind() == SyntaxKind::PropertyAnimation {
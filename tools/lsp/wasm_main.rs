@@ -243,7 +243,6 @@ fn drop(&mut self) {
     pub type HighlightInPreviewFunction;
 
     // Make console.log available:
-    #[allow(unused)]
     #[wasm_bindgen(js_namespace = console)]
     fn log(s: &str);
 }
@@ -386,6 +385,17 @@ pub fn handle_request(&self, _id: JsValue, method: String, params: JsValue) -> j
         })
     }
 
+    /// To be called when the client sent the `initialized` notification
+    #[wasm_bindgen]
+    pub async fn startup(&self) -> JsResult<()> {
+        let guard = self.reentry_guard.clone();
+        let _lock = ReentryGuard::lock(guard).await;
+        if let Err(e) = language::register_dynamic_capabilities(&self.ctx).await {
+            log(&format!("Error registering the dynamic capabilities: {e}"));
+        }
+        language::load_configuration(&self.ctx).await.map_err(|e| JsError::new(&e.to_string()))
+    }
+
     #[wasm_bindgen]
     pub async fn reload_config(&self) -> JsResult<()> {
         let guard = self.reentry_guard.clone();
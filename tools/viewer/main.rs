@@ -4,6 +4,9 @@
 #![doc = include_str!("README.md")]
 
 use i_slint_core::model::{Model, ModelRc};
+use i_slint_core::software_renderer::{
+    MinimalSoftwareWindow, PremultipliedRgbaColor, RepaintBufferType,
+};
 use i_slint_core::SharedVector;
 use slint_interpreter::{
     ComponentDefinition, ComponentHandle, ComponentInstance, SharedString, Value,
@@ -13,6 +16,7 @@
 use std::sync::{Arc, Mutex};
 
 use clap::Parser;
+use i_slint_compiler::diagnostics::{BuildDiagnostics, DiagnosticFormat};
 use itertools::Itertools;
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
@@ -58,6 +62,16 @@ struct Cli {
     #[arg(long, value_names(&["callback", "handler"]), number_of_values = 2, action)]
     on: Vec<String>,
 
+    /// Render one frame with the software renderer, without opening a window, write it
+    /// as a PNG to this file and exit
+    #[arg(long, name = "screenshot file", action)]
+    screenshot: Option<std::path::PathBuf>,
+
+    /// The size of the screenshot in pixels, as WIDTHxHEIGHT.
+    /// Defaults to the preferred size of the component
+    #[arg(long, name = "size", value_parser = parse_size, requires = "screenshot file", action)]
+    size: Option<(u32, u32)>,
+
     #[cfg(feature = "gettext")]
     /// Translation domain
     #[arg(long = "translation-domain", action)]
@@ -67,6 +81,10 @@ struct Cli {
     /// Translation directory where the translation files are searched for
     #[arg(long = "translation-dir", action)]
     translation_dir: Option<std::path::PathBuf>,
+
+    /// Set the format of the diagnostics printed on stderr ('human' or 'json')
+    #[arg(long = "diagnostic-format", default_value = "human", action)]
+    diagnostic_format: DiagnosticFormat,
 }
 
 thread_local! {static CURRENT_INSTANCE: std::cell::RefCell<Option<ComponentInstance>> = Default::default();}
@@ -81,6 +99,13 @@ fn main() -> Result<()> {
         std::process::exit(-1);
     }
 
+    if args.auto_reload && args.screenshot.is_some() {
+        eprintln!("Cannot pass both --auto-reload and --screenshot");
+        std::process::exit(-1);
+    }
+
+    let screenshot_window = args.screenshot.as_ref().map(|_| init_screenshot_platform());
+
     if let Some(backend) = &args.backend {
         std::env::set_var("SLINT_BACKEND", backend);
     }
@@ -97,7 +122,7 @@ fn main() -> Result<()> {
     let mut compiler = init_compiler(&args, fswatcher);
 
     let c = spin_on::spin_on(compiler.build_from_path(args.path));
-    slint_interpreter::print_diagnostics(compiler.diagnostics());
+    print_diagnostics(compiler.diagnostics(), args.diagnostic_format);
 
     let c = match c {
         Some(c) => c,
@@ -116,7 +141,11 @@ fn main() -> Result<()> {
         CURRENT_INSTANCE.with(|current| current.replace(Some(component.clone_strong())));
     }
 
-    component.run().unwrap();
+    if let (Some(window), Some(screenshot_path)) = (&screenshot_window, &args.screenshot) {
+        take_screenshot(&component, window, args.size, screenshot_path)?;
+    } else {
+        component.run().unwrap();
+    }
 
     if let Some(data_path) = args.save_data {
         let mut obj = serde_json::Map::new();
@@ -178,6 +207,9 @@ fn init_compiler(
     );
     if let Some(style) = &args.style {
         compiler.set_style(style.clone());
+    } else if args.screenshot.is_some() && std::env::var_os("SLINT_STYLE").is_none() {
+        // The native style would be the Qt style, which the software renderer can't draw
+        compiler.set_style("fluent".into());
     }
     if let Some(watcher) = fswatcher {
         notify::Watcher::watch(
@@ -213,6 +245,14 @@ fn init_compiler(
     compiler
 }
 
+fn print_diagnostics(diagnostics: &[slint_interpreter::Diagnostic], format: DiagnosticFormat) {
+    let mut build_diagnostics = BuildDiagnostics::default();
+    for d in diagnostics {
+        build_diagnostics.push_compiler_error(d.clone());
+    }
+    build_diagnostics.print_with_format(format);
+}
+
 fn init_dialog(instance: &ComponentInstance) {
     for cb in instance.definition().callbacks() {
         let exit_code = match cb.as_str() {
@@ -262,7 +302,7 @@ fn start_fswatch_thread(args: Cli) -> Result<Arc<Mutex<notify::RecommendedWatche
 async fn reload(args: Cli, fswatcher: Arc<Mutex<notify::RecommendedWatcher>>) {
     let mut compiler = init_compiler(&args, Some(fswatcher));
     let c = compiler.build_from_path(&args.path).await;
-    slint_interpreter::print_diagnostics(compiler.diagnostics());
+    print_diagnostics(compiler.diagnostics(), args.diagnostic_format);
 
     if let Some(c) = c {
         CURRENT_INSTANCE.with(|current| {
@@ -288,6 +328,77 @@ async fn reload(args: Cli, fswatcher: Arc<Mutex<notify::RecommendedWatcher>>) {
     PENDING_EVENTS.fetch_sub(1, Ordering::SeqCst);
 }
 
+fn parse_size(size: &str) -> std::result::Result<(u32, u32), String> {
+    size.split_once('x')
+        .and_then(|(w, h)| Some((w.trim().parse().ok()?, h.trim().parse().ok()?)))
+        .filter(|(w, h)| *w > 0 && *h > 0)
+        .ok_or_else(|| format!("Invalid size '{}', expected WIDTHxHEIGHT", size))
+}
+
+/// A platform with a single window rendered by the software renderer in memory, so that the
+/// screenshots can be taken without a display or a GPU
+struct ScreenshotPlatform(std::rc::Rc<MinimalSoftwareWindow>);
+
+impl i_slint_core::platform::Platform for ScreenshotPlatform {
+    fn create_window_adapter(
+        &self,
+    ) -> std::result::Result<
+        std::rc::Rc<dyn i_slint_core::window::WindowAdapter>,
+        i_slint_core::platform::PlatformError,
+    > {
+        Ok(self.0.clone())
+    }
+}
+
+fn init_screenshot_platform() -> std::rc::Rc<MinimalSoftwareWindow> {
+    let window = MinimalSoftwareWindow::new(RepaintBufferType::NewBuffer);
+    i_slint_core::platform::set_platform(Box::new(ScreenshotPlatform(window.clone())))
+        .expect("The platform was already initialized");
+    window
+}
+
+/// Render one frame of the component and write it as a PNG file
+fn take_screenshot(
+    instance: &ComponentInstance,
+    window: &MinimalSoftwareWindow,
+    size: Option<(u32, u32)>,
+    path: &std::path::Path,
+) -> Result<()> {
+    let (width, height) = size.unwrap_or_else(|| {
+        use i_slint_core::layout::Orientation;
+        let component = i_slint_core::window::WindowInner::from_pub(instance.window()).component();
+        let component = i_slint_core::item_tree::ItemTreeRc::borrow_pin(&component);
+        let preferred = |o| component.as_ref().layout_info(o).preferred_bounded().ceil() as u32;
+        (preferred(Orientation::Horizontal).max(1), preferred(Orientation::Vertical).max(1))
+    });
+    window.set_size(i_slint_core::api::PhysicalSize::new(width, height));
+    instance.show()?;
+    i_slint_core::platform::update_timers_and_animations();
+
+    let mut buffer = vec![PremultipliedRgbaColor::default(); width as usize * height as usize];
+    window.request_redraw();
+    window.draw_if_needed(|renderer| {
+        renderer.render(&mut buffer, width as usize);
+    });
+    instance.hide()?;
+
+    let image = image::RgbaImage::from_fn(width, height, |x, y| {
+        let pixel = buffer[(y * width + x) as usize];
+        let unpremultiply = |c: u8| match pixel.alpha {
+            0 => 0,
+            a => ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8,
+        };
+        image::Rgba([
+            unpremultiply(pixel.red),
+            unpremultiply(pixel.green),
+            unpremultiply(pixel.blue),
+            pixel.alpha,
+        ])
+    });
+    image.save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
+
 fn load_data(
     c: &ComponentDefinition,
     instance: &ComponentInstance,
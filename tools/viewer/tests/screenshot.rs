@@ -0,0 +1,43 @@
+// Copyright © SixtyFPS GmbH <info@slint.dev>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-Royalty-free-1.1 OR LicenseRef-Slint-commercial
+
+use std::process::Command;
+
+#[test]
+fn screenshot_without_display() {
+    let dir = std::env::temp_dir().join(format!("slint-viewer-screenshot-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("test.slint");
+    std::fs::write(
+        &source,
+        r#"
+import { Button } from "std-widgets.slint";
+export component Test inherits Window {
+    background: #ff0000;
+    Button { x: 10px; y: 10px; text: "Hello"; }
+}
+"#,
+    )
+    .unwrap();
+    let screenshot = dir.join("test.png");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_slint-viewer"))
+        .arg(&source)
+        .arg("--screenshot")
+        .arg(&screenshot)
+        .args(["--size", "120x50"])
+        .env_remove("DISPLAY")
+        .env_remove("WAYLAND_DISPLAY")
+        .env_remove("SLINT_STYLE")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let image = image::open(&screenshot).unwrap().into_rgba8();
+    assert_eq!(image.dimensions(), (120, 50));
+    assert_eq!(image.get_pixel(1, 1).0, [0xff, 0, 0, 0xff]);
+    // The button is drawn over the background
+    assert_ne!(image.get_pixel(20, 20).0, [0xff, 0, 0, 0xff]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}